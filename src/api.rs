@@ -0,0 +1,597 @@
+//! Service traits for the API clients
+//!
+//! Each client implements the matching trait, so application code can depend
+//! on `Arc<dyn TtsApi>` or a generic `impl BatchApi` instead of the concrete
+//! client, and swap in a fake in unit tests. With the `mock` feature,
+//! [`mock`](crate::mock) provides configurable in-memory fakes for every
+//! trait.
+//!
+//! ```no_run
+//! use hume::api::TtsApi;
+//!
+//! async fn voice_names(tts: &dyn TtsApi) -> hume::Result<Vec<String>> {
+//!     let voices = tts.list_voices(None).await?;
+//!     Ok(voices.voices.into_iter().map(|voice| voice.name).collect())
+//! }
+//! ```
+
+use crate::{
+    core::{error::Result, request::RequestOptions},
+    evi::{
+        chat::{ChatClient, ChatSocket},
+        configs::{ConfigsClient, CreateConfigRequest, UpdateConfigRequest},
+        models::*,
+        prompts::{CreatePromptRequest, CreatePromptVersionRequest, PromptsClient, UpdatePromptRequest},
+        tools::{CreateToolRequest, ToolsClient, UpdateToolRequest},
+        voices::{CreateCustomVoiceRequest, UpdateCustomVoiceRequest, VoicesClient},
+    },
+    expression_measurement::{
+        batch::BatchClient,
+        models::{BatchJob, BatchJobRequest, JobArtifacts, ListJobsResponse, Models, PredictionResults},
+        stream::{StreamClient, StreamSocket},
+    },
+    tts::{
+        models::{TtsRequest, TtsResponse, TtsStreamRequest, TtsStreamResponse, VoicesResponse},
+        TtsClient,
+    },
+};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::Stream;
+use std::pin::Pin;
+
+/// Stream of decoded items or raw chunks from a streaming endpoint
+pub type ApiStream<T> = Pin<Box<dyn Stream<Item = Result<T>> + Send>>;
+
+/// Text-to-Speech operations, implemented by [`TtsClient`]
+#[async_trait]
+pub trait TtsApi: Send + Sync {
+    /// Synthesize speech from text and return audio data
+    async fn synthesize(&self, request: TtsRequest, options: Option<RequestOptions>) -> Result<TtsResponse>;
+
+    /// Synthesize speech and return it as raw audio bytes
+    async fn synthesize_file(&self, request: TtsRequest, options: Option<RequestOptions>) -> Result<Bytes>;
+
+    /// Stream synthesis as JSON chunks
+    async fn stream_json(
+        &self,
+        request: TtsStreamRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<ApiStream<TtsStreamResponse>>;
+
+    /// Stream synthesis as raw audio chunks
+    async fn stream_file(&self, request: TtsStreamRequest, options: Option<RequestOptions>) -> Result<ApiStream<Bytes>>;
+
+    /// List available voices
+    async fn list_voices(&self, options: Option<RequestOptions>) -> Result<VoicesResponse>;
+}
+
+#[async_trait]
+impl TtsApi for TtsClient {
+    async fn synthesize(&self, request: TtsRequest, options: Option<RequestOptions>) -> Result<TtsResponse> {
+        TtsClient::synthesize(self, request, options).await
+    }
+
+    async fn synthesize_file(&self, request: TtsRequest, options: Option<RequestOptions>) -> Result<Bytes> {
+        TtsClient::synthesize_file(self, request, options).await
+    }
+
+    async fn stream_json(
+        &self,
+        request: TtsStreamRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<ApiStream<TtsStreamResponse>> {
+        TtsClient::stream_json(self, request, options).await
+    }
+
+    async fn stream_file(&self, request: TtsStreamRequest, options: Option<RequestOptions>) -> Result<ApiStream<Bytes>> {
+        TtsClient::stream_file(self, request, options).await
+    }
+
+    async fn list_voices(&self, options: Option<RequestOptions>) -> Result<VoicesResponse> {
+        TtsClient::list_voices(self, options).await
+    }
+}
+
+/// Batch expression measurement operations, implemented by [`BatchClient`]
+#[async_trait]
+pub trait BatchApi: Send + Sync {
+    /// List batch jobs
+    async fn list_jobs(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ListJobsResponse>;
+
+    /// Create a batch job and return its details
+    async fn create_job(&self, request: BatchJobRequest, options: Option<RequestOptions>) -> Result<BatchJob>;
+
+    /// Get job details
+    async fn get_job(&self, job_id: &str, options: Option<RequestOptions>) -> Result<BatchJob>;
+
+    /// Get job predictions
+    async fn get_predictions(&self, job_id: &str, options: Option<RequestOptions>) -> Result<PredictionResults>;
+
+    /// Get job artifacts
+    async fn get_artifacts(&self, job_id: &str, options: Option<RequestOptions>) -> Result<JobArtifacts>;
+}
+
+#[async_trait]
+impl BatchApi for BatchClient {
+    async fn list_jobs(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ListJobsResponse> {
+        BatchClient::list_jobs(self, limit, offset, options).await
+    }
+
+    async fn create_job(&self, request: BatchJobRequest, options: Option<RequestOptions>) -> Result<BatchJob> {
+        BatchClient::create_job(self, request, options).await
+    }
+
+    async fn get_job(&self, job_id: &str, options: Option<RequestOptions>) -> Result<BatchJob> {
+        BatchClient::get_job(self, job_id, options).await
+    }
+
+    async fn get_predictions(&self, job_id: &str, options: Option<RequestOptions>) -> Result<PredictionResults> {
+        BatchClient::get_predictions(self, job_id, options).await
+    }
+
+    async fn get_artifacts(&self, job_id: &str, options: Option<RequestOptions>) -> Result<JobArtifacts> {
+        BatchClient::get_artifacts(self, job_id, options).await
+    }
+}
+
+/// Streaming expression measurement, implemented by [`StreamClient`]
+#[async_trait]
+pub trait ExpressionStreamApi: Send + Sync {
+    /// Connect to the streaming WebSocket
+    async fn connect(&self, models: Models) -> Result<StreamSocket>;
+}
+
+#[async_trait]
+impl ExpressionStreamApi for StreamClient {
+    async fn connect(&self, models: Models) -> Result<StreamSocket> {
+        StreamClient::connect(self, models).await
+    }
+}
+
+/// EVI chat sessions and history, implemented by [`ChatClient`]
+#[async_trait]
+pub trait EviChatApi: Send + Sync {
+    /// Connect to a chat session
+    async fn connect(
+        &self,
+        config_id: Option<String>,
+        config_version: Option<u32>,
+        resumed_chat_group_id: Option<String>,
+    ) -> Result<ChatSocket>;
+
+    /// List chat history
+    async fn list_chats(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        ascending_order: Option<bool>,
+    ) -> Result<ReturnPagedChats>;
+
+    /// Get a specific chat
+    async fn get_chat(&self, chat_id: &str) -> Result<Chat>;
+
+    /// List chat groups
+    async fn list_chat_groups(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        ascending_order: Option<bool>,
+    ) -> Result<ReturnPagedChatGroups>;
+
+    /// Get chat messages
+    async fn get_chat_messages(
+        &self,
+        chat_id: &str,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+    ) -> Result<PagedResponse<ChatMessage>>;
+}
+
+#[async_trait]
+impl EviChatApi for ChatClient {
+    async fn connect(
+        &self,
+        config_id: Option<String>,
+        config_version: Option<u32>,
+        resumed_chat_group_id: Option<String>,
+    ) -> Result<ChatSocket> {
+        ChatClient::connect(self, config_id, config_version, resumed_chat_group_id).await
+    }
+
+    async fn list_chats(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        ascending_order: Option<bool>,
+    ) -> Result<ReturnPagedChats> {
+        ChatClient::list_chats(self, page_number, page_size, ascending_order).await
+    }
+
+    async fn get_chat(&self, chat_id: &str) -> Result<Chat> {
+        ChatClient::get_chat(self, chat_id).await
+    }
+
+    async fn list_chat_groups(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        ascending_order: Option<bool>,
+    ) -> Result<ReturnPagedChatGroups> {
+        ChatClient::list_chat_groups(self, page_number, page_size, ascending_order).await
+    }
+
+    async fn get_chat_messages(
+        &self,
+        chat_id: &str,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+    ) -> Result<PagedResponse<ChatMessage>> {
+        ChatClient::get_chat_messages(self, chat_id, page_number, page_size).await
+    }
+}
+
+/// EVI configuration management, implemented by [`ConfigsClient`]
+#[async_trait]
+pub trait EviConfigsApi: Send + Sync {
+    /// List all configurations
+    async fn list(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedConfigs>;
+
+    /// Create a new configuration
+    async fn create(&self, request: CreateConfigRequest, options: Option<RequestOptions>) -> Result<Config>;
+
+    /// Get a specific configuration
+    async fn get(&self, config_id: &str, options: Option<RequestOptions>) -> Result<Config>;
+
+    /// Update a configuration
+    async fn update(
+        &self,
+        config_id: &str,
+        request: UpdateConfigRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<Config>;
+
+    /// Delete a configuration
+    async fn delete(&self, config_id: &str, options: Option<RequestOptions>) -> Result<()>;
+
+    /// List configuration versions
+    async fn list_versions(
+        &self,
+        config_id: &str,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedConfigs>;
+
+    /// Get a specific configuration version
+    async fn get_version(&self, config_id: &str, version: u32, options: Option<RequestOptions>) -> Result<Config>;
+}
+
+#[async_trait]
+impl EviConfigsApi for ConfigsClient {
+    async fn list(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedConfigs> {
+        ConfigsClient::list(self, page_number, page_size, options).await
+    }
+
+    async fn create(&self, request: CreateConfigRequest, options: Option<RequestOptions>) -> Result<Config> {
+        ConfigsClient::create(self, request, options).await
+    }
+
+    async fn get(&self, config_id: &str, options: Option<RequestOptions>) -> Result<Config> {
+        ConfigsClient::get(self, config_id, options).await
+    }
+
+    async fn update(
+        &self,
+        config_id: &str,
+        request: UpdateConfigRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<Config> {
+        ConfigsClient::update(self, config_id, request, options).await
+    }
+
+    async fn delete(&self, config_id: &str, options: Option<RequestOptions>) -> Result<()> {
+        ConfigsClient::delete(self, config_id, options).await
+    }
+
+    async fn list_versions(
+        &self,
+        config_id: &str,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedConfigs> {
+        ConfigsClient::list_versions(self, config_id, page_number, page_size, options).await
+    }
+
+    async fn get_version(&self, config_id: &str, version: u32, options: Option<RequestOptions>) -> Result<Config> {
+        ConfigsClient::get_version(self, config_id, version, options).await
+    }
+}
+
+/// EVI tool management, implemented by [`ToolsClient`]
+#[async_trait]
+pub trait EviToolsApi: Send + Sync {
+    /// List all tools
+    async fn list(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedUserDefinedTools>;
+
+    /// Create a new tool
+    async fn create(&self, request: CreateToolRequest, options: Option<RequestOptions>) -> Result<Tool>;
+
+    /// Get a specific tool
+    async fn get(&self, tool_id: &str, options: Option<RequestOptions>) -> Result<Tool>;
+
+    /// Update a tool
+    async fn update(&self, tool_id: &str, request: UpdateToolRequest, options: Option<RequestOptions>) -> Result<Tool>;
+
+    /// Delete a tool
+    async fn delete(&self, tool_id: &str, options: Option<RequestOptions>) -> Result<()>;
+
+    /// List tool versions
+    async fn list_versions(
+        &self,
+        tool_id: &str,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedUserDefinedTools>;
+
+    /// Get a specific tool version
+    async fn get_version(&self, tool_id: &str, version_id: &str, options: Option<RequestOptions>) -> Result<Tool>;
+}
+
+#[async_trait]
+impl EviToolsApi for ToolsClient {
+    async fn list(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedUserDefinedTools> {
+        ToolsClient::list(self, page_number, page_size, options).await
+    }
+
+    async fn create(&self, request: CreateToolRequest, options: Option<RequestOptions>) -> Result<Tool> {
+        ToolsClient::create(self, request, options).await
+    }
+
+    async fn get(&self, tool_id: &str, options: Option<RequestOptions>) -> Result<Tool> {
+        ToolsClient::get(self, tool_id, options).await
+    }
+
+    async fn update(&self, tool_id: &str, request: UpdateToolRequest, options: Option<RequestOptions>) -> Result<Tool> {
+        ToolsClient::update(self, tool_id, request, options).await
+    }
+
+    async fn delete(&self, tool_id: &str, options: Option<RequestOptions>) -> Result<()> {
+        ToolsClient::delete(self, tool_id, options).await
+    }
+
+    async fn list_versions(
+        &self,
+        tool_id: &str,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedUserDefinedTools> {
+        ToolsClient::list_versions(self, tool_id, page_number, page_size, options).await
+    }
+
+    async fn get_version(&self, tool_id: &str, version_id: &str, options: Option<RequestOptions>) -> Result<Tool> {
+        ToolsClient::get_version(self, tool_id, version_id, options).await
+    }
+}
+
+/// EVI prompt management, implemented by [`PromptsClient`]
+#[async_trait]
+pub trait EviPromptsApi: Send + Sync {
+    /// List all prompts
+    async fn list(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedPrompts>;
+
+    /// Create a new prompt
+    async fn create(&self, request: CreatePromptRequest, options: Option<RequestOptions>) -> Result<Prompt>;
+
+    /// Get a specific prompt
+    async fn get(&self, prompt_id: &str, options: Option<RequestOptions>) -> Result<Prompt>;
+
+    /// Update a prompt
+    async fn update(
+        &self,
+        prompt_id: &str,
+        request: UpdatePromptRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<Prompt>;
+
+    /// Delete a prompt
+    async fn delete(&self, prompt_id: &str, options: Option<RequestOptions>) -> Result<()>;
+
+    /// List prompt versions
+    async fn list_versions(
+        &self,
+        prompt_id: &str,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedPrompts>;
+
+    /// Get a specific prompt version
+    async fn get_version(&self, prompt_id: &str, version: u32, options: Option<RequestOptions>) -> Result<Prompt>;
+
+    /// Create a new version of a prompt
+    async fn create_version(
+        &self,
+        prompt_id: &str,
+        request: CreatePromptVersionRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<Prompt>;
+}
+
+#[async_trait]
+impl EviPromptsApi for PromptsClient {
+    async fn list(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedPrompts> {
+        PromptsClient::list(self, page_number, page_size, options).await
+    }
+
+    async fn create(&self, request: CreatePromptRequest, options: Option<RequestOptions>) -> Result<Prompt> {
+        PromptsClient::create(self, request, options).await
+    }
+
+    async fn get(&self, prompt_id: &str, options: Option<RequestOptions>) -> Result<Prompt> {
+        PromptsClient::get(self, prompt_id, options).await
+    }
+
+    async fn update(
+        &self,
+        prompt_id: &str,
+        request: UpdatePromptRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<Prompt> {
+        PromptsClient::update(self, prompt_id, request, options).await
+    }
+
+    async fn delete(&self, prompt_id: &str, options: Option<RequestOptions>) -> Result<()> {
+        PromptsClient::delete(self, prompt_id, options).await
+    }
+
+    async fn list_versions(
+        &self,
+        prompt_id: &str,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedPrompts> {
+        PromptsClient::list_versions(self, prompt_id, page_number, page_size, options).await
+    }
+
+    async fn get_version(&self, prompt_id: &str, version: u32, options: Option<RequestOptions>) -> Result<Prompt> {
+        PromptsClient::get_version(self, prompt_id, version, options).await
+    }
+
+    async fn create_version(
+        &self,
+        prompt_id: &str,
+        request: CreatePromptVersionRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<Prompt> {
+        PromptsClient::create_version(self, prompt_id, request, options).await
+    }
+}
+
+/// EVI custom voice management, implemented by [`VoicesClient`]
+#[async_trait]
+pub trait EviVoicesApi: Send + Sync {
+    /// List all custom voices
+    async fn list(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedCustomVoices>;
+
+    /// Create a new custom voice
+    async fn create(&self, request: CreateCustomVoiceRequest, options: Option<RequestOptions>) -> Result<CustomVoice>;
+
+    /// Get a specific custom voice
+    async fn get(&self, voice_id: &str, options: Option<RequestOptions>) -> Result<CustomVoice>;
+
+    /// Update a custom voice
+    async fn update(
+        &self,
+        voice_id: &str,
+        request: UpdateCustomVoiceRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<CustomVoice>;
+
+    /// Delete a custom voice
+    async fn delete(&self, voice_id: &str, options: Option<RequestOptions>) -> Result<()>;
+}
+
+#[async_trait]
+impl EviVoicesApi for VoicesClient {
+    async fn list(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<ReturnPagedCustomVoices> {
+        VoicesClient::list(self, page_number, page_size, options).await
+    }
+
+    async fn create(&self, request: CreateCustomVoiceRequest, options: Option<RequestOptions>) -> Result<CustomVoice> {
+        VoicesClient::create(self, request, options).await
+    }
+
+    async fn get(&self, voice_id: &str, options: Option<RequestOptions>) -> Result<CustomVoice> {
+        VoicesClient::get(self, voice_id, options).await
+    }
+
+    async fn update(
+        &self,
+        voice_id: &str,
+        request: UpdateCustomVoiceRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<CustomVoice> {
+        VoicesClient::update(self, voice_id, request, options).await
+    }
+
+    async fn delete(&self, voice_id: &str, options: Option<RequestOptions>) -> Result<()> {
+        VoicesClient::delete(self, voice_id, options).await
+    }
+}
+
+// Every client must stay usable as a trait object; this fails to compile if a
+// trait loses object safety or a client stops implementing it
+const _: fn(
+    &TtsClient,
+    &BatchClient,
+    &StreamClient,
+    &ChatClient,
+    &ConfigsClient,
+    &ToolsClient,
+    &PromptsClient,
+    &VoicesClient,
+) = |tts, batch, stream, chat, configs, tools, prompts, voices| {
+    let _: &dyn TtsApi = tts;
+    let _: &dyn BatchApi = batch;
+    let _: &dyn ExpressionStreamApi = stream;
+    let _: &dyn EviChatApi = chat;
+    let _: &dyn EviConfigsApi = configs;
+    let _: &dyn EviToolsApi = tools;
+    let _: &dyn EviPromptsApi = prompts;
+    let _: &dyn EviVoicesApi = voices;
+};
@@ -0,0 +1,549 @@
+//! Synchronous facade over the async clients
+//!
+//! Available with the `blocking` feature. [`HumeClient`] mirrors
+//! [`crate::HumeClient`] and hands out TTS, Expression Measurement and EVI
+//! management clients whose methods block until the request completes. All
+//! clients created from one [`HumeClient`] share an internal single-threaded
+//! Tokio runtime, so callers need no runtime of their own. Response streams
+//! and paginators are returned as [`StreamIter`] iterators.
+//!
+//! WebSocket sessions (EVI chat and expression measurement streaming) are
+//! only available through the async clients, reachable with `inner()`.
+//!
+//! The blocking clients must not be used or dropped inside an async runtime,
+//! which would panic; use the async clients there instead.
+//!
+//! ```no_run
+//! use hume::blocking::HumeClient;
+//! use hume::tts::models::TtsRequestBuilder;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = HumeClient::from_env()?;
+//!
+//! let request = TtsRequestBuilder::new().utterance("Hello, world!")?.build();
+//! let audio = client.tts().synthesize_file(request, None)?;
+//! std::fs::write("output.mp3", &audio)?;
+//!
+//! for config in client.evi().configs().paginate(None) {
+//!     println!("{}", config?.name);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    api::ApiStream,
+    core::{
+        auth::AuthToken,
+        error::Result,
+        request::RequestOptions,
+        response::WithMeta,
+    },
+    evi::{
+        configs::{CreateConfigRequest, UpdateConfigRequest},
+        models::*,
+        prompts::{CreatePromptRequest, CreatePromptVersionRequest, UpdatePromptRequest},
+        tools::{CreateToolRequest, UpdateToolRequest},
+        voices::{CreateCustomVoiceRequest, UpdateCustomVoiceRequest},
+    },
+    expression_measurement::models::{
+        BatchJob, BatchJobRequest, FileInput, JobArtifacts, ListJobsResponse, Models, PredictionResults,
+    },
+    tts::models::{TtsRequest, TtsResponse, TtsStreamRequest, TtsStreamResponse, VoicesResponse},
+    HumeClientBuilder,
+};
+use bytes::Bytes;
+use futures_util::StreamExt;
+use std::{fmt, sync::Arc, time::Duration};
+use tokio::runtime::Runtime;
+
+/// Blocking counterpart of [`crate::HumeClient`]
+#[derive(Debug, Clone)]
+pub struct HumeClient {
+    inner: crate::HumeClient,
+    runtime: Arc<Runtime>,
+}
+
+impl HumeClient {
+    /// Create a new client with an API key
+    pub fn new(api_key: impl Into<String>) -> Result<Self> {
+        HumeClientBuilder::new(api_key).build_blocking()
+    }
+
+    /// Create a new client from environment variables and the config file
+    ///
+    /// See [`HumeClientBuilder::from_env`].
+    pub fn from_env() -> Result<Self> {
+        HumeClientBuilder::from_env()?.build_blocking()
+    }
+
+    /// Create a new client builder, finished with [`HumeClientBuilder::build_blocking`]
+    pub fn builder() -> HumeClientBuilder {
+        HumeClientBuilder::default()
+    }
+
+    /// Build the async client from `builder` on a new internal runtime
+    pub(crate) fn from_builder(builder: HumeClientBuilder) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let inner = {
+            let _guard = runtime.enter();
+            builder.build()?
+        };
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Get the async client
+    pub fn inner(&self) -> &crate::HumeClient {
+        &self.inner
+    }
+
+    /// Create a TTS client
+    pub fn tts(&self) -> TtsClient {
+        TtsClient {
+            inner: self.inner.tts(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Create an Expression Measurement client
+    pub fn expression(&self) -> ExpressionMeasurementClient {
+        ExpressionMeasurementClient {
+            inner: self.inner.expression(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Create an EVI client
+    pub fn evi(&self) -> EviClient {
+        EviClient {
+            inner: self.inner.evi(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Generate an access token using API key and secret key
+    pub fn generate_access_token(&self, api_key: &str, secret_key: &str) -> Result<AuthToken> {
+        self.runtime
+            .block_on(self.inner.generate_access_token(api_key, secret_key))
+    }
+}
+
+/// Blocking iterator over a response stream or paginator
+///
+/// Each call to `next` blocks until the next item arrives.
+pub struct StreamIter<T> {
+    stream: ApiStream<T>,
+    runtime: Arc<Runtime>,
+}
+
+impl<T> StreamIter<T> {
+    fn new(stream: ApiStream<T>, runtime: Arc<Runtime>) -> Self {
+        Self { stream, runtime }
+    }
+}
+
+impl<T> Iterator for StreamIter<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl<T> fmt::Debug for StreamIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamIter").finish_non_exhaustive()
+    }
+}
+
+/// Declare a blocking client wrapping an async client, with methods that run
+/// the async method of the same name on the shared runtime
+macro_rules! blocking_client {
+    (
+        $(#[$meta:meta])*
+        $name:ident($inner:ty) {
+            $(
+                $(#[$method_meta:meta])*
+                fn $method:ident($($arg:ident: $arg_ty:ty),* $(,)?) -> $ret:ty;
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        pub struct $name {
+            inner: $inner,
+            runtime: Arc<Runtime>,
+        }
+
+        impl $name {
+            /// Get the async client
+            pub fn inner(&self) -> &$inner {
+                &self.inner
+            }
+
+            $(
+                $(#[$method_meta])*
+                pub fn $method(&self, $($arg: $arg_ty),*) -> Result<$ret> {
+                    self.runtime.block_on(self.inner.$method($($arg),*))
+                }
+            )*
+        }
+    };
+}
+
+blocking_client! {
+    /// Blocking counterpart of [`crate::tts::TtsClient`]
+    TtsClient(crate::tts::TtsClient) {
+        /// Synthesize speech from text
+        fn synthesize(request: TtsRequest, options: Option<RequestOptions>) -> TtsResponse;
+        /// Synthesize speech, returning the response metadata alongside
+        fn synthesize_with_response(request: TtsRequest, options: Option<RequestOptions>) -> WithMeta<TtsResponse>;
+        /// Synthesize speech and return the audio file
+        fn synthesize_file(request: TtsRequest, options: Option<RequestOptions>) -> Bytes;
+        /// Synthesize speech to a file, returning the response metadata alongside
+        fn synthesize_file_with_response(request: TtsRequest, options: Option<RequestOptions>) -> WithMeta<Bytes>;
+        /// List available voices
+        fn list_voices(options: Option<RequestOptions>) -> VoicesResponse;
+        /// List available voices, returning the response metadata alongside
+        fn list_voices_with_response(options: Option<RequestOptions>) -> WithMeta<VoicesResponse>;
+        /// Convenience method to synthesize with default settings
+        fn synthesize_simple(text: impl Into<String>, voice_name: Option<impl Into<String>>) -> Bytes;
+    }
+}
+
+impl TtsClient {
+    /// Stream synthesis response as JSON chunks
+    pub fn stream_json(
+        &self,
+        request: TtsStreamRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<StreamIter<TtsStreamResponse>> {
+        let stream = self.runtime.block_on(self.inner.stream_json(request, options))?;
+        Ok(StreamIter::new(stream, self.runtime.clone()))
+    }
+
+    /// Stream synthesis response as raw audio chunks
+    pub fn stream_file(&self, request: TtsStreamRequest, options: Option<RequestOptions>) -> Result<StreamIter<Bytes>> {
+        let stream = self.runtime.block_on(self.inner.stream_file(request, options))?;
+        Ok(StreamIter::new(stream, self.runtime.clone()))
+    }
+}
+
+/// Blocking counterpart of [`crate::ExpressionMeasurementClient`]
+#[derive(Debug, Clone)]
+pub struct ExpressionMeasurementClient {
+    inner: crate::ExpressionMeasurementClient,
+    runtime: Arc<Runtime>,
+}
+
+impl ExpressionMeasurementClient {
+    /// Get the async client
+    pub fn inner(&self) -> &crate::ExpressionMeasurementClient {
+        &self.inner
+    }
+
+    /// Access batch processing functionality
+    pub fn batch(&self) -> BatchClient {
+        BatchClient {
+            inner: self.inner.batch(),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+blocking_client! {
+    /// Blocking counterpart of [`crate::expression_measurement::batch::BatchClient`]
+    BatchClient(crate::expression_measurement::batch::BatchClient) {
+        /// List batch jobs
+        fn list_jobs(limit: Option<u32>, offset: Option<u32>, options: Option<RequestOptions>) -> ListJobsResponse;
+        /// List batch jobs, returning the response metadata alongside
+        fn list_jobs_with_response(
+            limit: Option<u32>,
+            offset: Option<u32>,
+            options: Option<RequestOptions>,
+        ) -> WithMeta<ListJobsResponse>;
+        /// Create a new batch job
+        fn create_job(request: BatchJobRequest, options: Option<RequestOptions>) -> BatchJob;
+        /// Get job details
+        fn get_job(job_id: &str, options: Option<RequestOptions>) -> BatchJob;
+        /// Get job details, returning the response metadata alongside
+        fn get_job_with_response(job_id: &str, options: Option<RequestOptions>) -> WithMeta<BatchJob>;
+        /// Get job predictions
+        fn get_predictions(job_id: &str, options: Option<RequestOptions>) -> PredictionResults;
+        /// Get job predictions, returning the response metadata alongside
+        fn get_predictions_with_response(job_id: &str, options: Option<RequestOptions>) -> WithMeta<PredictionResults>;
+        /// Get job artifacts
+        fn get_artifacts(job_id: &str, options: Option<RequestOptions>) -> JobArtifacts;
+        /// Get job artifacts, returning the response metadata alongside
+        fn get_artifacts_with_response(job_id: &str, options: Option<RequestOptions>) -> WithMeta<JobArtifacts>;
+        /// Create a job from files
+        fn create_job_from_files(
+            models: Models,
+            files: Vec<FileInput>,
+            callback_url: Option<String>,
+            notify: Option<bool>,
+            options: Option<RequestOptions>,
+        ) -> BatchJob;
+        /// Create a job from URLs
+        fn create_job_from_urls(
+            models: Models,
+            urls: Vec<String>,
+            callback_url: Option<String>,
+            notify: Option<bool>,
+            options: Option<RequestOptions>,
+        ) -> BatchJob;
+        /// Create a job from text
+        fn create_job_from_text(
+            models: Models,
+            texts: Vec<String>,
+            callback_url: Option<String>,
+            notify: Option<bool>,
+            options: Option<RequestOptions>,
+        ) -> BatchJob;
+        /// Wait for a job to complete, polling every `poll_interval`
+        fn wait_for_job_completion(
+            job_id: &str,
+            poll_interval: Duration,
+            max_wait: Option<Duration>,
+        ) -> BatchJob;
+        /// Wait for a job to complete, passing options to each status request
+        fn wait_for_job_completion_with_options(
+            job_id: &str,
+            poll_interval: Duration,
+            max_wait: Option<Duration>,
+            options: Option<RequestOptions>,
+        ) -> BatchJob;
+    }
+}
+
+impl BatchClient {
+    /// Iterate over all jobs, fetching pages as needed
+    pub fn paginate_jobs(&self, options: Option<RequestOptions>) -> StreamIter<BatchJob> {
+        StreamIter::new(self.inner.paginate_jobs(options).into_stream(), self.runtime.clone())
+    }
+}
+
+/// Blocking counterpart of [`crate::EviClient`], for the REST APIs
+#[derive(Debug, Clone)]
+pub struct EviClient {
+    inner: crate::EviClient,
+    runtime: Arc<Runtime>,
+}
+
+impl EviClient {
+    /// Get the async client
+    pub fn inner(&self) -> &crate::EviClient {
+        &self.inner
+    }
+
+    /// Access chat history
+    pub fn chat(&self) -> ChatClient {
+        ChatClient {
+            inner: self.inner.chat(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Access tools management
+    pub fn tools(&self) -> ToolsClient {
+        ToolsClient {
+            inner: self.inner.tools(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Access prompts management
+    pub fn prompts(&self) -> PromptsClient {
+        PromptsClient {
+            inner: self.inner.prompts(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Access custom voices management
+    pub fn voices(&self) -> VoicesClient {
+        VoicesClient {
+            inner: self.inner.voices(),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Access configs management
+    pub fn configs(&self) -> ConfigsClient {
+        ConfigsClient {
+            inner: self.inner.configs(),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+blocking_client! {
+    /// Blocking counterpart of [`crate::evi::chat::ChatClient`], for chat history
+    ChatClient(crate::evi::chat::ChatClient) {
+        /// List chats
+        fn list_chats(page_number: Option<u32>, page_size: Option<u32>, ascending_order: Option<bool>) -> ReturnPagedChats;
+        /// Get a chat
+        fn get_chat(chat_id: &str) -> Chat;
+        /// List chat groups
+        fn list_chat_groups(
+            page_number: Option<u32>,
+            page_size: Option<u32>,
+            ascending_order: Option<bool>,
+        ) -> ReturnPagedChatGroups;
+        /// Get the messages of a chat
+        fn get_chat_messages(chat_id: &str, page_number: Option<u32>, page_size: Option<u32>) -> PagedResponse<ChatMessage>;
+    }
+}
+
+impl ChatClient {
+    /// Iterate over all chats, fetching pages as needed
+    pub fn paginate_chats(&self, ascending_order: Option<bool>) -> StreamIter<Chat> {
+        StreamIter::new(self.inner.paginate_chats(ascending_order).into_stream(), self.runtime.clone())
+    }
+
+    /// Iterate over all chat groups, fetching pages as needed
+    pub fn paginate_chat_groups(&self, ascending_order: Option<bool>) -> StreamIter<ChatGroup> {
+        StreamIter::new(
+            self.inner.paginate_chat_groups(ascending_order).into_stream(),
+            self.runtime.clone(),
+        )
+    }
+
+    /// Iterate over all messages of a chat, fetching pages as needed
+    pub fn paginate_chat_messages(&self, chat_id: impl Into<String>) -> StreamIter<ChatMessage> {
+        StreamIter::new(
+            self.inner.paginate_chat_messages(chat_id).into_stream(),
+            self.runtime.clone(),
+        )
+    }
+}
+
+blocking_client! {
+    /// Blocking counterpart of [`crate::evi::configs::ConfigsClient`]
+    ConfigsClient(crate::evi::configs::ConfigsClient) {
+        /// List configs
+        fn list(page_number: Option<u32>, page_size: Option<u32>, options: Option<RequestOptions>) -> ReturnPagedConfigs;
+        /// Create a config
+        fn create(request: CreateConfigRequest, options: Option<RequestOptions>) -> Config;
+        /// Get a config
+        fn get(config_id: &str, options: Option<RequestOptions>) -> Config;
+        /// Update a config
+        fn update(config_id: &str, request: UpdateConfigRequest, options: Option<RequestOptions>) -> Config;
+        /// Delete a config
+        fn delete(config_id: &str, options: Option<RequestOptions>) -> ();
+        /// List config versions
+        fn list_versions(
+            config_id: &str,
+            page_number: Option<u32>,
+            page_size: Option<u32>,
+            options: Option<RequestOptions>,
+        ) -> ReturnPagedConfigs;
+        /// Get a specific config version
+        fn get_version(config_id: &str, version: u32, options: Option<RequestOptions>) -> Config;
+    }
+}
+
+impl ConfigsClient {
+    /// Iterate over all configs, fetching pages as needed
+    pub fn paginate(&self, options: Option<RequestOptions>) -> StreamIter<Config> {
+        StreamIter::new(self.inner.paginate(options).into_stream(), self.runtime.clone())
+    }
+}
+
+blocking_client! {
+    /// Blocking counterpart of [`crate::evi::prompts::PromptsClient`]
+    PromptsClient(crate::evi::prompts::PromptsClient) {
+        /// List prompts
+        fn list(page_number: Option<u32>, page_size: Option<u32>, options: Option<RequestOptions>) -> ReturnPagedPrompts;
+        /// Create a prompt
+        fn create(request: CreatePromptRequest, options: Option<RequestOptions>) -> Prompt;
+        /// Get a prompt
+        fn get(prompt_id: &str, options: Option<RequestOptions>) -> Prompt;
+        /// Update a prompt
+        fn update(prompt_id: &str, request: UpdatePromptRequest, options: Option<RequestOptions>) -> Prompt;
+        /// Delete a prompt
+        fn delete(prompt_id: &str, options: Option<RequestOptions>) -> ();
+        /// List prompt versions
+        fn list_versions(
+            prompt_id: &str,
+            page_number: Option<u32>,
+            page_size: Option<u32>,
+            options: Option<RequestOptions>,
+        ) -> ReturnPagedPrompts;
+        /// Get a specific prompt version
+        fn get_version(prompt_id: &str, version: u32, options: Option<RequestOptions>) -> Prompt;
+        /// Create a new prompt version
+        fn create_version(prompt_id: &str, request: CreatePromptVersionRequest, options: Option<RequestOptions>) -> Prompt;
+    }
+}
+
+impl PromptsClient {
+    /// Iterate over all prompts, fetching pages as needed
+    pub fn paginate(&self, options: Option<RequestOptions>) -> StreamIter<Prompt> {
+        StreamIter::new(self.inner.paginate(options).into_stream(), self.runtime.clone())
+    }
+}
+
+blocking_client! {
+    /// Blocking counterpart of [`crate::evi::tools::ToolsClient`]
+    ToolsClient(crate::evi::tools::ToolsClient) {
+        /// List tools
+        fn list(
+            page_number: Option<u32>,
+            page_size: Option<u32>,
+            options: Option<RequestOptions>,
+        ) -> ReturnPagedUserDefinedTools;
+        /// Create a tool
+        fn create(request: CreateToolRequest, options: Option<RequestOptions>) -> Tool;
+        /// Get a tool
+        fn get(tool_id: &str, options: Option<RequestOptions>) -> Tool;
+        /// Update a tool
+        fn update(tool_id: &str, request: UpdateToolRequest, options: Option<RequestOptions>) -> Tool;
+        /// Delete a tool
+        fn delete(tool_id: &str, options: Option<RequestOptions>) -> ();
+        /// List tool versions
+        fn list_versions(
+            tool_id: &str,
+            page_number: Option<u32>,
+            page_size: Option<u32>,
+            options: Option<RequestOptions>,
+        ) -> ReturnPagedUserDefinedTools;
+        /// Get a specific tool version
+        fn get_version(tool_id: &str, version_id: &str, options: Option<RequestOptions>) -> Tool;
+    }
+}
+
+impl ToolsClient {
+    /// Iterate over all tools, fetching pages as needed
+    pub fn paginate(&self, options: Option<RequestOptions>) -> StreamIter<Tool> {
+        StreamIter::new(self.inner.paginate(options).into_stream(), self.runtime.clone())
+    }
+}
+
+blocking_client! {
+    /// Blocking counterpart of [`crate::evi::voices::VoicesClient`]
+    VoicesClient(crate::evi::voices::VoicesClient) {
+        /// List custom voices
+        fn list(page_number: Option<u32>, page_size: Option<u32>, options: Option<RequestOptions>) -> ReturnPagedCustomVoices;
+        /// Create a custom voice
+        fn create(request: CreateCustomVoiceRequest, options: Option<RequestOptions>) -> CustomVoice;
+        /// Get a custom voice
+        fn get(voice_id: &str, options: Option<RequestOptions>) -> CustomVoice;
+        /// Update a custom voice
+        fn update(voice_id: &str, request: UpdateCustomVoiceRequest, options: Option<RequestOptions>) -> CustomVoice;
+        /// Delete a custom voice
+        fn delete(voice_id: &str, options: Option<RequestOptions>) -> ();
+    }
+}
+
+impl VoicesClient {
+    /// Iterate over all custom voices, fetching pages as needed
+    pub fn paginate(&self, options: Option<RequestOptions>) -> StreamIter<CustomVoice> {
+        StreamIter::new(self.inner.paginate(options).into_stream(), self.runtime.clone())
+    }
+}
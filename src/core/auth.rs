@@ -1,22 +1,35 @@
 //! Authentication types and utilities
 
-use crate::core::error::{Error, Result};
+use crate::core::{
+    endpoints::Endpoints,
+    error::{Error, Result},
+    response::decode_json,
+    secret::Secret,
+    transport::{HttpRequest, ReqwestTransport, Transport},
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::Mutex;
+
+/// Default margin before expiry at which client-credentials tokens are refreshed
+pub const DEFAULT_REFRESH_MARGIN: Duration = Duration::from_secs(60);
 
 /// Authentication method for Hume API
 #[derive(Debug, Clone)]
 pub enum Auth {
     /// API key authentication
-    ApiKey(String),
+    ApiKey(Secret),
     /// Access token authentication
     AccessToken(AuthToken),
+    /// Access tokens generated and refreshed on demand from an API key and secret key
+    ClientCredentials(ClientCredentials),
 }
 
 impl Auth {
     /// Create a new API key authentication
     pub fn api_key(key: impl Into<String>) -> Self {
-        Self::ApiKey(key.into())
+        Self::ApiKey(Secret::new(key))
     }
 
     /// Create a new access token authentication
@@ -24,36 +37,150 @@ impl Auth {
         Self::AccessToken(token)
     }
 
+    /// Create a new client-credentials authentication
+    pub fn client_credentials(api_key: impl Into<String>, secret_key: impl Into<String>) -> Self {
+        Self::ClientCredentials(ClientCredentials::new(api_key, secret_key))
+    }
+
     /// Get the authorization header value
+    ///
+    /// For client credentials this uses the cached token, if any. Use
+    /// [`HttpClient::resolve_auth`](crate::core::http::HttpClient::resolve_auth)
+    /// to obtain a fresh token first.
     pub fn header_value(&self) -> Option<(&'static str, String)> {
         match self {
-            Self::ApiKey(key) => Some(("X-Hume-Api-Key", key.clone())),
-            Self::AccessToken(token) => Some(("Authorization", format!("Bearer {}", token.access_token))),
+            Self::ApiKey(key) => Some(("X-Hume-Api-Key", key.expose().to_string())),
+            Self::AccessToken(token) => Some(("Authorization", token.bearer())),
+            Self::ClientCredentials(credentials) => credentials
+                .cached_token()
+                .map(|token| ("Authorization", token.bearer())),
         }
     }
 
     /// Get the query parameter for WebSocket authentication
+    ///
+    /// Only used with [`WebSocketAuth::QueryParam`]. For client credentials
+    /// this uses the cached token, if any.
     pub fn query_param(&self) -> (&'static str, String) {
         match self {
-            Self::ApiKey(key) => ("api_key", key.clone()),
-            Self::AccessToken(token) => ("access_token", token.access_token.clone()),
+            Self::ApiKey(key) => ("api_key", key.expose().to_string()),
+            Self::AccessToken(token) => ("access_token", token.access_token.expose().to_string()),
+            Self::ClientCredentials(credentials) => (
+                "access_token",
+                credentials
+                    .cached_token()
+                    .map(|token| token.access_token.expose().to_string())
+                    .unwrap_or_default(),
+            ),
         }
     }
 
     /// Check if the authentication is expired (only applicable for access tokens)
+    ///
+    /// Client credentials never expire since their tokens are refreshed on demand.
     pub fn is_expired(&self) -> bool {
         match self {
-            Self::ApiKey(_) => false,
+            Self::ApiKey(_) | Self::ClientCredentials(_) => false,
             Self::AccessToken(token) => token.is_expired(),
         }
     }
 }
 
+/// How WebSocket handshakes carry credentials
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WebSocketAuth {
+    /// Send the `X-Hume-Api-Key` or `Authorization` header (default)
+    #[default]
+    Headers,
+    /// Append `api_key` or `access_token` to the socket URL
+    ///
+    /// The credential then appears in the URL, e.g. in proxy logs. Only use
+    /// this for servers that do not accept handshake headers.
+    QueryParam,
+}
+
+impl WebSocketAuth {
+    /// Add the credentials to a socket URL, returning the URL and the header to send
+    pub fn apply(&self, url: &url::Url, auth: &Auth) -> (url::Url, Option<(&'static str, String)>) {
+        match self {
+            Self::Headers => (url.clone(), auth.header_value()),
+            Self::QueryParam => {
+                let mut url = url.clone();
+                let (name, value) = auth.query_param();
+                url.query_pairs_mut().append_pair(name, &value);
+                (url, None)
+            }
+        }
+    }
+}
+
+/// API key and secret key pair used to generate access tokens on demand
+///
+/// The generated token is cached and shared between clones, and refreshed
+/// once it is within the refresh margin of its expiry.
+#[derive(Debug, Clone)]
+pub struct ClientCredentials {
+    api_key: Secret,
+    secret_key: Secret,
+    refresh_margin: Duration,
+    token: Arc<Mutex<Option<AuthToken>>>,
+}
+
+impl ClientCredentials {
+    /// Create new client credentials
+    pub fn new(api_key: impl Into<String>, secret_key: impl Into<String>) -> Self {
+        Self {
+            api_key: Secret::new(api_key),
+            secret_key: Secret::new(secret_key),
+            refresh_margin: DEFAULT_REFRESH_MARGIN,
+            token: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Set how long before expiry the token is refreshed (defaults to 60 seconds)
+    pub fn with_refresh_margin(mut self, margin: Duration) -> Self {
+        self.refresh_margin = margin;
+        self
+    }
+
+    /// Get the API key
+    pub fn api_key(&self) -> &Secret {
+        &self.api_key
+    }
+
+    /// Get the currently cached token without refreshing it
+    pub fn cached_token(&self) -> Option<AuthToken> {
+        self.token.try_lock().ok().and_then(|token| token.clone())
+    }
+
+    /// Get a valid access token, generating a new one if needed
+    pub async fn token(&self, transport: &dyn Transport, endpoints: &Endpoints) -> Result<AuthToken> {
+        let mut cached = self.token.lock().await;
+
+        if let Some(token) = cached.as_ref() {
+            if !token.expires_within(self.refresh_margin) {
+                return Ok(token.clone());
+            }
+        }
+
+        tracing::debug!("Generating new access token from client credentials");
+        let token =
+            request_access_token(transport, endpoints, self.api_key.expose(), self.secret_key.expose()).await?;
+        *cached = Some(token.clone());
+        Ok(token)
+    }
+
+    /// Discard the cached token so the next request generates a new one
+    pub async fn invalidate(&self) {
+        *self.token.lock().await = None;
+    }
+}
+
 /// Access token for authentication
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthToken {
     /// The access token
-    pub access_token: String,
+    pub access_token: Secret,
     /// Token type (usually "Bearer")
     pub token_type: String,
     /// Expiration time in seconds
@@ -65,21 +192,38 @@ pub struct AuthToken {
 
 impl AuthToken {
     /// Create a new auth token
-    pub fn new(access_token: String, token_type: String, expires_in: u64) -> Self {
+    pub fn new(access_token: impl Into<Secret>, token_type: String, expires_in: u64) -> Self {
         Self {
-            access_token,
+            access_token: access_token.into(),
             token_type,
             expires_in,
             created_at: Utc::now(),
         }
     }
 
+    /// Get the `Authorization` header value
+    pub fn bearer(&self) -> String {
+        format!("Bearer {}", self.access_token.expose())
+    }
+
     /// Check if the token is expired
     pub fn is_expired(&self) -> bool {
         let expiry = self.created_at + chrono::Duration::seconds(self.expires_in as i64);
         Utc::now() >= expiry
     }
 
+    /// Check if the token expires within the given duration
+    pub fn expires_within(&self, margin: Duration) -> bool {
+        let expiry = self.created_at + chrono::Duration::seconds(self.expires_in as i64);
+        match chrono::Duration::from_std(margin)
+            .ok()
+            .and_then(|margin| Utc::now().checked_add_signed(margin))
+        {
+            Some(deadline) => deadline >= expiry,
+            None => true,
+        }
+    }
+
     /// Get the remaining time until expiration in seconds
     pub fn time_until_expiry(&self) -> Option<u64> {
         let expiry = self.created_at + chrono::Duration::seconds(self.expires_in as i64);
@@ -96,16 +240,16 @@ impl AuthToken {
 #[derive(Debug, Serialize)]
 pub struct AccessTokenRequest {
     /// API key
-    pub api_key: String,
+    pub api_key: Secret,
     /// Secret key
-    pub secret_key: String,
+    pub secret_key: Secret,
 }
 
 /// Response from access token endpoint
 #[derive(Debug, Deserialize)]
 pub struct AccessTokenResponse {
     /// The access token
-    pub access_token: String,
+    pub access_token: Secret,
     /// Token type
     pub token_type: String,
     /// Expiration time in seconds
@@ -129,21 +273,36 @@ pub async fn generate_access_token(
     api_key: &str,
     secret_key: &str,
 ) -> Result<AuthToken> {
-    let url = format!("{}/oauth2-cc/token", base_url);
+    let transport = ReqwestTransport::from(client.clone());
+    request_access_token(&transport, &Endpoints::new(base_url)?, api_key, secret_key).await
+}
+
+/// Generate an access token, sending the request through the given transport
+pub async fn request_access_token(
+    transport: &dyn Transport,
+    endpoints: &Endpoints,
+    api_key: &str,
+    secret_key: &str,
+) -> Result<AuthToken> {
+    let url = endpoints.rest_url("/oauth2-cc/token", std::iter::empty::<(&str, &str)>());
     
-    let request = AccessTokenRequest {
-        api_key: api_key.to_string(),
-        secret_key: secret_key.to_string(),
+    let body = AccessTokenRequest {
+        api_key: api_key.into(),
+        secret_key: secret_key.into(),
     };
 
-    let response = client
-        .post(&url)
-        .json(&request)
-        .send()
-        .await?;
+    let mut request = HttpRequest::new(reqwest::Method::POST, url);
+    request.headers.insert(
+        reqwest::header::CONTENT_TYPE,
+        reqwest::header::HeaderValue::from_static("application/json"),
+    );
+    request.body = Some(serde_json::to_vec(&body)?.into());
+
+    let response = transport.send(request).await?;
 
     if response.status().is_success() {
-        let token_response: AccessTokenResponse = response.json().await?;
+        let bytes = response.bytes().await?;
+        let token_response: AccessTokenResponse = decode_json(&bytes)?;
         Ok(token_response.into())
     } else {
         let status = response.status().as_u16();
@@ -155,4 +314,4 @@ pub async fn generate_access_token(
             body,
         ))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,78 @@
+//! Cooperative cancellation
+//!
+//! A [`CancellationToken`] set on
+//! [`RequestOptions`](crate::core::request::RequestOptions) or on a socket
+//! stops retries and polling, ends response streams and closes WebSockets
+//! with a close frame. Cancelled operations fail with [`Error::Cancelled`].
+
+use crate::core::error::{Error, Result};
+use futures_util::{Stream, StreamExt};
+use std::future::Future;
+
+pub use tokio_util::sync::CancellationToken;
+
+/// Run `future` to completion unless `token` is cancelled first
+pub(crate) async fn cancellable<T>(
+    token: Option<&CancellationToken>,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    match token {
+        Some(token) => tokio::select! {
+            biased;
+            _ = token.cancelled() => Err(Error::Cancelled),
+            result = future => result,
+        },
+        None => future.await,
+    }
+}
+
+/// Forward the items of `stream` until `token` is cancelled, then yield
+/// [`Error::Cancelled`] and end
+pub(crate) fn cancellable_stream<S, T>(stream: S, token: CancellationToken) -> impl Stream<Item = Result<T>>
+where
+    S: Stream<Item = Result<T>> + Unpin,
+{
+    futures_util::stream::unfold(Some((stream, token)), |state| async move {
+        let (mut stream, token) = state?;
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => Some((Err(Error::Cancelled), None)),
+            item = stream.next() => item.map(|item| (item, Some((stream, token)))),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_cancellable() {
+        let token = CancellationToken::new();
+        let result = cancellable(Some(&token), async { Ok(1) }).await;
+        assert_eq!(result.unwrap(), 1);
+
+        token.cancel();
+        let result = cancellable(Some(&token), async { Ok(1) }).await;
+        assert!(result.unwrap_err().is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_cancellable_stream() {
+        let token = CancellationToken::new();
+        let source = futures_util::stream::iter([Ok(1), Ok(2)]).chain(futures_util::stream::pending());
+        let mut stream = Box::pin(cancellable_stream(Box::pin(source), token.clone()));
+
+        assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+        assert_eq!(stream.next().await.unwrap().unwrap(), 2);
+
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            canceller.cancel();
+        });
+        assert!(stream.next().await.unwrap().unwrap_err().is_cancelled());
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -0,0 +1,664 @@
+//! Record-and-replay cassettes for offline testing
+//!
+//! A [`Cassette`] in record mode captures every REST request/response pair and
+//! every `ChatSocket`/`StreamSocket` session made through the client and writes
+//! them to a JSON file. In replay mode the same file is served back without
+//! touching the network. Credentials are redacted before anything is written.
+//!
+//! Recorded traffic is buffered in memory and written when the cassette is
+//! dropped, or earlier with [`Cassette::save`].
+//!
+//! ```no_run
+//! use hume::{core::cassette::Cassette, HumeClientBuilder};
+//!
+//! # fn main() -> hume::Result<()> {
+//! // Record once against the real API
+//! let client = HumeClientBuilder::new("api-key")
+//!     .cassette(Cassette::record("tests/cassettes/voices.json"))
+//!     .build()?;
+//!
+//! // Replay in CI
+//! let client = HumeClientBuilder::new("unused")
+//!     .cassette(Cassette::replay("tests/cassettes/voices.json")?)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::{
+    error::{Error, Result},
+    transport::{HttpRequest, HttpResponse, Transport},
+};
+use async_trait::async_trait;
+use base64::Engine;
+use bytes::Bytes;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+use url::Url;
+
+/// Placeholder written in place of credentials
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Headers whose values are never written to a cassette
+const SENSITIVE_HEADERS: &[&str] = &["authorization", "x-hume-api-key", "x-hume-secret-key"];
+
+/// Query parameters and JSON body fields whose values are never written to a cassette
+///
+/// Applies to request and response bodies alike, so tokens returned by the
+/// client credentials exchange are not recorded.
+const SENSITIVE_FIELDS: &[&str] = &["api_key", "access_token", "secret_key"];
+
+/// Whether a cassette records or replays traffic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send requests to the API and record them
+    Record,
+    /// Serve recorded responses without network access
+    Replay,
+}
+
+/// A file of recorded HTTP interactions and WebSocket sessions
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    state: Mutex<CassetteState>,
+}
+
+#[derive(Debug, Default)]
+struct CassetteState {
+    file: CassetteFile,
+    used: Vec<bool>,
+    next_socket: usize,
+    dirty: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    #[serde(default)]
+    interactions: Vec<Interaction>,
+    #[serde(default)]
+    sockets: Vec<SocketSession>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    path: String,
+    #[serde(default)]
+    query: Vec<(String, String)>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body_base64: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SocketSession {
+    path: String,
+    #[serde(default)]
+    query: Vec<(String, String)>,
+    #[serde(default)]
+    sent: Vec<String>,
+    #[serde(default)]
+    received: Vec<String>,
+}
+
+impl Cassette {
+    /// Create a cassette that records traffic to `path`
+    ///
+    /// The file is overwritten when the cassette is dropped or saved.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            mode: CassetteMode::Record,
+            state: Mutex::new(CassetteState::default()),
+        }
+    }
+
+    /// Load a cassette from `path` and replay it
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let contents = std::fs::read(&path)?;
+        let file: CassetteFile = serde_json::from_slice(&contents)?;
+
+        Ok(Self {
+            path,
+            mode: CassetteMode::Replay,
+            state: Mutex::new(CassetteState {
+                used: vec![false; file.interactions.len()],
+                file,
+                next_socket: 0,
+                dirty: false,
+            }),
+        })
+    }
+
+    /// Get the cassette mode
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Get the cassette file path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the number of recorded HTTP interactions
+    pub fn len(&self) -> usize {
+        self.lock().file.interactions.len()
+    }
+
+    /// Check if no HTTP interactions are recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Write the recorded traffic to the cassette file
+    ///
+    /// Called automatically when a recording cassette is dropped. This does
+    /// blocking file I/O, so call it outside of async code or through
+    /// `tokio::task::spawn_blocking`.
+    pub fn save(&self) -> Result<()> {
+        let json = {
+            let mut state = self.lock();
+            state.dirty = false;
+            serde_json::to_vec_pretty(&state.file)?
+        };
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, json)?;
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CassetteState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn record_interaction(&self, request: &HttpRequest, response: &HttpResponse, body: &Bytes) {
+        let (body, body_base64) = match std::str::from_utf8(body) {
+            Ok(_) => (Some(redacted_body(body)), None),
+            Err(_) => (None, Some(base64::engine::general_purpose::STANDARD.encode(body))),
+        };
+
+        let interaction = Interaction {
+            request: RecordedRequest::from_request(request),
+            response: RecordedResponse {
+                status: response.status().as_u16(),
+                headers: header_map(response.headers()),
+                body,
+                body_base64,
+            },
+        };
+
+        let mut state = self.lock();
+        state.file.interactions.push(interaction);
+        state.dirty = true;
+    }
+
+    fn replay_interaction(&self, request: &HttpRequest) -> Result<HttpResponse> {
+        let recorded = RecordedRequest::from_request(request);
+        let mut state = self.lock();
+        let state = &mut *state;
+
+        let index = state
+            .file
+            .interactions
+            .iter()
+            .enumerate()
+            .position(|(i, interaction)| !state.used[i] && interaction.request.matches(&recorded))
+            .ok_or_else(|| {
+                Error::other(format!(
+                    "No recorded interaction in {} for {} {}",
+                    self.path.display(),
+                    recorded.method,
+                    request.url
+                ))
+            })?;
+        state.used[index] = true;
+
+        state.file.interactions[index].response.to_response()
+    }
+
+    pub(crate) fn socket_url(&self, url: &Url) -> (String, Vec<(String, String)>) {
+        (url.path().to_string(), redacted_query(url))
+    }
+
+    /// Start recording a WebSocket session
+    pub(crate) fn record_socket(self: &Arc<Self>, url: &Url) -> SocketRecorder {
+        let (path, query) = self.socket_url(url);
+        SocketRecorder {
+            cassette: self.clone(),
+            session: Some(SocketSession {
+                path,
+                query,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Find the next recorded WebSocket session for `url`
+    pub(crate) fn replay_socket(&self, url: &Url) -> Result<SocketReplay> {
+        let (path, query) = self.socket_url(url);
+        let mut state = self.lock();
+        let start = state.next_socket;
+
+        let offset = state.file.sockets[start.min(state.file.sockets.len())..]
+            .iter()
+            .position(|session| session.path == path && session.query == query)
+            .ok_or_else(|| {
+                Error::other(format!(
+                    "No recorded WebSocket session in {} for {}",
+                    self.path.display(),
+                    path
+                ))
+            })?;
+        state.next_socket = start + offset + 1;
+
+        let session = state.file.sockets[start + offset].clone();
+        Ok(SocketReplay {
+            sent: session.sent.into(),
+            received: session.received.into(),
+        })
+    }
+
+    fn push_socket(&self, session: SocketSession) {
+        let mut state = self.lock();
+        state.file.sockets.push(session);
+        state.dirty = true;
+    }
+}
+
+impl Drop for Cassette {
+    fn drop(&mut self) {
+        if self.mode == CassetteMode::Record && self.lock().dirty {
+            if let Err(e) = self.save() {
+                tracing::warn!("Failed to save cassette {}: {}", self.path.display(), e);
+            }
+        }
+    }
+}
+
+impl RecordedRequest {
+    fn from_request(request: &HttpRequest) -> Self {
+        let mut headers = header_map(&request.headers);
+        for (name, value) in headers.iter_mut() {
+            if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                *value = REDACTED.to_string();
+            }
+        }
+
+        Self {
+            method: request.method.to_string(),
+            path: request.url.path().to_string(),
+            query: redacted_query(&request.url),
+            headers,
+            body: request.body.as_deref().map(redacted_body),
+        }
+    }
+
+    /// Match on method, path, query and body; headers are informational
+    fn matches(&self, other: &Self) -> bool {
+        self.method == other.method
+            && self.path == other.path
+            && self.query == other.query
+            && bodies_match(self.body.as_deref(), other.body.as_deref())
+    }
+}
+
+impl RecordedResponse {
+    fn to_response(&self) -> Result<HttpResponse> {
+        let status = StatusCode::from_u16(self.status)
+            .map_err(|_| Error::other(format!("Invalid recorded status {}", self.status)))?;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                headers.insert(name, value);
+            }
+        }
+
+        let body = match (&self.body, &self.body_base64) {
+            (_, Some(encoded)) => Bytes::from(base64::engine::general_purpose::STANDARD.decode(encoded)?),
+            (Some(text), None) => Bytes::from(text.clone()),
+            (None, None) => Bytes::new(),
+        };
+
+        Ok(HttpResponse::new(status, headers, body))
+    }
+}
+
+fn header_map(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| Some((name.as_str().to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+/// Sorted query pairs with credentials redacted
+fn redacted_query(url: &Url) -> Vec<(String, String)> {
+    let mut query: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(name, value)| {
+            let value = if SENSITIVE_FIELDS.contains(&name.as_ref()) {
+                REDACTED.to_string()
+            } else {
+                value.into_owned()
+            };
+            (name.into_owned(), value)
+        })
+        .collect();
+    query.sort();
+    query
+}
+
+/// Body as text, with credential fields of JSON objects redacted
+fn redacted_body(body: &[u8]) -> String {
+    match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(mut value) => {
+            if let Some(object) = value.as_object_mut() {
+                for field in SENSITIVE_FIELDS {
+                    if let Some(entry) = object.get_mut(*field) {
+                        *entry = serde_json::Value::String(REDACTED.to_string());
+                    }
+                }
+            }
+            value.to_string()
+        }
+        Err(_) => String::from_utf8_lossy(body).into_owned(),
+    }
+}
+
+/// Compare bodies as JSON when possible so key order does not matter
+fn bodies_match(recorded: Option<&str>, actual: Option<&str>) -> bool {
+    match (recorded, actual) {
+        (Some(recorded), Some(actual)) => {
+            match (
+                serde_json::from_str::<serde_json::Value>(recorded),
+                serde_json::from_str::<serde_json::Value>(actual),
+            ) {
+                (Ok(recorded), Ok(actual)) => recorded == actual,
+                _ => recorded == actual,
+            }
+        }
+        (recorded, actual) => recorded.unwrap_or_default().is_empty() && actual.unwrap_or_default().is_empty(),
+    }
+}
+
+/// Transport that records to or replays from a [`Cassette`]
+///
+/// In record mode requests are forwarded to the inner transport and the
+/// buffered responses are recorded. In replay mode the inner transport is
+/// never used.
+#[derive(Debug, Clone)]
+pub struct CassetteTransport {
+    cassette: Arc<Cassette>,
+    inner: Arc<dyn Transport>,
+}
+
+impl CassetteTransport {
+    /// Wrap a transport with a cassette
+    pub fn new(cassette: Arc<Cassette>, inner: Arc<dyn Transport>) -> Self {
+        Self { cassette, inner }
+    }
+
+    /// Get the cassette
+    pub fn cassette(&self) -> &Arc<Cassette> {
+        &self.cassette
+    }
+}
+
+#[async_trait]
+impl Transport for CassetteTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        match self.cassette.mode() {
+            CassetteMode::Replay => self.cassette.replay_interaction(&request),
+            CassetteMode::Record => {
+                let mut response = self.inner.send(request.clone()).await?;
+                let body = response.buffer().await?;
+                self.cassette.record_interaction(&request, &response, &body);
+                Ok(response)
+            }
+        }
+    }
+}
+
+/// Records the text frames of a live WebSocket session
+#[derive(Debug)]
+pub(crate) struct SocketRecorder {
+    cassette: Arc<Cassette>,
+    session: Option<SocketSession>,
+}
+
+impl SocketRecorder {
+    pub(crate) fn sent(&mut self, text: &str) {
+        if let Some(session) = &mut self.session {
+            session.sent.push(text.to_string());
+        }
+    }
+
+    pub(crate) fn received(&mut self, text: &str) {
+        if let Some(session) = &mut self.session {
+            session.received.push(text.to_string());
+        }
+    }
+
+    /// Add the session to the cassette
+    pub(crate) fn finish(&mut self) {
+        if let Some(session) = self.session.take() {
+            self.cassette.push_socket(session);
+        }
+    }
+}
+
+impl Drop for SocketRecorder {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Serves the frames of a recorded WebSocket session
+#[derive(Debug)]
+pub(crate) struct SocketReplay {
+    sent: VecDeque<String>,
+    received: VecDeque<String>,
+}
+
+impl SocketReplay {
+    /// Check an outgoing frame against the next recorded one
+    pub(crate) fn send(&mut self, text: &str) -> Result<()> {
+        match self.sent.pop_front() {
+            Some(expected) if bodies_match(Some(&expected), Some(text)) => Ok(()),
+            Some(expected) => Err(Error::other(format!(
+                "WebSocket message does not match cassette: expected {}, got {}",
+                expected, text
+            ))),
+            None => Err(Error::other("WebSocket message sent after the recorded session ended")),
+        }
+    }
+
+    /// Get the next recorded incoming frame
+    pub(crate) fn receive(&mut self) -> Option<String> {
+        self.received.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::transport::{MockResponse, MockTransport};
+    use reqwest::Method;
+
+    fn request(method: Method, url: &str, body: Option<serde_json::Value>) -> HttpRequest {
+        let mut request = HttpRequest::new(method, Url::parse(url).unwrap());
+        request
+            .headers
+            .insert("x-hume-api-key", HeaderValue::from_static("secret-key"));
+        request.body = body.map(|body| body.to_string().into());
+        request
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassette.json");
+
+        let inner = MockTransport::new().with_json(
+            Method::POST,
+            "/v0/tts",
+            200,
+            serde_json::json!({ "generations": [] }),
+        );
+        let recorder = CassetteTransport::new(Arc::new(Cassette::record(&path)), Arc::new(inner));
+        let body = serde_json::json!({ "text": "hi", "format": { "type": "mp3" } });
+        recorder
+            .send(request(Method::POST, "https://api.hume.ai/v0/tts?b=2&a=1", Some(body)))
+            .await
+            .unwrap();
+
+        // Nothing is written until the cassette is saved or dropped
+        assert!(!path.exists());
+        drop(recorder);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("secret-key"));
+        assert!(contents.contains(REDACTED));
+
+        let replayer = CassetteTransport::new(
+            Arc::new(Cassette::replay(&path).unwrap()),
+            Arc::new(MockTransport::new()),
+        );
+
+        // Query order and body key order do not matter
+        let body = serde_json::json!({ "format": { "type": "mp3" }, "text": "hi" });
+        let response = replayer
+            .send(request(Method::POST, "https://api.hume.ai/v0/tts?a=1&b=2", Some(body)))
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), r#"{"generations":[]}"#);
+
+        // Each interaction is served once
+        let body = serde_json::json!({ "text": "hi", "format": { "type": "mp3" } });
+        let result = replayer
+            .send(request(Method::POST, "https://api.hume.ai/v0/tts?a=1&b=2", Some(body)))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replay_requires_matching_body() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassette.json");
+
+        let inner = MockTransport::new().with_response(Method::POST, "/v0/tts", MockResponse::new(200));
+        let recorder = CassetteTransport::new(Arc::new(Cassette::record(&path)), Arc::new(inner));
+        recorder
+            .send(request(Method::POST, "https://api.hume.ai/v0/tts", Some(serde_json::json!({ "text": "a" }))))
+            .await
+            .unwrap();
+        recorder.cassette().save().unwrap();
+
+        let replayer = CassetteTransport::new(
+            Arc::new(Cassette::replay(&path).unwrap()),
+            Arc::new(MockTransport::new()),
+        );
+        let result = replayer
+            .send(request(Method::POST, "https://api.hume.ai/v0/tts", Some(serde_json::json!({ "text": "b" }))))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_token_exchange_is_redacted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassette.json");
+
+        let inner = MockTransport::new().with_json(
+            Method::POST,
+            "/oauth2-cc/token",
+            200,
+            serde_json::json!({ "access_token": "live-token", "token_type": "Bearer", "expires_in": 1800 }),
+        );
+        let cassette = Arc::new(Cassette::record(&path));
+        let recorder = CassetteTransport::new(cassette.clone(), Arc::new(inner));
+        let endpoints = crate::core::endpoints::Endpoints::new("https://api.hume.ai").unwrap();
+        let token = crate::core::auth::request_access_token(&recorder, &endpoints, "key", "top-secret")
+            .await
+            .unwrap();
+        assert_eq!(token.access_token.expose(), "live-token");
+        cassette.save().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("live-token"));
+        assert!(!contents.contains("top-secret"));
+        assert!(contents.contains("expires_in"));
+    }
+
+    #[test]
+    fn test_binary_bodies_round_trip() {
+        let response = RecordedResponse {
+            status: 200,
+            headers: BTreeMap::new(),
+            body: None,
+            body_base64: Some(base64::engine::general_purpose::STANDARD.encode([0xff, 0x00, 0x10])),
+        };
+
+        let response = response.to_response().unwrap();
+        let bytes = tokio_test::block_on(response.bytes()).unwrap();
+        assert_eq!(bytes.as_ref(), &[0xff, 0x00, 0x10]);
+    }
+
+    #[test]
+    fn test_socket_record_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassette.json");
+        let url = Url::parse("wss://api.hume.ai/v0/evi/chat?api_key=secret&config_id=abc").unwrap();
+
+        let cassette = Arc::new(Cassette::record(&path));
+        let mut recorder = cassette.record_socket(&url);
+        recorder.sent(r#"{"type":"user_input","text":"hi"}"#);
+        recorder.received(r#"{"type":"assistant_end"}"#);
+        drop(recorder);
+        cassette.save().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("secret"));
+
+        let cassette = Cassette::replay(&path).unwrap();
+        let other_key = Url::parse("wss://api.hume.ai/v0/evi/chat?config_id=abc&api_key=other").unwrap();
+        let mut replay = cassette.replay_socket(&other_key).unwrap();
+        assert!(replay.send(r#"{"text":"hi","type":"user_input"}"#).is_ok());
+        assert_eq!(replay.receive().as_deref(), Some(r#"{"type":"assistant_end"}"#));
+        assert_eq!(replay.receive(), None);
+        assert!(cassette.replay_socket(&other_key).is_err());
+    }
+}
@@ -0,0 +1,237 @@
+//! Circuit breaker for repeated server failures
+//!
+//! After a configured number of consecutive server errors or connection
+//! failures the breaker opens and requests fail fast with
+//! [`Error::CircuitOpen`] instead of reaching the API. Once the cooldown has
+//! elapsed the breaker half-opens and lets a single probe request through;
+//! its outcome closes or re-opens the circuit.
+
+use crate::core::error::{Error, Result};
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Circuit breaker configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that trip the breaker
+    pub failure_threshold: u32,
+    /// How long the breaker stays open before allowing a probe request
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// Create a new configuration
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+        }
+    }
+}
+
+/// Current state of a circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests fail fast
+    Open,
+    /// The cooldown has elapsed and a probe request is allowed
+    HalfOpen,
+}
+
+/// Tracks consecutive failures and decides whether requests may be sent
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probe_started: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Create a closed circuit breaker
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(Inner {
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_started: None,
+            }),
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    /// Get the current state
+    pub fn state(&self) -> CircuitState {
+        let inner = self.lock();
+        match inner.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() >= self.config.cooldown => CircuitState::HalfOpen,
+            Some(_) => CircuitState::Open,
+        }
+    }
+
+    /// Get the number of consecutive failures recorded
+    pub fn consecutive_failures(&self) -> u32 {
+        self.lock().consecutive_failures
+    }
+
+    /// Check whether a request may be sent
+    ///
+    /// Returns [`Error::CircuitOpen`] while the circuit is open, and while a
+    /// half-open probe is already in flight.
+    pub fn check(&self) -> Result<()> {
+        let mut inner = self.lock();
+        let Some(opened_at) = inner.opened_at else {
+            return Ok(());
+        };
+
+        let elapsed = opened_at.elapsed();
+        if elapsed < self.config.cooldown {
+            return Err(Error::CircuitOpen {
+                retry_in: self.config.cooldown - elapsed,
+            });
+        }
+
+        // Allow a single probe; a probe that never reported back is replaced after another cooldown
+        match inner.probe_started {
+            Some(started) if started.elapsed() < self.config.cooldown => Err(Error::CircuitOpen {
+                retry_in: self.config.cooldown - started.elapsed(),
+            }),
+            _ => {
+                inner.probe_started = Some(Instant::now());
+                Ok(())
+            }
+        }
+    }
+
+    /// Record a successful request, closing the circuit
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        if inner.opened_at.is_some() {
+            tracing::info!("Circuit breaker closed");
+        }
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.probe_started = None;
+    }
+
+    /// Record a failed request, opening the circuit once the threshold is reached
+    pub fn record_failure(&self) {
+        let mut inner = self.lock();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+
+        if inner.probe_started.is_some() || inner.consecutive_failures >= self.config.failure_threshold {
+            if inner.opened_at.is_none() || inner.probe_started.is_some() {
+                tracing::warn!(
+                    "Circuit breaker opened after {} consecutive failures",
+                    inner.consecutive_failures
+                );
+            }
+            inner.opened_at = Some(Instant::now());
+            inner.probe_started = None;
+        }
+    }
+
+    /// Record the outcome of a request
+    ///
+    /// Server errors and connection failures count as failures. Other errors,
+    /// such as validation or client errors, show the API is reachable and
+    /// count as successes. Rate limiting is ignored.
+    pub fn record<T>(&self, result: &Result<T>) {
+        match result {
+            Ok(_) => self.record_success(),
+            Err(Error::RateLimit { .. } | Error::CircuitOpen { .. }) => {}
+            Err(error) if is_circuit_failure(error) => self.record_failure(),
+            Err(_) => self.record_success(),
+        }
+    }
+
+    /// Close the circuit and clear the failure count
+    pub fn reset(&self) {
+        self.record_success();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Determine if an error counts towards tripping the circuit breaker
+pub fn is_circuit_failure(error: &Error) -> bool {
+    match error {
+        Error::Http(e) => e.is_connect() || e.is_timeout(),
+        Error::Api { status, .. } => *status >= 500,
+        Error::Timeout | Error::Connect(_) | Error::Dns(_) => true,
+        Error::HandshakeRejected { status, .. } => *status >= 500,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server_error() -> Result<()> {
+        Err(Error::api(503, "unavailable".into(), None, None))
+    }
+
+    #[test]
+    fn test_trips_after_threshold() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig::new(2, Duration::from_secs(60)));
+
+        breaker.record(&server_error());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        breaker.record(&server_error());
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(breaker.check(), Err(Error::CircuitOpen { .. })));
+    }
+
+    #[test]
+    fn test_client_errors_reset_failures() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig::new(2, Duration::from_secs(60)));
+
+        breaker.record(&server_error());
+        breaker.record::<()>(&Err(Error::api(404, "not found".into(), None, None)));
+        breaker.record(&server_error());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_probe() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig::new(1, Duration::ZERO));
+
+        breaker.record(&server_error());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // A failed probe re-opens the circuit, a successful one closes it
+        assert!(breaker.check().is_ok());
+        breaker.record(&server_error());
+        assert!(breaker.check().is_ok());
+        breaker.record(&Ok(()));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 0);
+    }
+}
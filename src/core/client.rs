@@ -1,10 +1,24 @@
 //! Main Hume client implementation
 
 use crate::core::{
-    auth::{generate_access_token, Auth, AuthToken},
+    auth::{request_access_token, Auth, AuthToken, ClientCredentials, WebSocketAuth},
+    cassette::Cassette,
+    circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState},
+    config::{ApiDefaults, HumeConfig, Profile, AUTH_ENV},
+    endpoints::Endpoints,
     error::{Error, Result},
     http::{HttpClient, HttpClientBuilder as InternalHttpClientBuilder},
+    metrics::Metrics,
+    middleware::Middleware,
+    proxy::ProxyConfig,
+    rate_limit::RateLimiter,
+    retry::{RetryClassifier, RetryConfig},
+    secret::Secret,
+    socket::WsConnector,
+    tls::{TlsConfig, TlsVersion},
+    transport::{ReqwestTransport, Transport},
 };
+use rustls::ClientConfig;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -13,6 +27,7 @@ use std::time::Duration;
 pub struct HumeClient {
     pub(crate) http: Arc<HttpClient>,
     pub(crate) base_url: String,
+    pub(crate) defaults: ApiDefaults,
 }
 
 impl HumeClient {
@@ -21,13 +36,11 @@ impl HumeClient {
         HumeClientBuilder::new(api_key).build()
     }
 
-    /// Create a new client from environment variables
-    /// 
-    /// Reads the API key from the HUME_API_KEY environment variable
+    /// Create a new client from environment variables and the config file
+    ///
+    /// See [`HumeClientBuilder::from_env`].
     pub fn from_env() -> Result<Self> {
-        let api_key = std::env::var("HUME_API_KEY")
-            .map_err(|_| Error::config("HUME_API_KEY environment variable not set"))?;
-        Self::new(api_key)
+        HumeClientBuilder::from_env()?.build()
     }
 
     /// Create a new client builder
@@ -40,6 +53,11 @@ impl HumeClient {
         &self.base_url
     }
 
+    /// Get the REST and WebSocket endpoints
+    pub fn endpoints(&self) -> &Endpoints {
+        self.http.endpoints()
+    }
+
     /// Get a reference to the HTTP client
     pub fn http(&self) -> &HttpClient {
         &self.http
@@ -50,6 +68,21 @@ impl HumeClient {
         self.http.auth.as_ref()
     }
 
+    /// Get the per-API defaults
+    pub fn defaults(&self) -> &ApiDefaults {
+        &self.defaults
+    }
+
+    /// Get the circuit breaker, if one is configured
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreaker> {
+        self.http.circuit_breaker().map(|breaker| breaker.as_ref())
+    }
+
+    /// Get the circuit breaker state, if one is configured
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.circuit_breaker().map(CircuitBreaker::state)
+    }
+
     /// Create a TTS client
     pub fn tts(&self) -> crate::tts::TtsClient {
         crate::tts::TtsClient::from(self.clone())
@@ -67,33 +100,164 @@ impl HumeClient {
 
     /// Generate an access token using API key and secret key
     pub async fn generate_access_token(&self, api_key: &str, secret_key: &str) -> Result<AuthToken> {
-        generate_access_token(&self.http.client, &self.base_url, api_key, secret_key).await
+        request_access_token(self.http.transport.as_ref(), self.endpoints(), api_key, secret_key).await
     }
 }
 
 /// Builder for creating Hume clients
 #[derive(Debug, Default)]
 pub struct HumeClientBuilder {
-    api_key: Option<String>,
+    api_key: Option<Secret>,
     access_token: Option<AuthToken>,
+    client_credentials: Option<ClientCredentials>,
     base_url: Option<String>,
+    websocket_base_url: Option<String>,
+    websocket_auth: WebSocketAuth,
     timeout: Option<Duration>,
+    retry_config: Option<RetryConfig>,
     max_retries: Option<u32>,
+    deadline: Option<Duration>,
+    idempotency_keys: Option<bool>,
+    retry_classifier: Option<Arc<dyn RetryClassifier>>,
+    transport: Option<Arc<dyn Transport>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    cassette: Option<Arc<Cassette>>,
+    metrics: Option<Arc<dyn Metrics>>,
+    proxy: Option<String>,
+    proxy_auth: Option<(String, Secret)>,
+    tls: TlsConfig,
+    defaults: ApiDefaults,
 }
 
 impl HumeClientBuilder {
     /// Create a new builder with an API key
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
-            api_key: Some(api_key.into()),
+            api_key: Some(Secret::new(api_key)),
             ..Default::default()
         }
     }
 
+    /// Create a builder from the config file and environment variables
+    ///
+    /// The selected [`Profile`] from [`HumeConfig::load`] is applied first,
+    /// then `HUME_API_KEY` and `HUME_BASE_URL` override it. Environment
+    /// credentials use API-key auth unless `HUME_AUTH=client_credentials`
+    /// is set, in which case `HUME_SECRET_KEY` (or the profile's secret key)
+    /// is used to generate access tokens. `HUME_AUTH=api_key` forces API-key
+    /// auth even when the profile has a secret key.
+    ///
+    /// When `HUME_API_KEY` is set, a config file that cannot be read, or
+    /// whose selected profile is missing, is skipped with a warning.
+    pub fn from_env() -> Result<Self> {
+        Self::from_sources(HumeConfig::load(), |name| {
+            std::env::var(name).ok().filter(|value| !value.is_empty())
+        })
+    }
+
+    /// Apply the selected profile of `config` and then the variables returned by `env`
+    fn from_sources(config: Result<HumeConfig>, env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let profile = config.and_then(|config| Ok(config.active_profile()?.cloned()));
+        let profile = match profile {
+            Ok(profile) => profile,
+            Err(error) if env("HUME_API_KEY").is_some() => {
+                tracing::warn!("Ignoring the Hume config file: {}", error);
+                None
+            }
+            Err(error) => return Err(error),
+        };
+        Self::from_lookup(profile.as_ref(), env)
+    }
+
+    /// Apply a profile and then the variables returned by `env`
+    fn from_lookup(profile: Option<&Profile>, env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let mut builder = match profile {
+            Some(profile) => Self::default().profile(profile),
+            None => Self::default(),
+        };
+
+        let profile_key = |key: Option<&Secret>| key.map(|key| key.expose().to_string());
+        let profile_api_key = profile_key(profile.and_then(|profile| profile.api_key.as_ref()));
+        let profile_secret_key = profile_key(profile.and_then(|profile| profile.secret_key.as_ref()));
+
+        let api_key = env("HUME_API_KEY");
+        match env(AUTH_ENV).as_deref() {
+            None => {
+                if let Some(api_key) = api_key {
+                    builder = builder.api_key(api_key);
+                }
+            }
+            Some("api_key") => {
+                if let Some(api_key) = api_key.or(profile_api_key) {
+                    builder = builder.api_key(api_key);
+                }
+            }
+            Some("client_credentials") => {
+                match (api_key.or(profile_api_key), env("HUME_SECRET_KEY").or(profile_secret_key)) {
+                    (Some(api_key), Some(secret_key)) => {
+                        builder = builder.client_credentials(api_key, secret_key)
+                    }
+                    _ => {
+                        return Err(Error::config(
+                            "HUME_AUTH=client_credentials requires HUME_API_KEY and HUME_SECRET_KEY",
+                        ))
+                    }
+                }
+            }
+            Some(other) => {
+                return Err(Error::config(format!(
+                    "Invalid HUME_AUTH value '{}', expected 'api_key' or 'client_credentials'",
+                    other
+                )))
+            }
+        }
+        if let Some(base_url) = env("HUME_BASE_URL") {
+            builder = builder.base_url(base_url);
+        }
+
+        if builder.api_key.is_none() && builder.access_token.is_none() && builder.client_credentials.is_none() {
+            return Err(Error::config(
+                "HUME_API_KEY environment variable not set and no credentials found in the config profile",
+            ));
+        }
+
+        Ok(builder)
+    }
+
+    /// Apply the settings from a config file profile
+    pub fn profile(mut self, profile: &Profile) -> Self {
+        match (&profile.api_key, &profile.secret_key) {
+            (Some(api_key), Some(secret_key)) => {
+                self = self.client_credentials(api_key.expose(), secret_key.expose())
+            }
+            (Some(api_key), None) => self = self.api_key(api_key.expose()),
+            _ => {}
+        }
+        if let Some(base_url) = &profile.base_url {
+            self = self.base_url(base_url);
+        }
+        if let Some(websocket_url) = &profile.websocket_url {
+            self = self.websocket_base_url(websocket_url);
+        }
+        if let Some(timeout) = profile.timeout() {
+            self = self.timeout(timeout);
+        }
+        if let Some(max_retries) = profile.max_retries {
+            self = self.max_retries(max_retries);
+        }
+        if let Some(proxy) = &profile.proxy {
+            self = self.proxy(proxy);
+        }
+        self.defaults(profile.defaults())
+    }
+
     /// Set the API key
     pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
-        self.api_key = Some(api_key.into());
+        self.api_key = Some(Secret::new(api_key));
         self.access_token = None;
+        self.client_credentials = None;
         self
     }
 
@@ -106,13 +270,14 @@ impl HumeClientBuilder {
     pub fn access_token(mut self, token: AuthToken) -> Self {
         self.access_token = Some(token);
         self.api_key = None;
+        self.client_credentials = None;
         self
     }
 
     /// Set the access token (alias for consistency with documentation)
     pub fn with_access_token(self, token: impl Into<String>) -> Self {
         let auth_token = AuthToken {
-            access_token: token.into(),
+            access_token: Secret::new(token),
             token_type: "Bearer".to_string(),
             expires_in: 3600, // Default 1 hour
             created_at: chrono::Utc::now(),
@@ -120,6 +285,22 @@ impl HumeClientBuilder {
         self.access_token(auth_token)
     }
 
+    /// Authenticate with access tokens generated from an API key and secret key
+    ///
+    /// Tokens are generated on first use, cached, and refreshed shortly before
+    /// they expire or when a request is rejected with 401.
+    pub fn client_credentials(self, api_key: impl Into<String>, secret_key: impl Into<String>) -> Self {
+        self.with_client_credentials(ClientCredentials::new(api_key, secret_key))
+    }
+
+    /// Set preconfigured client credentials
+    pub fn with_client_credentials(mut self, credentials: ClientCredentials) -> Self {
+        self.client_credentials = Some(credentials);
+        self.api_key = None;
+        self.access_token = None;
+        self
+    }
+
     /// Set the base URL (defaults to https://api.hume.ai)
     pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
         self.base_url = Some(base_url.into());
@@ -131,6 +312,135 @@ impl HumeClientBuilder {
         self.base_url(base_url)
     }
 
+    /// Set a separate base URL for WebSocket connections
+    ///
+    /// Defaults to the base URL with a `ws://` or `wss://` scheme.
+    pub fn websocket_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.websocket_base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set how WebSocket handshakes carry credentials
+    ///
+    /// Defaults to [`WebSocketAuth::Headers`]; use [`WebSocketAuth::QueryParam`]
+    /// only for servers that do not accept handshake headers.
+    pub fn websocket_auth(mut self, mode: WebSocketAuth) -> Self {
+        self.websocket_auth = mode;
+        self
+    }
+
+    /// Set the transport used to send HTTP requests
+    ///
+    /// Defaults to [`ReqwestTransport`](crate::core::transport::ReqwestTransport).
+    /// Use [`MockTransport`](crate::core::transport::MockTransport) to serve
+    /// scripted responses in unit tests.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Append a middleware to the request chain
+    ///
+    /// Middleware wraps every REST request attempt, in the order added.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Enable client-side rate limiting
+    ///
+    /// Requests wait for the matching bucket before they are sent, and
+    /// `retry-after` responses pause the bucket for the requested time.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(Arc::new(rate_limiter));
+        self
+    }
+
+    /// Enable a circuit breaker for repeated server failures
+    ///
+    /// After `failure_threshold` consecutive 5xx responses or connection
+    /// failures, requests fail fast with [`Error::CircuitOpen`] until the
+    /// cooldown has elapsed and a probe request succeeds.
+    pub fn circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(config);
+        self
+    }
+
+    /// Record or replay REST and WebSocket traffic with a cassette
+    ///
+    /// In record mode requests go to the API and are written to the cassette
+    /// file with credentials redacted. In replay mode recorded responses are
+    /// served without network access.
+    pub fn cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(Arc::new(cassette));
+        self
+    }
+
+    /// Report request latencies, retries, streamed bytes and WebSocket
+    /// session lifetimes to `metrics`
+    ///
+    /// See [`core::metrics`](crate::core::metrics) for the tracing spans
+    /// emitted alongside.
+    pub fn metrics(mut self, metrics: impl Metrics + 'static) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+
+    /// Route HTTP and WebSocket connections through a proxy
+    ///
+    /// Accepts `http://`, `https://`, `socks5://` and `socks5h://` URLs; see
+    /// [`ProxyConfig`]. REST requests ignore it when a custom transport is set.
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Authenticate with the proxy
+    pub fn proxy_basic_auth(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.proxy_auth = Some((username.into(), Secret::new(password)));
+        self
+    }
+
+    /// Set the TLS settings for HTTP and WebSocket connections
+    ///
+    /// REST requests ignore them when a custom transport is set.
+    pub fn tls_config(mut self, tls: TlsConfig) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Trust the PEM-encoded root certificates, e.g. a private CA
+    pub fn add_root_certificate_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.tls = self.tls.add_root_certificate_pem(pem);
+        self
+    }
+
+    /// Set whether the built-in root certificates are trusted (default: true)
+    pub fn tls_built_in_root_certs(mut self, enabled: bool) -> Self {
+        self.tls = self.tls.built_in_roots(enabled);
+        self
+    }
+
+    /// Present a client certificate for mutual TLS
+    ///
+    /// Takes a PEM-encoded certificate chain and private key.
+    pub fn client_identity_pem(mut self, cert_chain: impl Into<Vec<u8>>, private_key: impl Into<Vec<u8>>) -> Self {
+        self.tls = self.tls.client_identity_pem(cert_chain, private_key);
+        self
+    }
+
+    /// Set the minimum TLS protocol version
+    pub fn min_tls_version(mut self, version: TlsVersion) -> Self {
+        self.tls = self.tls.min_version(version);
+        self
+    }
+
+    /// Set the per-API defaults used by the service clients
+    pub fn defaults(mut self, defaults: ApiDefaults) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
     /// Set the default request timeout
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(timeout);
@@ -143,37 +453,162 @@ impl HumeClientBuilder {
         self
     }
 
+    /// Set the default overall time budget for a request
+    ///
+    /// Unlike [`timeout`](Self::timeout), which applies to each attempt, the
+    /// deadline bounds all attempts together with the delays between them.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Set whether create requests get a generated idempotency key
+    ///
+    /// Enabled by default. Requests creating resources, such as
+    /// [`BatchClient::create_job`](crate::expression_measurement::batch::BatchClient::create_job)
+    /// and the EVI `create` and `create_version` methods, send an
+    /// `Idempotency-Key` header that stays the same across retries. A key set
+    /// with
+    /// [`RequestOptions::with_idempotency_key`](crate::core::request::RequestOptions::with_idempotency_key)
+    /// is used instead of the generated one. Other non-idempotent requests,
+    /// such as TTS synthesis, are only retried when given a key that way.
+    pub fn idempotency_keys(mut self, enabled: bool) -> Self {
+        self.idempotency_keys = Some(enabled);
+        self
+    }
+
+    /// Set the retry configuration, including backoff intervals and multiplier
+    ///
+    /// Use [`RetryPolicyBuilder`](crate::core::retry::RetryPolicyBuilder) to
+    /// build one. Values set with [`max_retries`](Self::max_retries),
+    /// [`deadline`](Self::deadline) and
+    /// [`idempotency_keys`](Self::idempotency_keys) take precedence over it,
+    /// whichever is called first.
+    pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = Some(retry_config);
+        self
+    }
+
+    /// Set the classifier deciding which failed requests are retried
+    ///
+    /// Defaults to [`DefaultRetryClassifier`](crate::core::retry::DefaultRetryClassifier).
+    pub fn retry_classifier(mut self, classifier: impl RetryClassifier + 'static) -> Self {
+        self.retry_classifier = Some(Arc::new(classifier));
+        self
+    }
+
+    /// Build a [`blocking::HumeClient`](crate::blocking::HumeClient) with its own runtime
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> Result<crate::blocking::HumeClient> {
+        crate::blocking::HumeClient::from_builder(self)
+    }
+
     /// Build the client
     pub fn build(self) -> Result<HumeClient> {
         let base_url = self.base_url.unwrap_or_else(|| crate::DEFAULT_BASE_URL.to_string());
+        let mut endpoints = Endpoints::new(&base_url)?;
+        if let Some(websocket_base_url) = &self.websocket_base_url {
+            endpoints = endpoints.with_websocket_base(websocket_base_url)?;
+        }
 
-        let auth = if let Some(token) = self.access_token {
+        let auth = if let Some(credentials) = self.client_credentials {
+            Some(Auth::ClientCredentials(credentials))
+        } else if let Some(token) = self.access_token {
             Some(Auth::access_token(token))
         } else if let Some(api_key) = self.api_key {
-            Some(Auth::api_key(api_key))
+            Some(Auth::ApiKey(api_key))
         } else {
-            return Err(Error::config("Either api_key or access_token must be provided"));
+            return Err(Error::config(
+                "Either api_key, access_token or client_credentials must be provided",
+            ));
         };
 
-        let mut http_builder = InternalHttpClientBuilder::new(base_url.clone());
+        let mut http_builder = InternalHttpClientBuilder::new(&base_url).endpoints(endpoints);
         
         if let Some(auth) = auth {
             http_builder = http_builder.auth(auth);
         }
         
+        let proxy = match &self.proxy {
+            Some(url) => {
+                let proxy = ProxyConfig::new(url)?;
+                Some(match &self.proxy_auth {
+                    Some((username, password)) => proxy.basic_auth(username, password.expose()),
+                    None => proxy,
+                })
+            }
+            None => None,
+        };
+        // WebSocket connections always use the built config, so they trust
+        // the same roots as REST requests
+        let tls = self.tls.build()?;
+
+        if let Some(transport) = self.transport {
+            http_builder = http_builder.transport(transport);
+        } else if proxy.is_some() || !self.tls.is_default() {
+            let mut client = ReqwestTransport::client_builder();
+            if let Some(proxy) = &proxy {
+                client = client.proxy(proxy.to_reqwest()?);
+            }
+            if !self.tls.is_default() {
+                client = client.use_preconfigured_tls(ClientConfig::clone(&tls));
+            }
+            http_builder = http_builder.transport(Arc::new(ReqwestTransport::from(client.build()?)));
+        }
+        http_builder = http_builder
+            .ws_connector(WsConnector::new(proxy, Some(tls)))
+            .websocket_auth(self.websocket_auth);
+
+        for middleware in self.middleware {
+            http_builder = http_builder.middleware(middleware);
+        }
+
+        if let Some(rate_limiter) = self.rate_limiter {
+            http_builder = http_builder.rate_limiter(rate_limiter);
+        }
+
+        if let Some(config) = self.circuit_breaker {
+            http_builder = http_builder.circuit_breaker(Arc::new(CircuitBreaker::new(config)));
+        }
+
+        if let Some(cassette) = self.cassette {
+            http_builder = http_builder.cassette(cassette);
+        }
+
+        if let Some(metrics) = self.metrics {
+            http_builder = http_builder.metrics(metrics);
+        }
+
         if let Some(timeout) = self.timeout {
             http_builder = http_builder.timeout(timeout);
         }
         
+        if let Some(retry_config) = self.retry_config {
+            http_builder = http_builder.retry_config(retry_config);
+        }
+
         if let Some(max_retries) = self.max_retries {
             http_builder = http_builder.max_retries(max_retries);
         }
 
+        if let Some(deadline) = self.deadline {
+            http_builder = http_builder.deadline(deadline);
+        }
+
+        if let Some(enabled) = self.idempotency_keys {
+            http_builder = http_builder.idempotency_keys(enabled);
+        }
+
+        if let Some(classifier) = self.retry_classifier {
+            http_builder = http_builder.retry_classifier(classifier);
+        }
+
         let http = http_builder.build()?;
 
         Ok(HumeClient {
             http: Arc::new(http),
             base_url,
+            defaults: self.defaults,
         })
     }
 }
@@ -202,9 +637,102 @@ mod tests {
         assert_eq!(client.base_url(), custom_url);
     }
 
+    #[test]
+    fn test_builder_with_client_credentials() {
+        let client = HumeClientBuilder::new("test-api-key")
+            .client_credentials("test-api-key", "test-secret-key")
+            .build()
+            .expect("Failed to build client");
+
+        assert!(matches!(client.auth(), Some(Auth::ClientCredentials(_))));
+    }
+
+    #[test]
+    fn test_retry_overrides_apply_over_retry_config() {
+        let retry_config = RetryConfig {
+            initial_backoff: Duration::from_millis(5),
+            ..Default::default()
+        };
+        let client = HumeClientBuilder::new("test-api-key")
+            .max_retries(7)
+            .deadline(Duration::from_secs(20))
+            .retry_config(retry_config)
+            .idempotency_keys(false)
+            .build()
+            .unwrap();
+
+        let config = client.http().retry_config();
+        assert_eq!(config.max_retries, 7);
+        assert_eq!(config.deadline, Some(Duration::from_secs(20)));
+        assert!(!config.idempotency_keys);
+        assert_eq!(config.initial_backoff, Duration::from_millis(5));
+    }
+
     #[test]
     fn test_builder_requires_auth() {
         let result = HumeClientBuilder::default().build();
         assert!(result.is_err());
     }
+
+    fn env_auth(profile: Option<&Profile>, vars: &[(&str, &str)]) -> Result<Option<Auth>> {
+        let env = |name: &str| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string());
+        let client = HumeClientBuilder::from_lookup(profile, env)?.build()?;
+        Ok(client.auth().cloned())
+    }
+
+    #[test]
+    fn test_env_defaults_to_api_key_auth() {
+        let vars = [("HUME_API_KEY", "key"), ("HUME_SECRET_KEY", "secret")];
+        assert!(matches!(env_auth(None, &vars).unwrap(), Some(Auth::ApiKey(_))));
+    }
+
+    #[test]
+    fn test_env_client_credentials_on_request() {
+        let vars = [
+            ("HUME_API_KEY", "key"),
+            ("HUME_SECRET_KEY", "secret"),
+            ("HUME_AUTH", "client_credentials"),
+        ];
+        assert!(matches!(env_auth(None, &vars).unwrap(), Some(Auth::ClientCredentials(_))));
+
+        let vars = [("HUME_API_KEY", "key"), ("HUME_AUTH", "client_credentials")];
+        assert!(env_auth(None, &vars).is_err());
+
+        let vars = [("HUME_API_KEY", "key"), ("HUME_AUTH", "oauth")];
+        assert!(env_auth(None, &vars).is_err());
+    }
+
+    #[test]
+    fn test_env_key_skips_bad_config_file() {
+        let env = |name: &str| (name == "HUME_API_KEY").then(|| "env-key".to_string());
+        let config = HumeConfig::parse("[profiles.default]\nnew_setting = true\n");
+        assert!(config.is_err());
+        let client = HumeClientBuilder::from_sources(config, env).unwrap().build().unwrap();
+        assert!(matches!(client.auth(), Some(Auth::ApiKey(_))));
+
+        let config = HumeConfig::parse("not toml [");
+        assert!(HumeClientBuilder::from_sources(config, env).is_ok());
+        let config = HumeConfig::parse("not toml [");
+        assert!(HumeClientBuilder::from_sources(config, |_| None).is_err());
+    }
+
+    #[test]
+    fn test_env_auth_with_profile() {
+        let profile = Profile {
+            api_key: Some(Secret::new("profile-key")),
+            secret_key: Some(Secret::new("profile-secret")),
+            ..Default::default()
+        };
+
+        // A profile with a secret key asks for client credentials
+        assert!(matches!(env_auth(Some(&profile), &[]).unwrap(), Some(Auth::ClientCredentials(_))));
+        assert!(matches!(
+            env_auth(Some(&profile), &[("HUME_AUTH", "api_key")]).unwrap(),
+            Some(Auth::ApiKey(_))
+        ));
+        assert!(matches!(
+            env_auth(Some(&profile), &[("HUME_API_KEY", "env-key")]).unwrap(),
+            Some(Auth::ApiKey(_))
+        ));
+    }
 }
\ No newline at end of file
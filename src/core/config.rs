@@ -0,0 +1,265 @@
+//! Profile-based configuration files
+//!
+//! Client settings can be kept in a TOML file with one named profile per
+//! environment. The file is read from `$HUME_CONFIG` if set, otherwise from
+//! `$XDG_CONFIG_HOME/hume/config.toml` or `~/.config/hume/config.toml`
+//! (`%APPDATA%\hume\config.toml` on Windows). The profile is selected with
+//! `HUME_PROFILE`, falling back to `default_profile` and then `default`.
+//!
+//! ```toml
+//! default_profile = "prod"
+//!
+//! [profiles.prod]
+//! api_key = "..."
+//! timeout_secs = 30
+//! max_retries = 3
+//!
+//! [profiles.prod.evi]
+//! config_id = "..."
+//!
+//! [profiles.staging]
+//! api_key = "..."
+//! secret_key = "..."
+//! base_url = "https://staging.api.hume.ai"
+//! proxy = "http://proxy.internal:8080"
+//!
+//! [profiles.staging.tts]
+//! voice = "Ava Song"
+//! ```
+
+use crate::core::{
+    client::HumeClientBuilder,
+    error::{Error, Result},
+    secret::Secret,
+};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Environment variable overriding the config file path
+pub const CONFIG_PATH_ENV: &str = "HUME_CONFIG";
+
+/// Environment variable selecting the profile
+pub const PROFILE_ENV: &str = "HUME_PROFILE";
+
+/// Environment variable selecting `api_key` or `client_credentials` auth
+pub const AUTH_ENV: &str = "HUME_AUTH";
+
+/// Profile used when none is selected
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Contents of a Hume config file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HumeConfig {
+    /// Profile used when `HUME_PROFILE` is not set
+    #[serde(default)]
+    pub default_profile: Option<String>,
+    /// Named profiles
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// Client settings for one environment
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// API key
+    pub api_key: Option<Secret>,
+    /// Secret key; with `api_key`, authenticates with client credentials
+    pub secret_key: Option<Secret>,
+    /// Base URL for the API
+    pub base_url: Option<String>,
+    /// Base URL for WebSocket connections, if different from `base_url`
+    pub websocket_url: Option<String>,
+    /// Default request timeout in seconds
+    pub timeout_secs: Option<u64>,
+    /// Maximum number of retries for failed requests
+    pub max_retries: Option<u32>,
+    /// Proxy URL for HTTP and WebSocket connections
+    pub proxy: Option<String>,
+    /// Text-to-Speech defaults
+    #[serde(default)]
+    pub tts: TtsDefaults,
+    /// Empathic Voice Interface defaults
+    #[serde(default)]
+    pub evi: EviDefaults,
+}
+
+/// Per-API defaults applied by the service clients
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiDefaults {
+    /// Text-to-Speech defaults
+    pub tts: TtsDefaults,
+    /// Empathic Voice Interface defaults
+    pub evi: EviDefaults,
+}
+
+/// Text-to-Speech defaults
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TtsDefaults {
+    /// Voice name used by TTS utterances and stream requests that do not
+    /// specify one
+    pub voice: Option<String>,
+}
+
+/// Empathic Voice Interface defaults
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EviDefaults {
+    /// Config ID used when connecting without one
+    pub config_id: Option<String>,
+    /// Config version used with the default config ID
+    pub config_version: Option<u32>,
+}
+
+impl HumeConfig {
+    /// Parse a config file's contents
+    pub fn parse(contents: &str) -> Result<Self> {
+        toml::from_str(contents).map_err(|e| Error::config(format!("Invalid config file: {}", e)))
+    }
+
+    /// Read a config file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        Self::parse(&contents)
+            .map_err(|e| Error::config(format!("{} ({})", e, path.display())))
+    }
+
+    /// Read the config file from the default location
+    ///
+    /// Returns an empty config if the file does not exist.
+    pub fn load() -> Result<Self> {
+        match Self::default_path() {
+            Some(path) if path.exists() => Self::from_file(path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Get the default config file path
+    pub fn default_path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os(CONFIG_PATH_ENV) {
+            return Some(PathBuf::from(path));
+        }
+
+        let config_dir = if cfg!(windows) {
+            std::env::var_os("APPDATA").map(PathBuf::from)
+        } else {
+            std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        };
+
+        config_dir.map(|dir| dir.join("hume").join("config.toml"))
+    }
+
+    /// Get a profile by name
+    pub fn profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles.get(name)
+    }
+
+    /// Get the name of the selected profile
+    ///
+    /// Uses `HUME_PROFILE`, then `default_profile`, then `default`.
+    pub fn active_profile_name(&self) -> String {
+        std::env::var(PROFILE_ENV)
+            .ok()
+            .filter(|name| !name.is_empty())
+            .or_else(|| self.default_profile.clone())
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+    }
+
+    /// Get the selected profile
+    ///
+    /// Fails if a profile was explicitly selected but does not exist.
+    pub fn active_profile(&self) -> Result<Option<&Profile>> {
+        let name = self.active_profile_name();
+        match self.profile(&name) {
+            Some(profile) => Ok(Some(profile)),
+            None if name == DEFAULT_PROFILE => Ok(None),
+            None => Err(Error::config(format!("Profile '{}' not found in config file", name))),
+        }
+    }
+}
+
+impl Profile {
+    /// Get the default request timeout
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs.map(Duration::from_secs)
+    }
+
+    /// Get the per-API defaults
+    pub fn defaults(&self) -> ApiDefaults {
+        ApiDefaults {
+            tts: self.tts.clone(),
+            evi: self.evi.clone(),
+        }
+    }
+
+    /// Apply this profile's settings to a client builder
+    pub fn apply(&self, builder: HumeClientBuilder) -> HumeClientBuilder {
+        builder.profile(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        default_profile = "prod"
+
+        [profiles.prod]
+        api_key = "prod-key"
+        timeout_secs = 10
+        max_retries = 1
+
+        [profiles.prod.evi]
+        config_id = "config-1"
+
+        [profiles.staging]
+        api_key = "staging-key"
+        secret_key = "staging-secret"
+        base_url = "https://staging.example.com"
+    "#;
+
+    #[test]
+    fn test_parse_profiles() {
+        let config = HumeConfig::parse(CONFIG).unwrap();
+        assert_eq!(config.default_profile.as_deref(), Some("prod"));
+
+        let prod = config.profile("prod").unwrap();
+        assert_eq!(prod.api_key.as_ref().map(Secret::expose), Some("prod-key"));
+        assert_eq!(prod.timeout(), Some(Duration::from_secs(10)));
+        assert_eq!(prod.evi.config_id.as_deref(), Some("config-1"));
+
+        let staging = config.profile("staging").unwrap();
+        assert_eq!(staging.base_url.as_deref(), Some("https://staging.example.com"));
+        assert_eq!(staging.tts, TtsDefaults::default());
+    }
+
+    #[test]
+    fn test_unknown_fields_are_rejected() {
+        let result = HumeConfig::parse("[profiles.prod]\napi_kee = \"typo\"\n");
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn test_apply_profile() {
+        let config = HumeConfig::parse(CONFIG).unwrap();
+        let client = config
+            .profile("staging")
+            .unwrap()
+            .apply(HumeClientBuilder::default())
+            .build()
+            .unwrap();
+
+        assert_eq!(client.base_url(), "https://staging.example.com");
+        assert!(matches!(client.auth(), Some(crate::core::auth::Auth::ClientCredentials(_))));
+    }
+}
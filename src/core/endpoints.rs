@@ -0,0 +1,185 @@
+//! REST and WebSocket URL construction
+//!
+//! [`Endpoints`] holds the client's REST base URL and the WebSocket base URL,
+//! which is derived from the REST base (`https://` becomes `wss://`, `http://`
+//! becomes `ws://`) unless configured separately. Path segments and query
+//! parameters are percent-encoded.
+
+use crate::core::error::{Error, Result};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use url::Url;
+
+/// Characters escaped in a path segment
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Base URLs for the REST and WebSocket APIs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoints {
+    rest_base: Url,
+    websocket_base: Url,
+}
+
+impl Endpoints {
+    /// Create endpoints from the REST base URL
+    ///
+    /// The WebSocket base uses the same host and path with a `ws://` or
+    /// `wss://` scheme.
+    pub fn new(base_url: &str) -> Result<Self> {
+        let rest_base = parse_base(base_url)?;
+        let websocket_scheme = match rest_base.scheme() {
+            "https" => "wss",
+            "http" => "ws",
+            "wss" | "ws" => rest_base.scheme(),
+            scheme => {
+                return Err(Error::config(format!(
+                    "Unsupported base URL scheme '{}'; expected http or https",
+                    scheme
+                )))
+            }
+        };
+
+        let mut websocket_base = rest_base.clone();
+        websocket_base
+            .set_scheme(websocket_scheme)
+            .map_err(|_| Error::config(format!("Cannot derive a WebSocket URL from '{}'", base_url)))?;
+
+        Ok(Self {
+            rest_base,
+            websocket_base,
+        })
+    }
+
+    /// Use a separate base URL for WebSocket connections
+    ///
+    /// `http://` and `https://` URLs are mapped to `ws://` and `wss://`.
+    pub fn with_websocket_base(mut self, websocket_base: &str) -> Result<Self> {
+        self.websocket_base = Self::new(websocket_base)?.websocket_base;
+        Ok(self)
+    }
+
+    /// Get the REST base URL
+    pub fn rest_base(&self) -> &Url {
+        &self.rest_base
+    }
+
+    /// Get the WebSocket base URL
+    pub fn websocket_base(&self) -> &Url {
+        &self.websocket_base
+    }
+
+    /// Build a REST URL from a path, as returned by [`Endpoints::path`], and query parameters
+    pub fn rest_url<I, K, V>(&self, path: &str, query: I) -> Url
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        join(&self.rest_base, path, query)
+    }
+
+    /// Build a WebSocket URL from a path, as returned by [`Endpoints::path`], and query parameters
+    pub fn websocket_url<I, K, V>(&self, path: &str, query: I) -> Url
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        join(&self.websocket_base, path, query)
+    }
+
+    /// Build an API path from its segments, percent-encoding each one
+    ///
+    /// `Endpoints::path(&["v0", "evi", "configs", id])` gives `/v0/evi/configs/{id}`.
+    pub fn path<S: AsRef<str>>(segments: &[S]) -> String {
+        segments
+            .iter()
+            .map(|segment| format!("/{}", utf8_percent_encode(segment.as_ref(), PATH_SEGMENT)))
+            .collect()
+    }
+}
+
+fn parse_base(base_url: &str) -> Result<Url> {
+    let url = Url::parse(base_url)
+        .map_err(|e| Error::config(format!("Invalid base URL '{}': {}", base_url, e)))?;
+    if url.host_str().is_none() {
+        return Err(Error::config(format!("Base URL '{}' has no host", base_url)));
+    }
+    Ok(url)
+}
+
+fn join<I, K, V>(base: &Url, path: &str, query: I) -> Url
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut url = base.clone();
+    url.set_path(&format!("{}{}", base.path().trim_end_matches('/'), path));
+    url.set_query(None);
+    url.query_pairs_mut().extend_pairs(query);
+    if url.query() == Some("") {
+        url.set_query(None);
+    }
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NO_QUERY: [(&str, &str); 0] = [];
+
+    #[test]
+    fn test_websocket_base_follows_rest_scheme() {
+        let endpoints = Endpoints::new("https://api.hume.ai").unwrap();
+        assert_eq!(endpoints.websocket_base().as_str(), "wss://api.hume.ai/");
+
+        let endpoints = Endpoints::new("http://localhost:8080/gateway/").unwrap();
+        assert_eq!(
+            endpoints.websocket_url("/v0/evi/chat", NO_QUERY).as_str(),
+            "ws://localhost:8080/gateway/v0/evi/chat"
+        );
+
+        assert!(matches!(Endpoints::new("ftp://api.hume.ai"), Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn test_separate_websocket_base() {
+        let endpoints = Endpoints::new("https://api.hume.ai")
+            .unwrap()
+            .with_websocket_base("https://stream.example.com")
+            .unwrap();
+        assert_eq!(
+            endpoints.rest_url("/v0/tts", NO_QUERY).as_str(),
+            "https://api.hume.ai/v0/tts"
+        );
+        assert_eq!(
+            endpoints.websocket_url("/v0/stream/models", NO_QUERY).as_str(),
+            "wss://stream.example.com/v0/stream/models"
+        );
+    }
+
+    #[test]
+    fn test_path_and_query_are_encoded() {
+        let endpoints = Endpoints::new("https://api.hume.ai").unwrap();
+        let path = Endpoints::path(&["v0", "evi", "configs", "a/b c?"]);
+        assert_eq!(path, "/v0/evi/configs/a%2Fb%20c%3F");
+
+        let url = endpoints.websocket_url(&path, [("config_id", "x&y=z"), ("api_key", "k+/=")]);
+        assert_eq!(
+            url.as_str(),
+            "wss://api.hume.ai/v0/evi/configs/a%2Fb%20c%3F?config_id=x%26y%3Dz&api_key=k%2B%2F%3D"
+        );
+    }
+}
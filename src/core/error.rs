@@ -1,6 +1,9 @@
 //! Error types for the Hume SDK
 
+use crate::core::response::ResponseMeta;
+use std::{error::Error as StdError, fmt, io, str::FromStr};
 use thiserror::Error;
+use tokio_tungstenite::tungstenite::Error as WsError;
 
 /// A type alias for `Result<T, hume::Error>`.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -10,18 +13,46 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     /// HTTP client error
     #[error("HTTP client error: {0}")]
-    Http(#[from] reqwest::Error),
+    Http(#[source] reqwest::Error),
 
     /// JSON serialization/deserialization error
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// A response body did not match the expected type
+    #[error(transparent)]
+    Decode(Box<DecodeError>),
+
     /// WebSocket error
     #[error("WebSocket error: {0}")]
-    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
+    WebSocket(#[source] Box<WsError>),
+
+    /// Could not connect to the server
+    #[error("Connection error: {0}")]
+    Connect(String),
+
+    /// Could not resolve the server's host name
+    #[error("DNS error: {0}")]
+    Dns(String),
+
+    /// TLS handshake or certificate verification failed
+    #[error("TLS error: {0}")]
+    Tls(String),
+
+    /// The server rejected the WebSocket handshake
+    #[error("WebSocket handshake rejected (status {status})")]
+    HandshakeRejected {
+        /// HTTP status code of the handshake response
+        status: u16,
+        /// Response body, if any
+        body: Option<String>,
+    },
 
     /// API error returned by Hume
+    ///
+    /// New fields may be added, so match with `..`.
     #[error("API error (status {status}): {message}")]
+    #[non_exhaustive]
     Api {
         /// HTTP status code
         status: u16,
@@ -29,8 +60,12 @@ pub enum Error {
         message: String,
         /// Optional error code
         code: Option<String>,
+        /// Field-specific errors, for validation failures
+        field_errors: Vec<FieldError>,
         /// Raw response body
         body: Option<String>,
+        /// Status, headers, request ID, timing and attempts
+        meta: Option<Box<ResponseMeta>>,
     },
 
     /// Authentication error
@@ -68,11 +103,39 @@ pub enum Error {
         retry_after: Option<u64>,
     },
 
+    /// The operation was cancelled through its cancellation token
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    /// Circuit breaker is open after repeated server failures
+    #[error("Circuit breaker open, retry in {retry_in:?}")]
+    CircuitOpen {
+        /// Time until the circuit breaker allows a probe request
+        retry_in: std::time::Duration,
+    },
+
     /// Other errors
     #[error("{0}")]
     Other(String),
 }
 
+/// A response body that did not match the expected type
+#[derive(Error, Debug)]
+#[error("Failed to decode {target} at `{path}`: {source}")]
+pub struct DecodeError {
+    /// Rust type being decoded
+    pub target: &'static str,
+    /// JSON path of the value that failed, e.g. `voices[2].name`
+    pub path: String,
+    /// Type expected at `path`, as reported by serde
+    pub expected: Option<String>,
+    /// Raw response body, truncated
+    pub body: String,
+    /// The underlying JSON error
+    #[source]
+    pub source: serde_json::Error,
+}
+
 impl Error {
     /// Create a new API error
     pub fn api(status: u16, message: String, code: Option<String>, body: Option<String>) -> Self {
@@ -80,10 +143,32 @@ impl Error {
             status,
             message,
             code,
+            field_errors: Vec::new(),
             body,
+            meta: None,
         }
     }
 
+    /// Create a new API error from the error details in a response body
+    pub fn from_details(status: u16, details: ApiErrorDetails, body: Option<String>) -> Self {
+        Self::Api {
+            status,
+            message: details.message,
+            code: details.code,
+            field_errors: details.errors.unwrap_or_default(),
+            body,
+            meta: None,
+        }
+    }
+
+    /// Attach response metadata to an API error
+    pub fn with_meta(mut self, response_meta: ResponseMeta) -> Self {
+        if let Self::Api { meta, .. } = &mut self {
+            *meta = Some(Box::new(response_meta));
+        }
+        self
+    }
+
     /// Create a new authentication error
     pub fn auth(message: impl Into<String>) -> Self {
         Self::Auth(message.into())
@@ -114,18 +199,390 @@ impl Error {
         matches!(self, Self::RateLimit { .. })
     }
 
+    /// Returns true if the request was rejected by an open circuit breaker
+    pub fn is_circuit_open(&self) -> bool {
+        matches!(self, Self::CircuitOpen { .. })
+    }
+
     /// Returns true if this is a timeout error
     pub fn is_timeout(&self) -> bool {
         matches!(self, Self::Timeout)
     }
 
-    /// Get the status code if this is an API error
+    /// Returns true if the operation was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, Self::Cancelled)
+    }
+
+    /// Returns true if the server could not be reached
+    pub fn is_connect(&self) -> bool {
+        matches!(self, Self::Connect(_) | Self::Dns(_))
+    }
+
+    /// Get the response metadata if this is an API error
+    pub fn meta(&self) -> Option<&ResponseMeta> {
+        match self {
+            Self::Api { meta, .. } => meta.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Get the request ID assigned by the API, if known
+    pub fn request_id(&self) -> Option<&str> {
+        self.meta().and_then(|meta| meta.request_id.as_deref())
+    }
+
+    /// Get the status code if this is an API error or a rejected handshake
     pub fn status_code(&self) -> Option<u16> {
         match self {
-            Self::Api { status, .. } => Some(*status),
+            Self::Api { status, .. } | Self::HandshakeRejected { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Get the raw response body of an API or decode error
+    pub fn body(&self) -> Option<&str> {
+        match self {
+            Self::Api { body, .. } | Self::HandshakeRejected { body, .. } => body.as_deref(),
+            Self::Decode(error) => Some(&error.body),
+            _ => None,
+        }
+    }
+
+    /// Get the raw error code if this is an API error
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            Self::Api { code, .. } => code.as_deref(),
             _ => None,
         }
     }
+
+    /// Get the Hume error code if this is an API error with a code like `E0300`
+    pub fn hume_code(&self) -> Option<HumeCode> {
+        self.code().and_then(|code| code.parse().ok())
+    }
+
+    /// Get the field-specific errors if this is a validation failure
+    pub fn field_errors(&self) -> &[FieldError] {
+        match self {
+            Self::Api { field_errors, .. } => field_errors,
+            _ => &[],
+        }
+    }
+
+    /// Classify the error
+    ///
+    /// API errors are classified by their Hume error code when it has a
+    /// known kind, and by status code otherwise.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Api { status, .. } => self
+                .hume_code()
+                .map(|code| code.kind())
+                .filter(|kind| *kind != ErrorKind::Other)
+                .unwrap_or_else(|| ErrorKind::from_status(*status)),
+            Self::Http(error) => error
+                .status()
+                .map(|status| ErrorKind::from_status(status.as_u16()))
+                .unwrap_or(ErrorKind::Other),
+            Self::HandshakeRejected { status, .. } => ErrorKind::from_status(*status),
+            Self::WebSocket(_) | Self::Connect(_) | Self::Dns(_) | Self::CircuitOpen { .. } => {
+                ErrorKind::Transient
+            }
+            Self::Auth(_) => ErrorKind::Authentication,
+            Self::Validation(_) => ErrorKind::InvalidRequest,
+            Self::Timeout => ErrorKind::Timeout,
+            Self::RateLimit { .. } => ErrorKind::RateLimited,
+            Self::Cancelled => ErrorKind::Cancelled,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            return Self::Timeout;
+        }
+        match classify_network_error(&error) {
+            Some(classified) => classified,
+            None if error.is_connect() => Self::Connect(error_chain(&error)),
+            None => Self::Http(error),
+        }
+    }
+}
+
+impl From<WsError> for Error {
+    fn from(error: WsError) -> Self {
+        match error {
+            WsError::Http(response) => Self::HandshakeRejected {
+                status: response.status().as_u16(),
+                body: response
+                    .body()
+                    .as_deref()
+                    .map(|body| String::from_utf8_lossy(body).into_owned()),
+            },
+            WsError::Tls(error) => Self::Tls(error_chain(&error)),
+            WsError::Io(error) => match classify_network_error(&error) {
+                Some(classified) => classified,
+                None => Self::WebSocket(Box::new(WsError::Io(error))),
+            },
+            error => Self::WebSocket(Box::new(error)),
+        }
+    }
+}
+
+/// Classify a transport failure by walking its source chain
+///
+/// Returns `None` when no cause in the chain is a timeout, DNS, TLS or
+/// connection failure.
+fn classify_network_error(error: &(dyn StdError + 'static)) -> Option<Error> {
+    let mut source = Some(error);
+    while let Some(cause) = source {
+        if cause.is::<rustls::Error>() {
+            return Some(Error::Tls(error_chain(error)));
+        }
+        if let Some(io_error) = cause.downcast_ref::<io::Error>() {
+            if io_error.get_ref().is_some_and(|inner| inner.is::<rustls::Error>()) {
+                return Some(Error::Tls(error_chain(error)));
+            }
+            match io_error.kind() {
+                io::ErrorKind::TimedOut => return Some(Error::Timeout),
+                io::ErrorKind::ConnectionRefused
+                | io::ErrorKind::ConnectionReset
+                | io::ErrorKind::ConnectionAborted
+                | io::ErrorKind::NotConnected
+                | io::ErrorKind::AddrNotAvailable => return Some(Error::Connect(error_chain(error))),
+                _ => {}
+            }
+        }
+        // Resolver failures have no dedicated type: hyper reports "dns error",
+        // the system resolver "failed to lookup address information"
+        let message = cause.to_string();
+        if message.starts_with("dns error") || message.contains("failed to lookup address") {
+            return Some(Error::Dns(error_chain(error)));
+        }
+        source = cause.source();
+    }
+    None
+}
+
+/// Format an error and its sources as `error: source: source`
+fn error_chain(error: &(dyn StdError + 'static)) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        let cause_message = cause.to_string();
+        if !message.ends_with(&cause_message) {
+            message.push_str(": ");
+            message.push_str(&cause_message);
+        }
+        source = cause.source();
+    }
+    message
+}
+
+/// What an error means for the caller
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The request or message was malformed or failed validation
+    InvalidRequest,
+    /// The credentials are missing, invalid or lack permission
+    Authentication,
+    /// The account is out of credits or over its usage limit
+    QuotaExceeded,
+    /// The requested resource does not exist
+    NotFound,
+    /// The request conflicts with the current state of a resource
+    Conflict,
+    /// Too many requests were sent
+    RateLimited,
+    /// The request or session timed out
+    Timeout,
+    /// A temporary server or upstream failure; retrying may succeed
+    Transient,
+    /// The caller cancelled the operation
+    Cancelled,
+    /// An EVI chat hit, or is about to hit, its inactivity or duration limit
+    ///
+    /// Not transient: reconnecting resumes the same limits.
+    SessionLimit,
+    /// Anything else
+    Other,
+}
+
+impl ErrorKind {
+    /// Classify an HTTP status code
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            400 | 422 => Self::InvalidRequest,
+            401 | 403 => Self::Authentication,
+            402 => Self::QuotaExceeded,
+            404 => Self::NotFound,
+            409 => Self::Conflict,
+            408 | 504 => Self::Timeout,
+            429 => Self::RateLimited,
+            500..=599 => Self::Transient,
+            _ => Self::Other,
+        }
+    }
+
+    /// Returns true if retrying the same request may succeed
+    pub fn is_transient(self) -> bool {
+        matches!(self, Self::RateLimited | Self::Timeout | Self::Transient)
+    }
+}
+
+/// Severity of a Hume error code, given by its letter prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// `E` codes: the request or session failed
+    Error,
+    /// `W` codes: the session continues, but something needs attention
+    Warning,
+    /// `I` codes: informational, e.g. why a chat was closed
+    Info,
+}
+
+/// A Hume error code such as `E0300`, `W0105` or `I0100`
+///
+/// Codes documented by Hume have their own variant. Well-formed codes this
+/// SDK does not know yet parse to [`HumeCode::Unknown`], which keeps the raw
+/// code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HumeCode {
+    /// `E0100`: a client message could not be parsed or is not a known type
+    InvalidMessage,
+    /// `E0101`: the session settings are invalid
+    InvalidSessionSettings,
+    /// `E0200`: the audio input could not be decoded
+    InvalidAudio,
+    /// `E0300`: the account has run out of credits
+    OutOfCredits,
+    /// `E0301`: the account has reached its monthly usage limit
+    UsageLimitReached,
+    /// `E0400`: the config does not exist
+    ConfigNotFound,
+    /// `E0401`: a tool referenced by the config does not exist
+    ToolNotFound,
+    /// `E0402`: the chat group to resume does not exist
+    ChatGroupNotFound,
+    /// `E0500`: the language model provider returned an error
+    LanguageModelError,
+    /// `E0501`: the language model provider did not respond in time
+    LanguageModelTimeout,
+    /// `E0700`: an internal server error
+    InternalError,
+    /// `W0101`: the chat will be closed soon if no input is received
+    InactivityWarning,
+    /// `W0105`: the chat is close to its maximum duration
+    MaxDurationWarning,
+    /// `I0100`: the chat was closed after a period of inactivity
+    InactivityTimeout,
+    /// `I0101`: the chat was closed after reaching its maximum duration
+    MaxDurationReached,
+    /// A well-formed code without a variant of its own
+    Unknown(String),
+}
+
+impl HumeCode {
+    /// Get the code as written by the API, e.g. `E0300`
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::InvalidMessage => "E0100",
+            Self::InvalidSessionSettings => "E0101",
+            Self::InvalidAudio => "E0200",
+            Self::OutOfCredits => "E0300",
+            Self::UsageLimitReached => "E0301",
+            Self::ConfigNotFound => "E0400",
+            Self::ToolNotFound => "E0401",
+            Self::ChatGroupNotFound => "E0402",
+            Self::LanguageModelError => "E0500",
+            Self::LanguageModelTimeout => "E0501",
+            Self::InternalError => "E0700",
+            Self::InactivityWarning => "W0101",
+            Self::MaxDurationWarning => "W0105",
+            Self::InactivityTimeout => "I0100",
+            Self::MaxDurationReached => "I0101",
+            Self::Unknown(code) => code,
+        }
+    }
+
+    /// Get the severity, given by the letter prefix
+    pub fn severity(&self) -> Severity {
+        match self.as_str().as_bytes().first() {
+            Some(b'W') => Severity::Warning,
+            Some(b'I') => Severity::Info,
+            _ => Severity::Error,
+        }
+    }
+
+    /// Get the number, e.g. `300` for `E0300`
+    pub fn number(&self) -> u16 {
+        self.as_str().get(1..).and_then(|digits| digits.parse().ok()).unwrap_or_default()
+    }
+
+    /// Classify the code
+    ///
+    /// Unknown codes are [`ErrorKind::Other`], so [`Error::kind`] falls back
+    /// to the HTTP status.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::InvalidMessage | Self::InvalidSessionSettings | Self::InvalidAudio => {
+                ErrorKind::InvalidRequest
+            }
+            Self::OutOfCredits | Self::UsageLimitReached => ErrorKind::QuotaExceeded,
+            Self::ConfigNotFound | Self::ToolNotFound | Self::ChatGroupNotFound => ErrorKind::NotFound,
+            Self::LanguageModelError | Self::InternalError => ErrorKind::Transient,
+            Self::LanguageModelTimeout => ErrorKind::Timeout,
+            Self::InactivityWarning
+            | Self::MaxDurationWarning
+            | Self::InactivityTimeout
+            | Self::MaxDurationReached => ErrorKind::SessionLimit,
+            Self::Unknown(_) => ErrorKind::Other,
+        }
+    }
+}
+
+impl fmt::Display for HumeCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Fails unless the code is `E`, `W` or `I` followed by four digits
+impl FromStr for HumeCode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let well_formed = s.len() == 5
+            && matches!(s.as_bytes()[0], b'E' | b'W' | b'I')
+            && s.bytes().skip(1).all(|b| b.is_ascii_digit());
+        if !well_formed {
+            return Err(Error::validation(format!("Invalid Hume error code: {}", s)));
+        }
+
+        Ok(match s {
+            "E0100" => Self::InvalidMessage,
+            "E0101" => Self::InvalidSessionSettings,
+            "E0200" => Self::InvalidAudio,
+            "E0300" => Self::OutOfCredits,
+            "E0301" => Self::UsageLimitReached,
+            "E0400" => Self::ConfigNotFound,
+            "E0401" => Self::ToolNotFound,
+            "E0402" => Self::ChatGroupNotFound,
+            "E0500" => Self::LanguageModelError,
+            "E0501" => Self::LanguageModelTimeout,
+            "E0700" => Self::InternalError,
+            "W0101" => Self::InactivityWarning,
+            "W0105" => Self::MaxDurationWarning,
+            "I0100" => Self::InactivityTimeout,
+            "I0101" => Self::MaxDurationReached,
+            code => Self::Unknown(code.to_string()),
+        })
+    }
 }
 
 /// API error details returned by Hume
@@ -140,7 +597,7 @@ pub struct ApiErrorDetails {
 }
 
 /// Field-specific error for validation
-#[derive(Debug, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
 pub struct FieldError {
     /// Field name
     pub field: String,
@@ -148,4 +605,132 @@ pub struct FieldError {
     pub message: String,
     /// Error code
     pub code: Option<String>,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_is_small() {
+        // Large payloads are boxed so `Result<T>` stays cheap to move
+        assert!(std::mem::size_of::<Error>() <= 112);
+    }
+
+    #[test]
+    fn test_parse_hume_code() {
+        let code: HumeCode = "E0300".parse().unwrap();
+        assert_eq!(code, HumeCode::OutOfCredits);
+        assert_eq!(code.kind(), ErrorKind::QuotaExceeded);
+        assert_eq!(code.to_string(), "E0300");
+
+        let code: HumeCode = "I0100".parse().unwrap();
+        assert_eq!(code, HumeCode::InactivityTimeout);
+        assert_eq!(code.severity(), Severity::Info);
+        assert_eq!(code.kind(), ErrorKind::SessionLimit);
+        assert!(!code.kind().is_transient());
+
+        let code: HumeCode = "E0599".parse().unwrap();
+        assert_eq!(code, HumeCode::Unknown("E0599".to_string()));
+        assert_eq!((code.severity(), code.number()), (Severity::Error, 599));
+        assert_eq!(code.kind(), ErrorKind::Other);
+
+        assert!("INVALID_INPUT".parse::<HumeCode>().is_err());
+        assert!("E300".parse::<HumeCode>().is_err());
+        assert!("E+300".parse::<HumeCode>().is_err());
+    }
+
+    #[test]
+    fn test_hume_code_mapping() {
+        let cases = [
+            ("E0100", HumeCode::InvalidMessage, ErrorKind::InvalidRequest),
+            ("E0101", HumeCode::InvalidSessionSettings, ErrorKind::InvalidRequest),
+            ("E0200", HumeCode::InvalidAudio, ErrorKind::InvalidRequest),
+            ("E0300", HumeCode::OutOfCredits, ErrorKind::QuotaExceeded),
+            ("E0301", HumeCode::UsageLimitReached, ErrorKind::QuotaExceeded),
+            ("E0400", HumeCode::ConfigNotFound, ErrorKind::NotFound),
+            ("E0401", HumeCode::ToolNotFound, ErrorKind::NotFound),
+            ("E0402", HumeCode::ChatGroupNotFound, ErrorKind::NotFound),
+            ("E0500", HumeCode::LanguageModelError, ErrorKind::Transient),
+            ("E0501", HumeCode::LanguageModelTimeout, ErrorKind::Timeout),
+            ("E0700", HumeCode::InternalError, ErrorKind::Transient),
+            ("W0101", HumeCode::InactivityWarning, ErrorKind::SessionLimit),
+            ("W0105", HumeCode::MaxDurationWarning, ErrorKind::SessionLimit),
+            ("I0100", HumeCode::InactivityTimeout, ErrorKind::SessionLimit),
+            ("I0101", HumeCode::MaxDurationReached, ErrorKind::SessionLimit),
+        ];
+        for (raw, code, kind) in cases {
+            let parsed: HumeCode = raw.parse().unwrap();
+            assert_eq!(parsed, code, "{}", raw);
+            assert_eq!(parsed.kind(), kind, "{}", raw);
+            assert_eq!(parsed.as_str(), raw);
+        }
+    }
+
+    #[test]
+    fn test_error_kind() {
+        let error = Error::api(400, "no credits".into(), Some("E0300".into()), None);
+        assert_eq!(error.kind(), ErrorKind::QuotaExceeded);
+
+        let error = Error::api(503, "unavailable".into(), Some("INTERNAL".into()), None);
+        assert_eq!(error.kind(), ErrorKind::Transient);
+        assert!(error.kind().is_transient());
+
+        assert_eq!(Error::RateLimit { retry_after: None }.kind(), ErrorKind::RateLimited);
+        assert_eq!(Error::validation("bad").kind(), ErrorKind::InvalidRequest);
+    }
+
+    #[test]
+    fn test_classify_websocket_errors() {
+        let response = tokio_tungstenite::tungstenite::http::Response::builder()
+            .status(403)
+            .body(Some(b"forbidden".to_vec()))
+            .unwrap();
+        let error = Error::from(WsError::Http(response));
+        assert!(matches!(error, Error::HandshakeRejected { status: 403, ref body } if body.as_deref() == Some("forbidden")));
+        assert_eq!(error.kind(), ErrorKind::Authentication);
+
+        let error = Error::from(WsError::Io(io::Error::new(io::ErrorKind::ConnectionRefused, "refused")));
+        assert!(matches!(error, Error::Connect(_)));
+        assert!(error.is_connect());
+
+        let error = Error::from(WsError::Io(io::Error::new(io::ErrorKind::TimedOut, "timed out")));
+        assert!(error.is_timeout());
+
+        let error = Error::from(WsError::Io(io::Error::other(rustls::Error::DecryptError)));
+        assert!(matches!(error, Error::Tls(_)));
+
+        let error = Error::from(WsError::ConnectionClosed);
+        assert!(matches!(error, Error::WebSocket(e) if matches!(*e, WsError::ConnectionClosed)));
+    }
+
+    #[tokio::test]
+    async fn test_classify_reqwest_errors() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let error = Error::from(reqwest::get(format!("http://{}", addr)).await.unwrap_err());
+        assert!(matches!(error, Error::Connect(_)), "{:?}", error);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let error = Error::from(client.get(format!("http://{}", addr)).send().await.unwrap_err());
+        assert!(error.is_timeout());
+    }
+
+    #[test]
+    fn test_error_from_details() {
+        let details: ApiErrorDetails = serde_json::from_str(
+            r#"{"message": "Invalid request", "code": "E0100", "errors": [{"field": "name", "message": "required", "code": null}]}"#,
+        )
+        .unwrap();
+        let error = Error::from_details(422, details, None);
+        assert_eq!(error.kind(), ErrorKind::InvalidRequest);
+        assert_eq!(error.field_errors().len(), 1);
+        assert_eq!(error.field_errors()[0].field, "name");
+    }
+}
@@ -1,42 +1,98 @@
 //! HTTP client implementation with retry logic
 
 use crate::core::{
-    auth::Auth,
+    auth::{Auth, WebSocketAuth},
+    cancel::{cancellable, cancellable_stream},
+    cassette::{Cassette, CassetteMode, CassetteTransport},
+    circuit_breaker::CircuitBreaker,
+    endpoints::Endpoints,
     error::{ApiErrorDetails, Error, Result},
+    metrics::{endpoint_label, Metrics, StreamDirection},
+    middleware::{Middleware, MiddlewareChain},
+    rate_limit::RateLimiter,
     request::RequestOptions,
-    retry::{retry_with_backoff, RetryConfig},
+    response::{decode_json, retry_delay, ResponseMeta, WithMeta},
+    retry::{
+        get_retry_after, is_idempotent_method, retry_with_classifier, DefaultRetryClassifier,
+        RetryClassifier, RetryConfig, RetryContext,
+    },
+    socket::{WebSocket, WsConnector},
+    transport::{HttpRequest, HttpResponse, ReqwestTransport, Transport},
 };
 use bytes::Bytes;
 use futures_util::{Stream, StreamExt};
-use reqwest::{header::HeaderMap, Method, Response, StatusCode};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
+    Method, StatusCode,
+};
 use serde::{de::DeserializeOwned, Serialize};
-use std::{pin::Pin, time::Duration};
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tokio_tungstenite::tungstenite::Error as WsError;
+use tracing::{field, Instrument};
+use uuid::Uuid;
+
+/// Header carrying the idempotency key of a non-idempotent request
+const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
 
 /// HTTP client with retry logic and error handling
 #[derive(Debug, Clone)]
 pub struct HttpClient {
-    pub(crate) client: reqwest::Client,
-    base_url: String,
+    pub(crate) transport: Arc<dyn Transport>,
+    endpoints: Endpoints,
     pub(crate) auth: Option<Auth>,
+    middleware: MiddlewareChain,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    cassette: Option<Arc<Cassette>>,
+    metrics: Option<Arc<dyn Metrics>>,
+    ws_connector: WsConnector,
+    websocket_auth: WebSocketAuth,
     default_timeout: Duration,
-    max_retries: u32,
+    retry_config: RetryConfig,
+    retry_classifier: Arc<dyn RetryClassifier>,
 }
 
 impl HttpClient {
     /// Create a new HTTP client
     pub fn new(base_url: String, auth: Option<Auth>) -> Result<Self> {
-        let client = reqwest::Client::builder()
-            .user_agent(format!("hume-rust-sdk/{}", crate::SDK_VERSION))
-            .timeout(Duration::from_secs(30))
-            .build()?;
-
-        Ok(Self {
-            client,
-            base_url,
+        let transport = ReqwestTransport::new()?;
+        Ok(Self::with_transport(Endpoints::new(&base_url)?, auth, Arc::new(transport)))
+    }
+
+    /// Create a new HTTP client that sends requests through the given transport
+    pub fn with_transport(endpoints: Endpoints, auth: Option<Auth>, transport: Arc<dyn Transport>) -> Self {
+        Self {
+            transport,
+            endpoints,
             auth,
+            middleware: MiddlewareChain::new(),
+            rate_limiter: None,
+            circuit_breaker: None,
+            cassette: None,
+            metrics: None,
+            ws_connector: WsConnector::default(),
+            websocket_auth: WebSocketAuth::default(),
             default_timeout: Duration::from_secs(30),
-            max_retries: 3,
-        })
+            retry_config: RetryConfig::default(),
+            retry_classifier: Arc::new(DefaultRetryClassifier),
+        }
+    }
+
+    /// Get the REST and WebSocket endpoints
+    pub fn endpoints(&self) -> &Endpoints {
+        &self.endpoints
+    }
+
+    /// Get the transport used to send requests
+    pub fn transport(&self) -> &Arc<dyn Transport> {
+        &self.transport
     }
 
     /// Set the authentication method
@@ -44,6 +100,54 @@ impl HttpClient {
         self.auth = Some(auth);
     }
 
+    /// Append a middleware to the request chain
+    pub fn add_middleware(&mut self, middleware: Arc<dyn Middleware>) {
+        self.middleware.push(middleware);
+    }
+
+    /// Set the client-side rate limiter
+    pub fn set_rate_limiter(&mut self, rate_limiter: Arc<RateLimiter>) {
+        self.rate_limiter = Some(rate_limiter);
+    }
+
+    /// Set the circuit breaker guarding requests
+    pub fn set_circuit_breaker(&mut self, circuit_breaker: Arc<CircuitBreaker>) {
+        self.circuit_breaker = Some(circuit_breaker);
+    }
+
+    /// Get the circuit breaker, if one is configured
+    pub fn circuit_breaker(&self) -> Option<&Arc<CircuitBreaker>> {
+        self.circuit_breaker.as_ref()
+    }
+
+    /// Record or replay REST and WebSocket traffic with a cassette
+    ///
+    /// Wraps the current transport, so set the transport first.
+    pub fn set_cassette(&mut self, cassette: Arc<Cassette>) {
+        self.transport = Arc::new(CassetteTransport::new(cassette.clone(), self.transport.clone()));
+        self.cassette = Some(cassette);
+    }
+
+    /// Get the cassette, if one is configured
+    pub fn cassette(&self) -> Option<&Arc<Cassette>> {
+        self.cassette.as_ref()
+    }
+
+    /// Set the metrics hooks receiving request and WebSocket measurements
+    pub fn set_metrics(&mut self, metrics: Arc<dyn Metrics>) {
+        self.metrics = Some(metrics);
+    }
+
+    /// Set the proxy and TLS settings used for WebSocket connections
+    pub(crate) fn set_ws_connector(&mut self, connector: WsConnector) {
+        self.ws_connector = connector;
+    }
+
+    /// Set how WebSocket handshakes carry credentials
+    pub fn set_websocket_auth(&mut self, mode: WebSocketAuth) {
+        self.websocket_auth = mode;
+    }
+
     /// Set the default timeout
     pub fn set_default_timeout(&mut self, timeout: Duration) {
         self.default_timeout = timeout;
@@ -51,7 +155,109 @@ impl HttpClient {
 
     /// Set the maximum number of retries
     pub fn set_max_retries(&mut self, max_retries: u32) {
-        self.max_retries = max_retries;
+        self.retry_config.max_retries = max_retries;
+    }
+
+    /// Set the retry configuration
+    pub fn set_retry_config(&mut self, retry_config: RetryConfig) {
+        self.retry_config = retry_config;
+    }
+
+    /// Set the classifier deciding which failed requests are retried
+    pub fn set_retry_classifier(&mut self, classifier: Arc<dyn RetryClassifier>) {
+        self.retry_classifier = classifier;
+    }
+
+    /// Get the default retry configuration
+    pub fn retry_config(&self) -> &RetryConfig {
+        &self.retry_config
+    }
+
+    /// Get the authentication to use for the next request
+    ///
+    /// For client credentials this generates or refreshes the access token as
+    /// needed and returns it as [`Auth::AccessToken`].
+    pub async fn resolve_auth(&self) -> Result<Option<Auth>> {
+        match &self.auth {
+            Some(Auth::ClientCredentials(credentials)) => {
+                let token = credentials.token(self.transport.as_ref(), &self.endpoints).await?;
+                Ok(Some(Auth::AccessToken(token)))
+            }
+            other => Ok(other.clone()),
+        }
+    }
+
+    /// Discard a cached client-credentials token, returning whether one could be refreshed
+    async fn invalidate_token(&self) -> bool {
+        match &self.auth {
+            Some(Auth::ClientCredentials(credentials)) => {
+                credentials.invalidate().await;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Open a WebSocket connection
+    ///
+    /// Credentials are added to the handshake according to the
+    /// [`WebSocketAuth`] mode. A handshake rejected with 401 is retried once
+    /// with a refreshed token when using client credentials. With a cassette
+    /// the session is recorded, or replayed without connecting. The session
+    /// runs inside a `hume.websocket` span.
+    pub(crate) async fn connect_websocket(&self, url: url::Url) -> Result<WebSocket> {
+        let endpoint = endpoint_label(url.path());
+        let span = tracing::info_span!("hume.websocket", endpoint = %endpoint, chat_id = field::Empty);
+        let mut socket = self.open_websocket(url).instrument(span.clone()).await?;
+        socket.set_telemetry(endpoint, span, self.metrics.clone());
+        Ok(socket)
+    }
+
+    async fn open_websocket(&self, url: url::Url) -> Result<WebSocket> {
+        if let Some(cassette) = self.cassette.as_ref().filter(|c| c.mode() == CassetteMode::Replay) {
+            // Credentials are redacted in cassettes, so any value matches
+            let (url, _) = self.websocket_auth.apply(&url, &Auth::api_key(String::new()));
+            return Ok(WebSocket::replay(cassette.replay_socket(&url)?));
+        }
+
+        let auth = self
+            .resolve_auth()
+            .await?
+            .ok_or_else(|| Error::auth("No authentication configured"))?;
+
+        let (mut socket_url, mut headers) = self.websocket_handshake(&url, &auth)?;
+        let ws_stream = match self.ws_connector.connect(socket_url.as_str(), headers).await {
+            Ok(ws_stream) => ws_stream,
+            Err(WsError::Http(response))
+                if response.status() == StatusCode::UNAUTHORIZED && self.invalidate_token().await =>
+            {
+                let auth = self
+                    .resolve_auth()
+                    .await?
+                    .ok_or_else(|| Error::auth("No authentication configured"))?;
+                (socket_url, headers) = self.websocket_handshake(&url, &auth)?;
+                self.ws_connector.connect(socket_url.as_str(), headers).await?
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        match &self.cassette {
+            Some(cassette) => {
+                let recorder = cassette.record_socket(&socket_url);
+                Ok(WebSocket::recording(ws_stream, recorder))
+            }
+            None => Ok(WebSocket::live(ws_stream)),
+        }
+    }
+
+    /// Build the socket URL and handshake headers for `auth`
+    fn websocket_handshake(&self, url: &url::Url, auth: &Auth) -> Result<(url::Url, HeaderMap)> {
+        let (url, header) = self.websocket_auth.apply(url, auth);
+        let mut headers = HeaderMap::new();
+        if let Some((name, value)) = header {
+            headers.insert(name, parse_header_value(&value)?);
+        }
+        Ok((url, headers))
     }
 
     /// Make a GET request
@@ -97,6 +303,89 @@ impl HttpClient {
         self.request(Method::DELETE, path, None::<()>, options).await
     }
 
+    /// Make a GET request, returning response metadata with the result
+    pub async fn get_with_response<T>(&self, path: &str, options: Option<RequestOptions>) -> Result<WithMeta<T>>
+    where
+        T: DeserializeOwned,
+    {
+        self.request_with_response(Method::GET, path, None::<()>, options).await
+    }
+
+    /// Make a POST request, returning response metadata with the result
+    pub async fn post_with_response<B, T>(
+        &self,
+        path: &str,
+        body: B,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<T>>
+    where
+        B: Serialize,
+        T: DeserializeOwned,
+    {
+        self.request_with_response(Method::POST, path, Some(body), options).await
+    }
+
+    /// Make a POST request creating a resource, returning response metadata with the result
+    ///
+    /// Unless [`RetryConfig::idempotency_keys`] is disabled, a request without
+    /// an idempotency key in its options gets a generated one, so it can be
+    /// retried without creating the resource twice.
+    pub async fn create_with_response<B, T>(
+        &self,
+        path: &str,
+        body: B,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<T>>
+    where
+        B: Serialize,
+        T: DeserializeOwned,
+    {
+        let mut options = options.unwrap_or_default();
+        // Generate the key once so every attempt, including the one after a
+        // token refresh, carries the same key
+        let retry_config = options.retry_config.as_ref().unwrap_or(&self.retry_config);
+        if options.idempotency_key.is_none() && retry_config.idempotency_keys {
+            options.idempotency_key = Some(Uuid::new_v4().to_string());
+        }
+        self.request_with_response(Method::POST, path, Some(body), Some(options)).await
+    }
+
+    /// Make a PUT request, returning response metadata with the result
+    pub async fn put_with_response<B, T>(
+        &self,
+        path: &str,
+        body: B,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<T>>
+    where
+        B: Serialize,
+        T: DeserializeOwned,
+    {
+        self.request_with_response(Method::PUT, path, Some(body), options).await
+    }
+
+    /// Make a PATCH request, returning response metadata with the result
+    pub async fn patch_with_response<B, T>(
+        &self,
+        path: &str,
+        body: B,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<T>>
+    where
+        B: Serialize,
+        T: DeserializeOwned,
+    {
+        self.request_with_response(Method::PATCH, path, Some(body), options).await
+    }
+
+    /// Make a DELETE request, returning response metadata with the result
+    pub async fn delete_with_response<T>(&self, path: &str, options: Option<RequestOptions>) -> Result<WithMeta<T>>
+    where
+        T: DeserializeOwned,
+    {
+        self.request_with_response(Method::DELETE, path, None::<()>, options).await
+    }
+
     /// Make a request that returns raw bytes
     pub async fn request_bytes(
         &self,
@@ -105,8 +394,19 @@ impl HttpClient {
         body: Option<impl Serialize>,
         options: Option<RequestOptions>,
     ) -> Result<Bytes> {
-        let response = self.execute_request(method, path, body, options).await?;
-        Ok(response.bytes().await?)
+        Ok(self.request_bytes_with_response(method, path, body, options).await?.data)
+    }
+
+    /// Make a request that returns raw bytes, with response metadata
+    pub async fn request_bytes_with_response(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<impl Serialize>,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Bytes>> {
+        let (response, meta) = self.execute_request(method, path, body, options).await?;
+        Ok(WithMeta::new(response.bytes().await?, meta))
     }
 
     /// Make a request that returns a stream
@@ -117,11 +417,21 @@ impl HttpClient {
         body: Option<impl Serialize>,
         options: Option<RequestOptions>,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>> {
-        let response = self.execute_request(method, path, body, options).await?;
-        let stream = response
-            .bytes_stream()
-            .map(|result| result.map_err(Error::from));
-        Ok(Box::pin(stream))
+        let cancellation = options.as_ref().and_then(|options| options.cancellation.clone());
+        let (response, _) = self.execute_request(method, path, body, options).await?;
+        let mut stream = response.into_stream();
+        if let Some(metrics) = self.metrics.clone() {
+            let endpoint = endpoint_label(path);
+            stream = Box::pin(stream.inspect(move |chunk| {
+                if let Ok(bytes) = chunk {
+                    metrics.bytes_streamed(&endpoint, StreamDirection::Received, bytes.len());
+                }
+            }));
+        }
+        Ok(match cancellation {
+            Some(token) => Box::pin(cancellable_stream(stream, token)),
+            None => stream,
+        })
     }
 
     /// Make a request with automatic retry
@@ -136,125 +446,270 @@ impl HttpClient {
         B: Serialize,
         T: DeserializeOwned,
     {
-        let response = self.execute_request(method, path, body, options).await?;
-        let status = response.status();
-        let headers = response.headers().clone();
+        Ok(self.request_with_response(method, path, body, options).await?.data)
+    }
 
-        if status.is_success() {
-            response.json::<T>().await.map_err(Error::from)
-        } else {
-            let body_text = response.text().await.ok();
-            self.handle_error_response(status, headers, body_text)
-        }
+    /// Make a request with automatic retry, returning response metadata with the result
+    pub async fn request_with_response<B, T>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<B>,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<T>>
+    where
+        B: Serialize,
+        T: DeserializeOwned,
+    {
+        let cancellation = options.as_ref().and_then(|options| options.cancellation.clone());
+        let (response, meta) = self.execute_request(method, path, body, options).await?;
+        let bytes = cancellable(cancellation.as_ref(), response.bytes()).await?;
+        let data = decode_json::<T>(&bytes)?;
+        Ok(WithMeta::new(data, meta))
     }
 
     /// Execute a request with retry logic
+    ///
+    /// A 401 response is retried once with a refreshed token when using
+    /// client credentials. API errors carry the same metadata as successful
+    /// responses. The request runs inside a `hume.request` span.
     async fn execute_request(
         &self,
         method: Method,
         path: &str,
         body: Option<impl Serialize>,
         options: Option<RequestOptions>,
-    ) -> Result<Response> {
-        let url = format!("{}{}", self.base_url, path);
+    ) -> Result<(HttpResponse, ResponseMeta)> {
         let options = options.unwrap_or_default();
-        
-        let retry_config = RetryConfig {
-            max_retries: options.max_retries.unwrap_or(self.max_retries),
-            ..Default::default()
-        };
-
-        retry_with_backoff(&retry_config, || async {
-            let mut request = self.client.request(method.clone(), &url);
-
-            // Set auth header
-            if let Some(auth) = &self.auth {
-                if let Some((header_name, header_value)) = auth.header_value() {
-                    request = request.header(header_name, header_value);
+        let body = body
+            .map(|body| serde_json::to_vec(&body))
+            .transpose()?
+            .map(Bytes::from);
+
+        let endpoint = endpoint_label(path);
+        let span = tracing::info_span!(
+            "hume.request",
+            endpoint = %endpoint,
+            method = %method,
+            status = field::Empty,
+            attempts = field::Empty,
+            request_id = field::Empty,
+        );
+
+        let started = Instant::now();
+        let attempts = AtomicU32::new(0);
+        let result = async {
+            match self.send_with_retry(&method, path, &body, &options, started, &attempts).await {
+                Err(error)
+                    if error.status_code() == Some(StatusCode::UNAUTHORIZED.as_u16())
+                        && self.invalidate_token().await =>
+                {
+                    tracing::debug!("Access token rejected, retrying with a refreshed token");
+                    self.send_with_retry(&method, path, &body, &options, started, &attempts).await
                 }
+                result => result,
             }
-
-            // Set custom headers
-            for (key, value) in &options.headers {
-                request = request.header(key, value);
+        }
+        .instrument(span.clone())
+        .await;
+
+        let attempts = attempts.into_inner();
+        let result = match result {
+            Ok(response) => {
+                let meta = ResponseMeta::new(
+                    response.status(),
+                    response.headers().clone(),
+                    started.elapsed(),
+                    attempts,
+                );
+                Ok((response, meta))
             }
-
-            // Set query parameters
-            for (key, value) in &options.query {
-                request = request.query(&[(key, value)]);
+            Err(mut error) => {
+                if let Error::Api { meta: Some(meta), .. } = &mut error {
+                    meta.elapsed = started.elapsed();
+                    meta.attempts = attempts;
+                }
+                Err(error)
             }
+        };
 
-            // Set timeout
-            let timeout = options.timeout.unwrap_or(self.default_timeout);
-            request = request.timeout(timeout);
+        let status = match &result {
+            Ok((_, meta)) => Some(meta.status.as_u16()),
+            Err(error) => error.status_code(),
+        };
+        let request_id = match &result {
+            Ok((_, meta)) => meta.request_id.as_deref(),
+            Err(error) => error.request_id(),
+        };
+        span.record("attempts", attempts);
+        if let Some(status) = status {
+            span.record("status", status);
+        }
+        if let Some(request_id) = request_id {
+            span.record("request_id", request_id);
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.request_completed(&method, &endpoint, status, started.elapsed());
+        }
+        result
+    }
 
-            // Set body
-            if let Some(body) = &body {
-                request = request.json(body);
+    /// Send a request, retrying transient failures with backoff
+    ///
+    /// The deadline, if any, counts from `started`.
+    async fn send_with_retry(
+        &self,
+        method: &Method,
+        path: &str,
+        body: &Option<Bytes>,
+        options: &RequestOptions,
+        started: Instant,
+        attempts: &AtomicU32,
+    ) -> Result<HttpResponse> {
+        let mut retry_config = options
+            .retry_config
+            .clone()
+            .unwrap_or_else(|| self.retry_config.clone());
+        if let Some(max_retries) = options.max_retries {
+            retry_config.max_retries = max_retries;
+        }
+        if let Some(deadline) = options.deadline {
+            retry_config.deadline = Some(deadline);
+        }
+        retry_config.deadline = retry_config
+            .deadline
+            .map(|deadline| deadline.saturating_sub(started.elapsed()));
+        let classifier = options
+            .retry_classifier
+            .as_ref()
+            .unwrap_or(&self.retry_classifier);
+        let mut context = RetryContext::new(method.clone(), path);
+        context.cancellation = options.cancellation.clone();
+        context.idempotency_key = options.idempotency_key.clone();
+
+        retry_with_classifier(&retry_config, classifier.as_ref(), context, || async {
+            let mut request = self.build_request(method, path, body, options).await?;
+            self.middleware.before_send(&mut request).await?;
+
+            // Fail fast without reaching the API, or using up a rate limit
+            // token, while the circuit is open
+            if let Some(breaker) = &self.circuit_breaker {
+                breaker.check()?;
             }
 
-            let response = request.send().await?;
-            
-            let status = response.status();
-            
-            // Check if we should retry based on status
-            if status == StatusCode::TOO_MANY_REQUESTS {
-                let retry_after = response
-                    .headers()
-                    .get("retry-after")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|v| v.parse::<u64>().ok());
-                    
-                return Err(Error::RateLimit { retry_after });
+            let permit = match &self.rate_limiter {
+                Some(limiter) => Some(limiter.acquire(path).await),
+                None => None,
+            };
+
+            let attempt = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+            if attempt > 1 {
+                if let Some(metrics) = &self.metrics {
+                    metrics.request_retried(method, &endpoint_label(path), attempt);
+                }
+            }
+            let result = self
+                .send_attempt(&request)
+                .instrument(tracing::debug_span!("hume.attempt", attempt))
+                .await;
+            if let Some(breaker) = &self.circuit_breaker {
+                breaker.record(&result);
             }
-            
-            if status.is_server_error() {
-                return Err(Error::api(
-                    status.as_u16(),
-                    format!("Server error: {}", status),
-                    None,
-                    None,
-                ));
+            // The concurrency slot stays taken until the body has been read
+            let result = match permit {
+                Some(permit) => result.map(|response| response.hold(permit)),
+                None => result,
+            };
+            if let Err(error) = &result {
+                if let (Some(limiter), Some(retry_after)) = (&self.rate_limiter, get_retry_after(error)) {
+                    limiter.observe_retry_after(path, retry_after).await;
+                }
+                self.middleware.on_error(&request, error).await;
             }
-            
-            Ok(response)
+            result
         })
         .await
     }
 
-    /// Check if an error should trigger a retry
-    fn should_retry(&self, error: &reqwest::Error) -> bool {
-        error.is_connect() || error.is_timeout()
+    /// Send a single attempt through the transport and middleware
+    async fn send_attempt(&self, request: &HttpRequest) -> Result<HttpResponse> {
+        let mut response = self.transport.send(request.clone()).await?;
+        self.middleware.after_receive(request, &mut response).await?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        // Map error statuses so the retry classifier can inspect them
+        let headers = response.headers().clone();
+        let body_text = response.text().await.ok();
+        self.handle_error_response(status, headers, body_text)
     }
 
-    /// Check if a status code should trigger a retry
-    fn should_retry_status(&self, status: StatusCode) -> bool {
-        status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+    /// Build a single request attempt
+    async fn build_request(
+        &self,
+        method: &Method,
+        path: &str,
+        body: &Option<Bytes>,
+        options: &RequestOptions,
+    ) -> Result<HttpRequest> {
+        let url = self.endpoints.rest_url(path, &options.query);
+        let mut request = HttpRequest::new(method.clone(), url);
+
+        // Set auth header
+        if let Some(auth) = self.resolve_auth().await? {
+            if let Some((header_name, header_value)) = auth.header_value() {
+                request.headers.insert(header_name, parse_header_value(&header_value)?);
+            }
+        }
+
+        // Set custom headers
+        for (key, value) in &options.headers {
+            let name = HeaderName::from_bytes(key.as_bytes())
+                .map_err(|_| Error::validation(format!("Invalid header name: {}", key)))?;
+            request.headers.insert(name, parse_header_value(value)?);
+        }
+
+        // Set idempotency key
+        if let Some(key) = options.idempotency_key.as_ref().filter(|_| !is_idempotent_method(method)) {
+            request.headers.insert(IDEMPOTENCY_KEY, parse_header_value(key)?);
+        }
+
+        // Set timeout
+        request.timeout = Some(options.timeout.unwrap_or(self.default_timeout));
+
+        // Set body
+        if let Some(body) = body {
+            request
+                .headers
+                .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            request.body = Some(body.clone());
+        }
+
+        Ok(request)
     }
 
     /// Handle error responses
     fn handle_error_response<T>(&self, status: StatusCode, headers: HeaderMap, body: Option<String>) -> Result<T> {
-        let retry_after = headers
-            .get("retry-after")
-            .and_then(|v| v.to_str().ok())
-            .and_then(|v| v.parse::<u64>().ok());
-
         if status == StatusCode::TOO_MANY_REQUESTS {
+            // Round up so the retry does not arrive before the window resets
+            let retry_after = retry_delay(&headers).map(|delay| delay.as_secs_f64().ceil() as u64);
             return Err(Error::RateLimit { retry_after });
         }
 
         // Try to parse error details from body
-        let (message, code) = if let Some(body_text) = &body {
-            if let Ok(error_details) = serde_json::from_str::<ApiErrorDetails>(body_text) {
-                (error_details.message, error_details.code)
-            } else {
-                (format!("HTTP {} error", status.as_u16()), None)
-            }
-        } else {
-            (format!("HTTP {} error", status.as_u16()), None)
+        let details = body
+            .as_deref()
+            .and_then(|body_text| serde_json::from_str::<ApiErrorDetails>(body_text).ok());
+        let error = match details {
+            Some(details) => Error::from_details(status.as_u16(), details, body),
+            None => Error::api(status.as_u16(), format!("HTTP {} error", status.as_u16()), None, body),
         };
 
-        Err(Error::api(status.as_u16(), message, code, body))
+        let meta = ResponseMeta::new(status, headers, Duration::ZERO, 1);
+        Err(error.with_meta(meta))
     }
 }
 
@@ -262,9 +717,22 @@ impl HttpClient {
 #[derive(Debug)]
 pub struct HttpClientBuilder {
     base_url: String,
+    endpoints: Option<Endpoints>,
     auth: Option<Auth>,
+    transport: Option<Arc<dyn Transport>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    cassette: Option<Arc<Cassette>>,
+    metrics: Option<Arc<dyn Metrics>>,
+    ws_connector: Option<WsConnector>,
+    websocket_auth: Option<WebSocketAuth>,
     timeout: Option<Duration>,
+    retry_config: Option<RetryConfig>,
     max_retries: Option<u32>,
+    deadline: Option<Duration>,
+    idempotency_keys: Option<bool>,
+    retry_classifier: Option<Arc<dyn RetryClassifier>>,
 }
 
 impl HttpClientBuilder {
@@ -272,18 +740,85 @@ impl HttpClientBuilder {
     pub fn new(base_url: impl Into<String>) -> Self {
         Self {
             base_url: base_url.into(),
+            endpoints: None,
             auth: None,
+            transport: None,
+            middleware: Vec::new(),
+            rate_limiter: None,
+            circuit_breaker: None,
+            cassette: None,
+            metrics: None,
+            ws_connector: None,
+            websocket_auth: None,
             timeout: None,
+            retry_config: None,
             max_retries: None,
+            deadline: None,
+            idempotency_keys: None,
+            retry_classifier: None,
         }
     }
 
+    /// Set the endpoints, replacing the base URL passed to [`HttpClientBuilder::new`]
+    pub fn endpoints(mut self, endpoints: Endpoints) -> Self {
+        self.endpoints = Some(endpoints);
+        self
+    }
+
     /// Set the authentication method
     pub fn auth(mut self, auth: Auth) -> Self {
         self.auth = Some(auth);
         self
     }
 
+    /// Set the transport used to send requests (defaults to [`ReqwestTransport`])
+    pub fn transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Append a middleware to the request chain
+    pub fn middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Set the client-side rate limiter
+    pub fn rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Set the circuit breaker guarding requests
+    pub fn circuit_breaker(mut self, circuit_breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Record or replay traffic with a cassette
+    pub fn cassette(mut self, cassette: Arc<Cassette>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Set the metrics hooks receiving request and WebSocket measurements
+    pub fn metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Set the proxy and TLS settings used for WebSocket connections
+    pub(crate) fn ws_connector(mut self, connector: WsConnector) -> Self {
+        self.ws_connector = Some(connector);
+        self
+    }
+
+    /// Set how WebSocket handshakes carry credentials
+    pub fn websocket_auth(mut self, mode: WebSocketAuth) -> Self {
+        self.websocket_auth = Some(mode);
+        self
+    }
+
     /// Set the default timeout
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(timeout);
@@ -296,18 +831,98 @@ impl HttpClientBuilder {
         self
     }
 
+    /// Set the default overall time budget across all attempts
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Set whether create requests get a generated idempotency key
+    pub fn idempotency_keys(mut self, enabled: bool) -> Self {
+        self.idempotency_keys = Some(enabled);
+        self
+    }
+
+    /// Set the retry configuration
+    ///
+    /// The maximum retries, deadline and idempotency key settings of this
+    /// builder apply on top of it, whichever is set first.
+    pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = Some(retry_config);
+        self
+    }
+
+    /// Set the classifier deciding which failed requests are retried
+    pub fn retry_classifier(mut self, classifier: Arc<dyn RetryClassifier>) -> Self {
+        self.retry_classifier = Some(classifier);
+        self
+    }
+
     /// Build the HTTP client
     pub fn build(self) -> Result<HttpClient> {
-        let mut client = HttpClient::new(self.base_url, self.auth)?;
+        let endpoints = match self.endpoints {
+            Some(endpoints) => endpoints,
+            None => Endpoints::new(&self.base_url)?,
+        };
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => Arc::new(ReqwestTransport::new()?),
+        };
+        let mut client = HttpClient::with_transport(endpoints, self.auth, transport);
+
+        for middleware in self.middleware {
+            client.add_middleware(middleware);
+        }
+
+        if let Some(rate_limiter) = self.rate_limiter {
+            client.set_rate_limiter(rate_limiter);
+        }
+
+        if let Some(circuit_breaker) = self.circuit_breaker {
+            client.set_circuit_breaker(circuit_breaker);
+        }
+
+        if let Some(cassette) = self.cassette {
+            client.set_cassette(cassette);
+        }
+
+        if let Some(metrics) = self.metrics {
+            client.set_metrics(metrics);
+        }
+
+        if let Some(connector) = self.ws_connector {
+            client.set_ws_connector(connector);
+        }
+
+        if let Some(mode) = self.websocket_auth {
+            client.set_websocket_auth(mode);
+        }
         
         if let Some(timeout) = self.timeout {
             client.set_default_timeout(timeout);
         }
         
+        let mut retry_config = self.retry_config.unwrap_or_default();
         if let Some(max_retries) = self.max_retries {
-            client.set_max_retries(max_retries);
+            retry_config.max_retries = max_retries;
+        }
+        if let Some(deadline) = self.deadline {
+            retry_config.deadline = Some(deadline);
+        }
+        if let Some(enabled) = self.idempotency_keys {
+            retry_config.idempotency_keys = enabled;
+        }
+        client.set_retry_config(retry_config);
+
+        if let Some(classifier) = self.retry_classifier {
+            client.set_retry_classifier(classifier);
         }
         
         Ok(client)
     }
-}
\ No newline at end of file
+}
+
+/// Parse a header value, rejecting invalid characters
+fn parse_header_value(value: &str) -> Result<HeaderValue> {
+    HeaderValue::from_str(value).map_err(|_| Error::validation("Invalid header value"))
+}
@@ -0,0 +1,112 @@
+//! Tracing spans and metrics hooks
+//!
+//! Every REST call runs inside a `hume.request` span, with a `hume.attempt`
+//! child span per attempt. WebSocket sessions run inside a `hume.websocket`
+//! span, with a `hume.message` child span per message sent or received. The
+//! span fields are stable, so they can be exported as OpenTelemetry
+//! attributes through `tracing-opentelemetry`:
+//!
+//! | Span             | Fields                                                 |
+//! |------------------|--------------------------------------------------------|
+//! | `hume.request`   | `endpoint`, `method`, `status`, `attempts`, `request_id` |
+//! | `hume.attempt`   | `attempt`                                              |
+//! | `hume.websocket` | `endpoint`, `chat_id`                                  |
+//! | `hume.message`   | `direction`, `bytes`                                   |
+//!
+//! `endpoint` is the request path with IDs replaced by `{id}`, see
+//! [`endpoint_label`]. A [`Metrics`] implementation registered on
+//! [`HumeClientBuilder`](crate::HumeClientBuilder) receives the same
+//! measurements, to be bridged to Prometheus or another metrics backend.
+
+use reqwest::Method;
+use std::{fmt, time::Duration};
+use uuid::Uuid;
+
+/// Direction of a streamed message or chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreamDirection {
+    /// Sent by the SDK
+    Sent,
+    /// Received from the API
+    Received,
+}
+
+impl StreamDirection {
+    /// Name used in span fields and metric labels
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StreamDirection::Sent => "sent",
+            StreamDirection::Received => "received",
+        }
+    }
+}
+
+impl fmt::Display for StreamDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Receives measurements from REST calls and WebSocket sessions
+///
+/// All methods have no-op defaults, so implementations only override the
+/// measurements they need. Methods are called inline and should not block.
+pub trait Metrics: Send + Sync + fmt::Debug {
+    /// Called when a REST call finishes, after all retries
+    ///
+    /// `status` is `None` when no response was received.
+    fn request_completed(&self, method: &Method, endpoint: &str, status: Option<u16>, latency: Duration) {
+        let _ = (method, endpoint, status, latency);
+    }
+
+    /// Called before each retry of a REST call, with the attempt about to be sent
+    fn request_retried(&self, method: &Method, endpoint: &str, attempt: u32) {
+        let _ = (method, endpoint, attempt);
+    }
+
+    /// Called for each WebSocket message or response stream chunk
+    fn bytes_streamed(&self, endpoint: &str, direction: StreamDirection, bytes: usize) {
+        let _ = (endpoint, direction, bytes);
+    }
+
+    /// Called when a WebSocket session is opened
+    fn socket_opened(&self, endpoint: &str) {
+        let _ = endpoint;
+    }
+
+    /// Called when a WebSocket session is closed or dropped
+    fn socket_closed(&self, endpoint: &str, lifetime: Duration) {
+        let _ = (endpoint, lifetime);
+    }
+}
+
+/// Label for a request path, with ID segments replaced by `{id}`
+///
+/// Keeps the number of distinct endpoints bounded, as metric backends
+/// require.
+pub fn endpoint_label(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            if Uuid::parse_str(segment).is_ok() {
+                "{id}"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_label() {
+        assert_eq!(endpoint_label("/v0/tts"), "/v0/tts");
+        assert_eq!(
+            endpoint_label("/v0/batch/jobs/2b3c6f7e-8a5d-4b1e-9f0a-1c2d3e4f5a6b/predictions"),
+            "/v0/batch/jobs/{id}/predictions"
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Request/response middleware for HTTP calls
+//!
+//! Middleware registered on [`HumeClientBuilder`](crate::HumeClientBuilder)
+//! wraps every REST request attempt made by the SDK clients, after
+//! authentication headers have been applied and before the request reaches
+//! the [`Transport`](crate::core::transport::Transport).
+
+use crate::core::{
+    error::{Error, Result},
+    transport::{HttpRequest, HttpResponse},
+};
+use async_trait::async_trait;
+use std::{fmt, sync::Arc};
+
+/// Hook points around each HTTP request attempt
+///
+/// All methods have no-op defaults, so implementations only override the
+/// hooks they need. Retried requests pass through the middleware once per
+/// attempt.
+#[async_trait]
+pub trait Middleware: Send + Sync + fmt::Debug {
+    /// Called before a request is sent; may modify the URL, headers or body
+    async fn before_send(&self, request: &mut HttpRequest) -> Result<()> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Called after a response is received, before its status is checked
+    async fn after_receive(&self, request: &HttpRequest, response: &mut HttpResponse) -> Result<()> {
+        let _ = (request, response);
+        Ok(())
+    }
+
+    /// Called when an attempt fails, including error statuses
+    async fn on_error(&self, request: &HttpRequest, error: &Error) {
+        let _ = (request, error);
+    }
+}
+
+/// Ordered chain of middleware
+///
+/// `before_send` hooks run in registration order; `after_receive` and
+/// `on_error` hooks run in reverse order, so the first middleware added is
+/// the outermost layer.
+#[derive(Debug, Clone, Default)]
+pub struct MiddlewareChain {
+    middleware: Vec<Arc<dyn Middleware>>,
+}
+
+impl MiddlewareChain {
+    /// Create an empty chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a middleware to the chain
+    pub fn push(&mut self, middleware: Arc<dyn Middleware>) {
+        self.middleware.push(middleware);
+    }
+
+    /// Check if the chain has no middleware
+    pub fn is_empty(&self) -> bool {
+        self.middleware.is_empty()
+    }
+
+    /// Get the number of middleware in the chain
+    pub fn len(&self) -> usize {
+        self.middleware.len()
+    }
+
+    /// Run all `before_send` hooks
+    pub async fn before_send(&self, request: &mut HttpRequest) -> Result<()> {
+        for middleware in &self.middleware {
+            middleware.before_send(request).await?;
+        }
+        Ok(())
+    }
+
+    /// Run all `after_receive` hooks
+    pub async fn after_receive(&self, request: &HttpRequest, response: &mut HttpResponse) -> Result<()> {
+        for middleware in self.middleware.iter().rev() {
+            middleware.after_receive(request, response).await?;
+        }
+        Ok(())
+    }
+
+    /// Run all `on_error` hooks
+    pub async fn on_error(&self, request: &HttpRequest, error: &Error) {
+        for middleware in self.middleware.iter().rev() {
+            middleware.on_error(request, error).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Method;
+    use std::sync::Mutex;
+
+    #[derive(Debug)]
+    struct Recorder {
+        name: &'static str,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Middleware for Recorder {
+        async fn before_send(&self, _request: &mut HttpRequest) -> Result<()> {
+            self.calls.lock().unwrap().push(format!("before:{}", self.name));
+            Ok(())
+        }
+
+        async fn after_receive(&self, _request: &HttpRequest, _response: &mut HttpResponse) -> Result<()> {
+            self.calls.lock().unwrap().push(format!("after:{}", self.name));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chain_ordering() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut chain = MiddlewareChain::new();
+        for name in ["outer", "inner"] {
+            chain.push(Arc::new(Recorder {
+                name,
+                calls: calls.clone(),
+            }));
+        }
+
+        let mut request = HttpRequest::new(
+            Method::GET,
+            url::Url::parse("https://api.hume.ai/v0/tts/voices").unwrap(),
+        );
+        let mut response = HttpResponse::new(reqwest::StatusCode::OK, Default::default(), "");
+
+        chain.before_send(&mut request).await.unwrap();
+        chain.after_receive(&request, &mut response).await.unwrap();
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["before:outer", "before:inner", "after:inner", "after:outer"]
+        );
+    }
+}
@@ -1,15 +1,35 @@
 //! Core functionality for the Hume SDK
 
 pub mod auth;
+pub mod cancel;
+pub mod cassette;
+pub mod circuit_breaker;
 pub mod client;
+pub mod config;
+pub mod endpoints;
 pub mod error;
 pub mod http;
+pub mod metrics;
+pub mod middleware;
+pub mod pagination;
+pub mod proxy;
+pub mod rate_limit;
 pub mod request;
 pub mod response;
 pub mod retry;
+pub mod secret;
+pub mod socket;
+pub mod tls;
+pub mod transport;
 pub mod validation;
 
-pub use auth::{Auth, AuthToken};
+pub use auth::{Auth, AuthToken, ClientCredentials, WebSocketAuth};
+pub use cancel::CancellationToken;
 pub use client::{HumeClient, HumeClientBuilder};
-pub use error::{Error, Result};
-pub use request::RequestOptions;
\ No newline at end of file
+pub use endpoints::Endpoints;
+pub use error::{DecodeError, Error, ErrorKind, HumeCode, Result};
+pub use metrics::Metrics;
+pub use middleware::Middleware;
+pub use request::RequestOptions;
+pub use secret::Secret;
+pub use transport::{MockTransport, Transport};
\ No newline at end of file
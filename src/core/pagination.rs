@@ -0,0 +1,326 @@
+//! Auto-paginating streams for list endpoints
+//!
+//! A [`Paginator`] repeatedly calls a list endpoint and flattens the pages
+//! into a stream of items. Any response type implementing [`Page`] can be
+//! paginated; the list clients expose ready-made paginators.
+//!
+//! ```no_run
+//! use futures_util::StreamExt;
+//! use hume::HumeClient;
+//!
+//! # async fn example() -> hume::Result<()> {
+//! let client = HumeClient::new("api-key")?;
+//! let configs = client.evi().configs().paginate(None).page_size(50).collect_all().await?;
+//!
+//! let mut chats = client.evi().chat().paginate_chats(None).prefetch(2).into_stream();
+//! while let Some(chat) = chats.next().await {
+//!     println!("{}", chat?.id);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::{error::Result, response::PaginatedResponse};
+use futures_util::{stream, Stream, TryStreamExt};
+use std::{collections::VecDeque, fmt, future::Future, pin::Pin, sync::Arc};
+use tokio::task::JoinHandle;
+
+/// Default number of items requested per page
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// A page of results returned by a list endpoint
+pub trait Page: Send + 'static {
+    /// The item type
+    type Item: Send + 'static;
+
+    /// Number of entries on this page
+    fn item_count(&self) -> usize;
+
+    /// Consume the page into its items
+    fn into_items(self) -> Vec<Self::Item>;
+
+    /// Total number of pages, if reported by the API
+    fn total_pages(&self) -> Option<u32> {
+        None
+    }
+
+    /// Total number of items, if reported by the API
+    fn total_items(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// The page a [`Paginator`] asks its fetch function for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRequest {
+    /// Zero-based page number
+    pub page_number: u32,
+    /// Number of items per page
+    pub page_size: u32,
+}
+
+impl PageRequest {
+    /// Get the offset of the first item, for `limit`/`offset` endpoints
+    pub fn offset(&self) -> u32 {
+        self.page_number.saturating_mul(self.page_size)
+    }
+}
+
+type PageFuture<P> = Pin<Box<dyn Future<Output = Result<P>> + Send>>;
+type FetchPage<P> = Arc<dyn Fn(PageRequest) -> PageFuture<P> + Send + Sync>;
+
+/// Turns a list endpoint into a stream of items
+///
+/// Pagination stops at the last page reported by the API, or at the first
+/// empty or short page when the API reports no totals.
+pub struct Paginator<P: Page> {
+    fetch: FetchPage<P>,
+    page_size: u32,
+    start_page: u32,
+    prefetch: usize,
+}
+
+impl<P: Page> Paginator<P> {
+    /// Create a paginator from a function fetching one page
+    pub fn new<F, Fut>(fetch: F) -> Self
+    where
+        F: Fn(PageRequest) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<P>> + Send + 'static,
+    {
+        Self {
+            fetch: Arc::new(move |request| Box::pin(fetch(request))),
+            page_size: DEFAULT_PAGE_SIZE,
+            start_page: 0,
+            prefetch: 0,
+        }
+    }
+
+    /// Set the number of items requested per page
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Set the page to start from
+    pub fn start_page(mut self, page_number: u32) -> Self {
+        self.start_page = page_number;
+        self
+    }
+
+    /// Fetch up to `pages` pages ahead in the background
+    pub fn prefetch(mut self, pages: usize) -> Self {
+        self.prefetch = pages;
+        self
+    }
+
+    /// Stream whole pages
+    pub fn pages(self) -> Pin<Box<dyn Stream<Item = Result<P>> + Send>> {
+        let state = PageState {
+            fetch: self.fetch,
+            page_size: self.page_size,
+            next_page: self.start_page,
+            last_page: None,
+            items_seen: 0,
+            in_flight: VecDeque::new(),
+            prefetch: self.prefetch,
+        };
+
+        Box::pin(stream::unfold(Some(state), |state| async move {
+            let mut state = state?;
+            let page = state.next().await?;
+            Some((page, if state.is_done() { None } else { Some(state) }))
+        }))
+    }
+
+    /// Stream individual items across all pages
+    pub fn into_stream(self) -> Pin<Box<dyn Stream<Item = Result<P::Item>> + Send>> {
+        Box::pin(
+            self.pages()
+                .map_ok(|page| stream::iter(page.into_items().into_iter().map(Ok)))
+                .try_flatten(),
+        )
+    }
+
+    /// Fetch every page and collect all items
+    pub async fn collect_all(self) -> Result<Vec<P::Item>> {
+        self.into_stream().try_collect().await
+    }
+}
+
+impl<P: Page> fmt::Debug for Paginator<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Paginator")
+            .field("page_size", &self.page_size)
+            .field("start_page", &self.start_page)
+            .field("prefetch", &self.prefetch)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Aborts a prefetch task when the stream is dropped
+struct Prefetched<P>(JoinHandle<Result<P>>);
+
+impl<P> Drop for Prefetched<P> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+struct PageState<P: Page> {
+    fetch: FetchPage<P>,
+    page_size: u32,
+    next_page: u32,
+    // Set once the last page is known; later pages are never requested
+    last_page: Option<u32>,
+    items_seen: u64,
+    in_flight: VecDeque<(u32, Prefetched<P>)>,
+    prefetch: usize,
+}
+
+impl<P: Page> PageState<P> {
+    fn is_done(&self) -> bool {
+        self.in_flight.is_empty() && matches!(self.last_page, Some(last) if self.next_page > last)
+    }
+
+    fn request(&mut self) {
+        let request = PageRequest {
+            page_number: self.next_page,
+            page_size: self.page_size,
+        };
+        self.next_page += 1;
+        let handle = tokio::spawn((self.fetch)(request));
+        self.in_flight.push_back((request.page_number, Prefetched(handle)));
+    }
+
+    async fn next(&mut self) -> Option<Result<P>> {
+        while self.in_flight.len() <= self.prefetch
+            && !matches!(self.last_page, Some(last) if self.next_page > last)
+        {
+            self.request();
+        }
+
+        let (page_number, mut task) = self.in_flight.pop_front()?;
+        let page = match (&mut task.0).await {
+            Ok(page) => page,
+            Err(e) => Err(crate::core::error::Error::other(format!("Page fetch failed: {}", e))),
+        };
+
+        let page = match page {
+            Ok(page) => page,
+            Err(e) => {
+                self.finish(page_number);
+                return Some(Err(e));
+            }
+        };
+
+        let count = page.item_count();
+        self.items_seen += count as u64;
+
+        let is_last = match (page.total_pages(), page.total_items()) {
+            _ if count == 0 => true,
+            (Some(total_pages), _) => page_number + 1 >= total_pages,
+            (None, Some(total_items)) => self.items_seen >= total_items,
+            (None, None) => count < self.page_size as usize,
+        };
+        if is_last {
+            self.finish(page_number);
+        }
+
+        Some(Ok(page))
+    }
+
+    /// Stop after `page_number`, dropping (and aborting) later prefetches
+    fn finish(&mut self, page_number: u32) {
+        self.last_page = Some(page_number);
+        self.next_page = page_number + 1;
+        self.in_flight.clear();
+    }
+}
+
+impl<T: Send + 'static> Page for PaginatedResponse<T> {
+    type Item = T;
+
+    fn item_count(&self) -> usize {
+        self.data.len()
+    }
+
+    fn into_items(self) -> Vec<T> {
+        self.data
+    }
+
+    fn total_items(&self) -> Option<u64> {
+        self.total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn numbers(total: u64) -> impl Fn(PageRequest) -> std::future::Ready<Result<PaginatedResponse<u64>>> {
+        move |request| {
+            let start = request.offset() as u64;
+            let end = (start + request.page_size as u64).min(total);
+            std::future::ready(Ok(PaginatedResponse {
+                data: (start..end).collect(),
+                total: Some(total),
+                limit: Some(request.page_size as u64),
+                offset: Some(start),
+                has_more: None,
+                next_cursor: None,
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collect_all() {
+        let items = Paginator::new(numbers(7)).page_size(3).collect_all().await.unwrap();
+        assert_eq!(items, (0..7).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_stops_at_last_page() {
+        let requests = Arc::new(AtomicU32::new(0));
+        let counter = requests.clone();
+        let fetch = numbers(4);
+        let paginator = Paginator::new(move |request| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            fetch(request)
+        });
+
+        let pages: Vec<_> = paginator.page_size(2).prefetch(1).pages().try_collect().await.unwrap();
+        assert_eq!(pages.len(), 2);
+        assert!(requests.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[tokio::test]
+    async fn test_short_page_ends_stream_without_totals() {
+        let paginator = Paginator::new(|request: PageRequest| async move {
+            let data = if request.page_number == 0 { vec![1, 2] } else { vec![3] };
+            Ok(PaginatedResponse {
+                data,
+                total: None,
+                limit: None,
+                offset: None,
+                has_more: None,
+                next_cursor: None,
+            })
+        });
+
+        let items = paginator.page_size(2).collect_all().await.unwrap();
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_error_ends_stream() {
+        let paginator = Paginator::new(|_request: PageRequest| async {
+            Err::<PaginatedResponse<u32>, _>(crate::core::error::Error::other("boom"))
+        });
+
+        let mut pages = paginator.pages();
+        assert!(pages.next().await.unwrap().is_err());
+        assert!(pages.next().await.is_none());
+    }
+}
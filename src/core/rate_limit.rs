@@ -0,0 +1,253 @@
+//! Client-side rate limiting with per-endpoint token buckets
+//!
+//! A [`RateLimiter`] holds one bucket per configured path prefix. Requests
+//! wait for a token (and, optionally, a concurrency slot) before they are
+//! sent. Waiters are served in FIFO order. When the API responds with a
+//! `retry-after` header the matching bucket is paused for that long.
+
+use std::{sync::Arc, time::Duration};
+use tokio::{
+    sync::{Mutex, OwnedSemaphorePermit, Semaphore},
+    time::Instant,
+};
+
+/// Limits applied to a group of endpoints
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Sustained requests per second
+    pub requests_per_second: f64,
+    /// Maximum number of requests that may be sent in a burst
+    pub burst: u32,
+    /// Maximum number of requests in flight at once
+    pub max_concurrency: Option<usize>,
+}
+
+impl RateLimit {
+    /// Create a limit of `requests_per_second` with a burst of one request
+    pub fn per_second(requests_per_second: f64) -> Self {
+        Self {
+            requests_per_second,
+            burst: 1,
+            max_concurrency: None,
+        }
+    }
+
+    /// Set the burst size
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    /// Set the maximum number of concurrent requests
+    ///
+    /// A request keeps its slot until its response body has been read or
+    /// dropped, so a streaming response counts for as long as it is open.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency.max(1));
+        self
+    }
+}
+
+/// Client-side rate limiter with per-endpoint token buckets
+///
+/// ```
+/// use hume::core::rate_limit::{RateLimit, RateLimiter};
+///
+/// let limiter = RateLimiter::new()
+///     .with_default(RateLimit::per_second(20.0))
+///     .with_endpoint("/v0/tts", RateLimit::per_second(5.0).max_concurrency(2))
+///     .with_endpoint("/v0/batch", RateLimit::per_second(1.0).burst(5));
+/// ```
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    endpoints: Vec<(String, Arc<Bucket>)>,
+    default: Option<Arc<Bucket>>,
+}
+
+impl RateLimiter {
+    /// Create a limiter with no limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the limit for requests that match no endpoint prefix
+    pub fn with_default(mut self, limit: RateLimit) -> Self {
+        self.default = Some(Arc::new(Bucket::new(limit)));
+        self
+    }
+
+    /// Set the limit for requests whose path starts with `prefix`
+    ///
+    /// When several prefixes match, the longest one wins.
+    pub fn with_endpoint(mut self, prefix: impl Into<String>, limit: RateLimit) -> Self {
+        let prefix = prefix.into();
+        self.endpoints.retain(|(existing, _)| *existing != prefix);
+        self.endpoints.push((prefix, Arc::new(Bucket::new(limit))));
+        self.endpoints
+            .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        self
+    }
+
+    /// Wait until a request to `path` may be sent
+    ///
+    /// The returned permit holds a concurrency slot until it is dropped.
+    pub async fn acquire(&self, path: &str) -> RateLimitPermit {
+        match self.bucket(path) {
+            Some(bucket) => bucket.acquire().await,
+            None => RateLimitPermit { _slot: None },
+        }
+    }
+
+    /// Pause requests to `path` after the API asked to retry later
+    pub async fn observe_retry_after(&self, path: &str, retry_after: Duration) {
+        if let Some(bucket) = self.bucket(path) {
+            bucket.pause(retry_after).await;
+        }
+    }
+
+    fn bucket(&self, path: &str) -> Option<&Arc<Bucket>> {
+        self.endpoints
+            .iter()
+            .find(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .map(|(_, bucket)| bucket)
+            .or(self.default.as_ref())
+    }
+}
+
+/// Permission to send one request, returned by [`RateLimiter::acquire`]
+#[derive(Debug)]
+pub struct RateLimitPermit {
+    _slot: Option<OwnedSemaphorePermit>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    limit: RateLimit,
+    // Waiters hold this lock while sleeping so tokens are handed out in FIFO order
+    state: Mutex<BucketState>,
+    slots: Option<Arc<Semaphore>>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            state: Mutex::new(BucketState {
+                tokens: limit.burst as f64,
+                last_refill: Instant::now(),
+                paused_until: None,
+            }),
+            slots: limit
+                .max_concurrency
+                .map(|max| Arc::new(Semaphore::new(max))),
+        }
+    }
+
+    async fn acquire(&self) -> RateLimitPermit {
+        let slot = match &self.slots {
+            // The semaphore is never closed, so acquiring cannot fail
+            Some(slots) => slots.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+
+        let mut state = self.state.lock().await;
+        loop {
+            let now = Instant::now();
+
+            if let Some(paused_until) = state.paused_until {
+                if paused_until > now {
+                    tokio::time::sleep_until(paused_until).await;
+                    continue;
+                }
+                state.paused_until = None;
+                state.last_refill = now;
+            }
+
+            let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+            state.tokens = (state.tokens + elapsed * self.limit.requests_per_second)
+                .min(self.limit.burst as f64);
+            state.last_refill = now;
+
+            if state.tokens >= 1.0 {
+                state.tokens -= 1.0;
+                return RateLimitPermit { _slot: slot };
+            }
+
+            let missing = 1.0 - state.tokens;
+            let wait = Duration::from_secs_f64(missing / self.limit.requests_per_second.max(f64::EPSILON));
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    async fn pause(&self, duration: Duration) {
+        tracing::debug!("Pausing requests for {:?} after rate limit response", duration);
+        let until = Instant::now() + duration;
+        let mut state = self.state.lock().await;
+        state.tokens = 0.0;
+        if !matches!(state.paused_until, Some(current) if current >= until) {
+            state.paused_until = Some(until);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_longest_prefix_wins() {
+        let limiter = RateLimiter::new()
+            .with_endpoint("/v0", RateLimit::per_second(1.0))
+            .with_endpoint("/v0/tts", RateLimit::per_second(2.0));
+
+        let bucket = limiter.bucket("/v0/tts/voices").unwrap();
+        assert_eq!(bucket.limit.requests_per_second, 2.0);
+        assert!(limiter.bucket("/v1/other").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rate_is_enforced() {
+        let limiter = RateLimiter::new().with_default(RateLimit::per_second(20.0));
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter.acquire("/v0/tts").await;
+        }
+
+        // The first token is available immediately, the next two take 50ms each
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_pauses_bucket() {
+        let limiter = RateLimiter::new().with_default(RateLimit::per_second(1000.0).burst(10));
+        limiter
+            .observe_retry_after("/v0/tts", Duration::from_millis(100))
+            .await;
+
+        let start = Instant::now();
+        limiter.acquire("/v0/tts").await;
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_max_concurrency() {
+        let limiter = RateLimiter::new()
+            .with_default(RateLimit::per_second(1000.0).burst(10).max_concurrency(1));
+
+        let permit = limiter.acquire("/v0/tts").await;
+        let pending = tokio::time::timeout(Duration::from_millis(20), limiter.acquire("/v0/tts")).await;
+        assert!(pending.is_err());
+
+        drop(permit);
+        let next = tokio::time::timeout(Duration::from_millis(20), limiter.acquire("/v0/tts")).await;
+        assert!(next.is_ok());
+    }
+}
@@ -1,6 +1,11 @@
 //! Request configuration options
 
+use crate::core::{
+    cancel::CancellationToken,
+    retry::{RetryClassifier, RetryConfig},
+};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Options for customizing HTTP requests
@@ -10,10 +15,21 @@ pub struct RequestOptions {
     pub headers: HashMap<String, String>,
     /// Query parameters to include in the request
     pub query: HashMap<String, String>,
-    /// Request timeout
+    /// Request timeout, per attempt
     pub timeout: Option<Duration>,
+    /// Overall time budget across all attempts, overriding the client default
+    pub deadline: Option<Duration>,
     /// Maximum number of retries
     pub max_retries: Option<u32>,
+    /// Retry configuration, overriding the client default
+    pub retry_config: Option<RetryConfig>,
+    /// Retry classifier, overriding the client default
+    pub retry_classifier: Option<Arc<dyn RetryClassifier>>,
+    /// Token that cancels the request, its retries and any response stream
+    pub cancellation: Option<CancellationToken>,
+    /// Idempotency key for a non-idempotent request, replacing the one
+    /// generated for create requests
+    pub idempotency_key: Option<String>,
 }
 
 impl RequestOptions {
@@ -40,12 +56,42 @@ impl RequestOptions {
         self
     }
 
+    /// Set the overall time budget across all attempts
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
     /// Set the maximum number of retries
     pub fn with_max_retries(mut self, max_retries: u32) -> Self {
         self.max_retries = Some(max_retries);
         self
     }
 
+    /// Set the retry configuration
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = Some(retry_config);
+        self
+    }
+
+    /// Set the retry classifier
+    pub fn with_retry_classifier(mut self, classifier: impl RetryClassifier + 'static) -> Self {
+        self.retry_classifier = Some(Arc::new(classifier));
+        self
+    }
+
+    /// Set the cancellation token
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Set the idempotency key
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
     /// Merge with another RequestOptions, with other taking precedence
     pub fn merge(mut self, other: RequestOptions) -> Self {
         self.headers.extend(other.headers);
@@ -53,9 +99,24 @@ impl RequestOptions {
         if other.timeout.is_some() {
             self.timeout = other.timeout;
         }
+        if other.deadline.is_some() {
+            self.deadline = other.deadline;
+        }
         if other.max_retries.is_some() {
             self.max_retries = other.max_retries;
         }
+        if other.retry_config.is_some() {
+            self.retry_config = other.retry_config;
+        }
+        if other.retry_classifier.is_some() {
+            self.retry_classifier = other.retry_classifier;
+        }
+        if other.cancellation.is_some() {
+            self.cancellation = other.cancellation;
+        }
+        if other.idempotency_key.is_some() {
+            self.idempotency_key = other.idempotency_key;
+        }
         self
     }
 }
@@ -118,12 +179,42 @@ impl RequestOptionsBuilder {
         self
     }
 
+    /// Set the overall time budget across all attempts
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.options.deadline = Some(deadline);
+        self
+    }
+
     /// Set the maximum number of retries
     pub fn max_retries(mut self, max_retries: u32) -> Self {
         self.options.max_retries = Some(max_retries);
         self
     }
 
+    /// Set the retry configuration
+    pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.options.retry_config = Some(retry_config);
+        self
+    }
+
+    /// Set the retry classifier
+    pub fn retry_classifier(mut self, classifier: impl RetryClassifier + 'static) -> Self {
+        self.options.retry_classifier = Some(Arc::new(classifier));
+        self
+    }
+
+    /// Set the cancellation token
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.options.cancellation = Some(token);
+        self
+    }
+
+    /// Set the idempotency key
+    pub fn idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.options.idempotency_key = Some(key.into());
+        self
+    }
+
     /// Build the RequestOptions
     pub fn build(self) -> RequestOptions {
         self.options
@@ -1,6 +1,14 @@
 //! Response handling utilities
 
-use serde::{Deserialize, Serialize};
+use crate::core::error::{DecodeError, Error, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use reqwest::{header::HeaderMap, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use std::time::Duration;
+
+/// Maximum number of body bytes kept in [`Error::Decode`]
+const MAX_DECODE_BODY: usize = 2048;
 
 /// A paginated response from the API
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,4 +96,310 @@ pub struct StatusResponse {
     pub status: String,
     /// Optional message
     pub message: Option<String>,
-}
\ No newline at end of file
+}
+/// Transport-level metadata for a completed request
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    /// HTTP status code
+    pub status: StatusCode,
+    /// Response headers
+    pub headers: HeaderMap,
+    /// Request ID assigned by the API, for support tickets
+    pub request_id: Option<String>,
+    /// Time from the first attempt until the final response headers arrived
+    pub elapsed: Duration,
+    /// Number of attempts sent, including retries
+    pub attempts: u32,
+    /// Rate limit quota reported by the API
+    pub rate_limit: Option<RateLimitInfo>,
+    /// Delay requested by the API before the next request
+    pub retry_after: Option<Duration>,
+}
+
+impl ResponseMeta {
+    /// Create metadata from a response status and headers
+    pub fn new(status: StatusCode, headers: HeaderMap, elapsed: Duration, attempts: u32) -> Self {
+        let request_id = REQUEST_ID_HEADERS
+            .iter()
+            .find_map(|name| headers.get(*name))
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        let rate_limit = RateLimitInfo::from_headers(&headers);
+        let retry_after = retry_delay(&headers);
+
+        Self {
+            status,
+            headers,
+            request_id,
+            elapsed,
+            attempts,
+            rate_limit,
+            retry_after,
+        }
+    }
+}
+
+/// Headers checked, in order, for the request ID
+const REQUEST_ID_HEADERS: &[&str] = &["x-request-id", "request-id", "x-amzn-requestid"];
+
+/// Header name prefixes checked, in order, for rate limit quota
+const RATE_LIMIT_PREFIXES: &[&str] = &["x-ratelimit-", "ratelimit-"];
+
+/// Reset values above this are Unix timestamps rather than delays in seconds
+const RESET_TIMESTAMP_THRESHOLD: f64 = 1_000_000_000.0;
+
+/// Rate limit quota from `x-ratelimit-*` or `ratelimit-*` response headers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Requests allowed in the current window
+    pub limit: Option<u64>,
+    /// Requests left in the current window
+    pub remaining: Option<u64>,
+    /// Time until the window resets
+    pub reset: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    /// Parse the quota from response headers, if any are present
+    ///
+    /// `reset` may be a delay in seconds or a Unix timestamp.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        RATE_LIMIT_PREFIXES.iter().find_map(|prefix| {
+            let header = |name: &str| {
+                headers
+                    .get(format!("{}{}", prefix, name))
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::trim)
+            };
+            let info = Self {
+                limit: header("limit").and_then(|v| v.parse().ok()),
+                remaining: header("remaining").and_then(|v| v.parse().ok()),
+                reset: header("reset").and_then(parse_reset),
+            };
+            (info.limit.is_some() || info.remaining.is_some() || info.reset.is_some()).then_some(info)
+        })
+    }
+
+    /// Returns true if no requests are left in the current window
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == Some(0)
+    }
+}
+
+/// Parse a rate limit reset value: seconds, or a Unix timestamp
+fn parse_reset(value: &str) -> Option<Duration> {
+    let seconds = value.parse::<f64>().ok().filter(|s| s.is_finite() && *s >= 0.0)?;
+    if seconds > RESET_TIMESTAMP_THRESHOLD {
+        let now = Utc::now().timestamp_millis() as f64 / 1000.0;
+        Some(Duration::from_secs_f64((seconds - now).max(0.0)))
+    } else {
+        Some(Duration::from_secs_f64(seconds))
+    }
+}
+
+/// Parse a `Retry-After` header value as defined by RFC 9110
+///
+/// The value is either a number of seconds or an HTTP-date, in the preferred
+/// IMF-fixdate format or the obsolete RFC 850 and asctime formats. Dates in
+/// the past give a zero delay.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value)
+        .map(|date| date.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            ["%A, %d-%b-%y %H:%M:%S GMT", "%a %b %e %H:%M:%S %Y"]
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+                .map(|date| date.and_utc())
+        })?;
+    Some((date - Utc::now()).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Delay the server asks for before the next request
+///
+/// Uses `Retry-After`, or the rate limit reset time when the quota is
+/// exhausted.
+pub(crate) fn retry_delay(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get("retry-after")
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_retry_after)
+        .or_else(|| {
+            RateLimitInfo::from_headers(headers)
+                .filter(RateLimitInfo::is_exhausted)
+                .and_then(|info| info.reset)
+        })
+}
+
+/// A typed result together with its [`ResponseMeta`]
+#[derive(Debug, Clone)]
+pub struct WithMeta<T> {
+    /// The deserialized response body
+    pub data: T,
+    /// Response metadata
+    pub meta: ResponseMeta,
+}
+
+impl<T> WithMeta<T> {
+    /// Create a new result with metadata
+    pub fn new(data: T, meta: ResponseMeta) -> Self {
+        Self { data, meta }
+    }
+
+    /// Discard the metadata and return the data
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+
+    /// Map the data, keeping the metadata
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> WithMeta<U> {
+        WithMeta {
+            data: f(self.data),
+            meta: self.meta,
+        }
+    }
+}
+
+impl<T> std::ops::Deref for WithMeta<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+/// Deserialize a JSON response body
+///
+/// On failure, returns [`Error::Decode`] with the JSON path that failed, the
+/// type serde expected there and a truncated copy of the body.
+pub fn decode_json<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    let deserializer = &mut serde_json::Deserializer::from_slice(bytes);
+    serde_path_to_error::deserialize(deserializer).map_err(|error| {
+        let path = error.path().to_string();
+        let source = error.into_inner();
+        Error::Decode(Box::new(DecodeError {
+            target: std::any::type_name::<T>(),
+            path,
+            expected: expected_type(&source),
+            body: truncate_body(bytes),
+            source,
+        }))
+    })
+}
+
+/// Deserialize the flattened `extra` map of a response model
+///
+/// Unknown fields are only kept with the `lenient` feature, so enabling it
+/// changes what `extra` holds but not the shape of any type.
+pub(crate) fn unknown_fields<'de, D>(deserializer: D) -> std::result::Result<Map<String, Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let fields = Map::deserialize(deserializer)?;
+    Ok(if cfg!(feature = "lenient") { fields } else { Map::new() })
+}
+
+/// Extract the `expected ...` part of a serde error message
+fn expected_type(error: &serde_json::Error) -> Option<String> {
+    let message = error.to_string();
+    let (_, expected) = message.split_once("expected ")?;
+    let expected = expected.split(" at line ").next().unwrap_or(expected);
+    Some(expected.to_string())
+}
+
+/// Copy at most [`MAX_DECODE_BODY`] bytes of a body, on a character boundary
+fn truncate_body(bytes: &[u8]) -> String {
+    let body = String::from_utf8_lossy(bytes);
+    if body.len() <= MAX_DECODE_BODY {
+        return body.into_owned();
+    }
+    let mut end = MAX_DECODE_BODY;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}... ({} bytes total)", &body[..end], bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Voices {
+        #[allow(dead_code)]
+        voices: Vec<Voice>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Voice {
+        #[allow(dead_code)]
+        name: String,
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Sun, 06 Nov 1994 08:49:37 GMT"), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("Sunday, 06-Nov-94 08:49:37 GMT"), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("Sun Nov  6 08:49:37 1994"), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon"), None);
+
+        let date = (Utc::now() + chrono::Duration::seconds(30)).to_rfc2822();
+        let delay = parse_retry_after(&date.replace("+0000", "GMT")).unwrap();
+        assert!(delay > Duration::from_secs(28) && delay <= Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_rate_limit_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", "100".parse().unwrap());
+        headers.insert("x-ratelimit-remaining", "0".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "7".parse().unwrap());
+
+        let meta = ResponseMeta::new(StatusCode::OK, headers.clone(), Duration::ZERO, 1);
+        let info = meta.rate_limit.unwrap();
+        assert_eq!(info.limit, Some(100));
+        assert!(info.is_exhausted());
+        assert_eq!(meta.retry_after, Some(Duration::from_secs(7)));
+
+        // Retry-After takes precedence over the reset time
+        headers.insert("retry-after", "2".parse().unwrap());
+        assert_eq!(retry_delay(&headers), Some(Duration::from_secs(2)));
+
+        let reset = (Utc::now().timestamp() + 60).to_string();
+        let mut headers = HeaderMap::new();
+        headers.insert("ratelimit-reset", reset.parse().unwrap());
+        let reset = RateLimitInfo::from_headers(&headers).unwrap().reset.unwrap();
+        assert!(reset > Duration::from_secs(58) && reset <= Duration::from_secs(60));
+        assert_eq!(retry_delay(&headers), None);
+    }
+
+    #[test]
+    fn test_decode_error_reports_path() {
+        let body = br#"{"voices": [{"name": "a"}, {"name": 42}]}"#;
+        let error = decode_json::<Voices>(body).unwrap_err();
+        match &error {
+            Error::Decode(decode) => {
+                assert_eq!(decode.path, "voices[1].name");
+                assert_eq!(decode.expected.as_deref(), Some("a string"));
+                assert_eq!(decode.body.as_bytes(), body);
+            }
+            other => panic!("Expected decode error, got {:?}", other),
+        }
+        assert!(error.to_string().contains("voices[1].name"));
+    }
+
+    #[test]
+    fn test_decode_error_truncates_body() {
+        let body = format!("{{\"voices\": \"{}\"}}", "é".repeat(MAX_DECODE_BODY));
+        let error = decode_json::<Voices>(body.as_bytes()).unwrap_err();
+        let raw = error.body().unwrap();
+        assert!(raw.len() < MAX_DECODE_BODY + 32);
+        assert!(raw.ends_with(&format!("({} bytes total)", body.len())));
+    }
+}
@@ -1,11 +1,22 @@
 //! Retry logic for HTTP requests
 
-use crate::core::error::{Error, Result};
+use crate::core::{
+    cancel::{cancellable, CancellationToken},
+    error::{Error, Result},
+};
 use backoff::{backoff::Backoff, ExponentialBackoff, ExponentialBackoffBuilder};
-use std::time::Duration;
+use reqwest::Method;
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
 
 /// Retry configuration
+///
+/// Build one with [`RetryPolicyBuilder`], or start from
+/// [`RetryConfig::default`] and assign the fields to change.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct RetryConfig {
     /// Maximum number of retries
     pub max_retries: u32,
@@ -15,6 +26,11 @@ pub struct RetryConfig {
     pub max_backoff: Duration,
     /// Multiplier for exponential backoff
     pub backoff_multiplier: f64,
+    /// Overall time budget across all attempts and retry delays
+    pub deadline: Option<Duration>,
+    /// Generate an idempotency key for requests creating resources, such as
+    /// batch jobs and EVI tools, so they can be retried
+    pub idempotency_keys: bool,
 }
 
 impl Default for RetryConfig {
@@ -24,6 +40,8 @@ impl Default for RetryConfig {
             initial_backoff: Duration::from_millis(100),
             max_backoff: Duration::from_secs(10),
             backoff_multiplier: 2.0,
+            deadline: None,
+            idempotency_keys: true,
         }
     }
 }
@@ -46,6 +64,84 @@ impl RetryConfig {
     }
 }
 
+/// Information about the request being retried
+#[derive(Debug, Clone)]
+pub struct RetryContext {
+    /// HTTP method of the request
+    pub method: Method,
+    /// Request path, relative to the base URL
+    pub path: String,
+    /// Number of retries already made (0 for the first failure)
+    pub attempt: u32,
+    /// Token that stops the attempts and the delays between them
+    pub cancellation: Option<CancellationToken>,
+    /// Idempotency key sent with every attempt, if any
+    pub idempotency_key: Option<String>,
+}
+
+impl RetryContext {
+    /// Create a context for a request that has not been retried yet
+    pub fn new(method: Method, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            attempt: 0,
+            cancellation: None,
+            idempotency_key: None,
+        }
+    }
+
+    /// Stop retrying when `token` is cancelled
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Mark the request as carrying `key`, making it safe to retry
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Returns true if repeating the request cannot duplicate its effect
+    ///
+    /// This holds for idempotent methods and for any request with an
+    /// idempotency key.
+    pub fn is_idempotent(&self) -> bool {
+        is_idempotent_method(&self.method) || self.idempotency_key.is_some()
+    }
+}
+
+/// Returns true if the method is idempotent as defined by RFC 9110
+pub fn is_idempotent_method(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE | Method::PUT | Method::DELETE
+    )
+}
+
+/// Decides whether a failed request should be retried
+///
+/// The retry budget in [`RetryConfig`] is checked before the classifier is
+/// consulted, so implementations only need to decide on the error itself.
+pub trait RetryClassifier: Send + Sync + fmt::Debug {
+    /// Return true if the request should be retried after this error
+    fn should_retry(&self, error: &Error, context: &RetryContext) -> bool;
+}
+
+/// Default classifier, retrying the errors accepted by [`is_retryable_error`]
+///
+/// Non-idempotent requests, such as a POST creating a batch job, are only
+/// retried when they carry an idempotency key.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultRetryClassifier;
+
+impl RetryClassifier for DefaultRetryClassifier {
+    fn should_retry(&self, error: &Error, context: &RetryContext) -> bool {
+        context.is_idempotent() && is_retryable_error(error)
+    }
+}
+
 /// Determine if an error is retryable
 pub fn is_retryable_error(error: &Error) -> bool {
     match error {
@@ -57,15 +153,20 @@ pub fn is_retryable_error(error: &Error) -> bool {
                 s.as_u16() >= 500 || s.as_u16() == 429
             })
         }
+        // Server errors returned by the API are retryable
+        Error::Api { status, .. } => *status >= 500,
         // Rate limit errors are retryable
         Error::RateLimit { .. } => true,
         // Timeout errors are retryable
         Error::Timeout => true,
+        // The server could not be reached, or was unavailable during the handshake
+        Error::Connect(_) | Error::Dns(_) => true,
+        Error::HandshakeRejected { status, .. } => *status >= 500 || *status == 429,
         // WebSocket errors might be retryable
         Error::WebSocket(e) => {
             use tokio_tungstenite::tungstenite::Error as WsError;
             matches!(
-                e,
+                **e,
                 WsError::ConnectionClosed | WsError::AlreadyClosed | WsError::Io(_)
             )
         }
@@ -74,11 +175,14 @@ pub fn is_retryable_error(error: &Error) -> bool {
     }
 }
 
-/// Extract retry-after duration from error
+/// Extract the server-directed retry delay from an error
+///
+/// Rate limit errors carry it directly; other API errors, such as a 503,
+/// take it from their response's `Retry-After` or rate limit headers.
 pub fn get_retry_after(error: &Error) -> Option<Duration> {
     match error {
         Error::RateLimit { retry_after } => retry_after.map(Duration::from_secs),
-        // For HTTP errors, the retry-after is already extracted in the Error::RateLimit variant
+        Error::Api { .. } => error.meta().and_then(|meta| meta.retry_after),
         _ => None,
     }
 }
@@ -97,6 +201,31 @@ pub fn create_backoff(config: &RetryConfig) -> ExponentialBackoff {
 /// Retry a future with exponential backoff
 pub async fn retry_with_backoff<F, Fut, T>(
     config: &RetryConfig,
+    operation: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    retry_with_classifier(
+        config,
+        &DefaultRetryClassifier,
+        RetryContext::new(Method::GET, ""),
+        operation,
+    )
+    .await
+}
+
+/// Retry a future with exponential backoff, using a classifier to decide which errors to retry
+///
+/// With a [`RetryConfig::deadline`], an attempt still running when the
+/// deadline passes fails with [`Error::Timeout`], and a retry is skipped when
+/// its delay would use up the remaining budget. Cancelling the context's
+/// token fails the current attempt or delay with [`Error::Cancelled`].
+pub async fn retry_with_classifier<F, Fut, T>(
+    config: &RetryConfig,
+    classifier: &dyn RetryClassifier,
+    mut context: RetryContext,
     mut operation: F,
 ) -> Result<T>
 where
@@ -105,13 +234,28 @@ where
 {
     let mut backoff = create_backoff(config);
     let mut retries = 0;
+    let deadline = config.deadline.map(|deadline| Instant::now() + deadline);
+
+    let cancellation = context.cancellation.clone();
 
     loop {
-        match operation().await {
+        let attempt = async {
+            match deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline.into(), operation())
+                    .await
+                    .unwrap_or(Err(Error::Timeout)),
+                None => operation().await,
+            }
+        };
+        match cancellable(cancellation.as_ref(), attempt).await {
             Ok(result) => return Ok(result),
             Err(error) => {
                 // Check if we should retry
-                if retries >= config.max_retries || !is_retryable_error(&error) {
+                context.attempt = retries;
+                if error.is_cancelled()
+                    || retries >= config.max_retries
+                    || !classifier.should_retry(&error, &context)
+                {
                     return Err(error);
                 }
 
@@ -124,6 +268,20 @@ where
                     return Err(error);
                 };
 
+                // Give up if the retry could not start before the deadline
+                if let Some(deadline) = deadline {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if delay >= remaining {
+                        tracing::warn!(
+                            "Not retrying after error: {} ({:?} left of the deadline, delay {:?})",
+                            error,
+                            remaining,
+                            delay
+                        );
+                        return Err(error);
+                    }
+                }
+
                 // Log retry attempt
                 tracing::warn!(
                     "Retrying after error: {} (attempt {}/{}), waiting {:?}",
@@ -134,7 +292,11 @@ where
                 );
 
                 // Wait before retrying
-                tokio::time::sleep(delay).await;
+                cancellable(cancellation.as_ref(), async {
+                    tokio::time::sleep(delay).await;
+                    Ok(())
+                })
+                .await?;
                 retries += 1;
             }
         }
@@ -179,6 +341,18 @@ impl RetryPolicyBuilder {
         self
     }
 
+    /// Set the overall time budget across all attempts
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.config.deadline = Some(deadline);
+        self
+    }
+
+    /// Set whether create requests get a generated idempotency key
+    pub fn idempotency_keys(mut self, enabled: bool) -> Self {
+        self.config.idempotency_keys = enabled;
+        self
+    }
+
     /// Build the retry configuration
     pub fn build(self) -> RetryConfig {
         self.config
@@ -194,6 +368,7 @@ impl Default for RetryPolicyBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::response::ResponseMeta;
 
     #[test]
     fn test_is_retryable_error() {
@@ -208,6 +383,136 @@ mod tests {
         
         // Auth errors are not retryable
         assert!(!is_retryable_error(&Error::Auth("test".into())));
+
+        // Server errors are retryable, client errors are not
+        assert!(is_retryable_error(&Error::api(503, "unavailable".into(), None, None)));
+        assert!(!is_retryable_error(&Error::api(409, "conflict".into(), None, None)));
+    }
+
+    #[test]
+    fn test_default_classifier_requires_idempotency() {
+        let error = Error::api(503, "unavailable".into(), None, None);
+        let classifier = DefaultRetryClassifier;
+
+        assert!(classifier.should_retry(&error, &RetryContext::new(Method::PUT, "/v0/evi/configs")));
+        let context = RetryContext::new(Method::POST, "/v0/batch/jobs");
+        assert!(!classifier.should_retry(&error, &context));
+        assert!(classifier.should_retry(&error, &context.with_idempotency_key("key")));
+    }
+
+    #[derive(Debug)]
+    struct RetryConflicts;
+
+    impl RetryClassifier for RetryConflicts {
+        fn should_retry(&self, error: &Error, context: &RetryContext) -> bool {
+            context.method == Method::POST && error.status_code() == Some(409)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_classifier() {
+        let config = RetryPolicyBuilder::new()
+            .max_retries(2)
+            .initial_interval(Duration::from_millis(1))
+            .build();
+
+        let mut attempts = 0;
+        let result: Result<()> = retry_with_classifier(
+            &config,
+            &RetryConflicts,
+            RetryContext::new(Method::POST, "/v0/batch/jobs"),
+            || {
+                attempts += 1;
+                async { Err(Error::api(409, "conflict".into(), None, None)) }
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_stops_at_deadline() {
+        let config = RetryPolicyBuilder::new()
+            .max_retries(100)
+            .initial_interval(Duration::from_millis(20))
+            .max_interval(Duration::from_millis(20))
+            .deadline(Duration::from_millis(100))
+            .build();
+
+        let started = Instant::now();
+        let mut attempts = 0;
+        let result: Result<()> = retry_with_backoff(&config, || {
+            attempts += 1;
+            async { Err(Error::api(503, "unavailable".into(), None, None)) }
+        })
+        .await;
+
+        assert_eq!(result.unwrap_err().status_code(), Some(503));
+        assert!(attempts > 1 && attempts < 10, "{} attempts", attempts);
+        assert!(started.elapsed() < Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_retry_skipped_when_retry_after_exceeds_deadline() {
+        let config = RetryPolicyBuilder::new()
+            .deadline(Duration::from_millis(100))
+            .build();
+
+        let mut attempts = 0;
+        let result: Result<()> = retry_with_backoff(&config, || {
+            attempts += 1;
+            async { Err(Error::RateLimit { retry_after: Some(5) }) }
+        })
+        .await;
+
+        assert!(result.unwrap_err().is_rate_limit());
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_attempt_cancelled_at_deadline() {
+        let config = RetryPolicyBuilder::new()
+            .deadline(Duration::from_millis(50))
+            .build();
+
+        let result: Result<()> = retry_with_backoff(&config, || async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(())
+        })
+        .await;
+
+        assert!(result.unwrap_err().is_timeout());
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_stops_retries() {
+        let config = RetryPolicyBuilder::new()
+            .max_retries(100)
+            .initial_interval(Duration::from_millis(20))
+            .build();
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            canceller.cancel();
+        });
+
+        let mut attempts = 0;
+        let result: Result<()> = retry_with_classifier(
+            &config,
+            &DefaultRetryClassifier,
+            RetryContext::new(Method::GET, "/v0/tts/voices").with_cancellation(token),
+            || {
+                attempts += 1;
+                async { Err(Error::api(503, "unavailable".into(), None, None)) }
+            },
+        )
+        .await;
+
+        assert!(result.unwrap_err().is_cancelled());
+        assert!(attempts < 10, "{} attempts", attempts);
     }
 
     #[test]
@@ -222,6 +527,13 @@ mod tests {
         let error = Error::RateLimit { retry_after: None };
         assert_eq!(get_retry_after(&error), None);
         
+        // Server errors with a Retry-After header
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("retry-after", "3".parse().unwrap());
+        let meta = ResponseMeta::new(reqwest::StatusCode::SERVICE_UNAVAILABLE, headers, Duration::ZERO, 1);
+        let error = Error::api(503, "unavailable".into(), None, None).with_meta(meta);
+        assert_eq!(get_retry_after(&error), Some(Duration::from_secs(3)));
+
         // Other errors
         let error = Error::Timeout;
         assert_eq!(get_retry_after(&error), None);
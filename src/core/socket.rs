@@ -0,0 +1,332 @@
+//! WebSocket connections used by the streaming clients
+//!
+//! [`WebSocket`] wraps a live connection, optionally recording its text frames
+//! to a [`Cassette`](crate::core::cassette::Cassette), or serves a recorded
+//! session back without a network connection. [`WsConnector`] opens live
+//! connections with the client's proxy and TLS settings.
+//!
+//! A socket with a [`CancellationToken`] closes itself with a close frame
+//! once the token is cancelled, failing the pending call with
+//! [`Error::Cancelled`].
+//!
+//! Messages are sent and received inside the session's `hume.websocket`
+//! span, and reported to the client's [`Metrics`], if any.
+
+use crate::core::{
+    cancel::CancellationToken,
+    cassette::{SocketRecorder, SocketReplay},
+    error::{Error, Result},
+    metrics::{Metrics, StreamDirection},
+    proxy::{Io, ProxyConfig},
+    tls::TlsConfig,
+};
+use futures_util::{SinkExt, StreamExt};
+use rustls::ClientConfig;
+use std::{fmt, io, sync::Arc, time::Instant};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    client_async_tls_with_config,
+    tungstenite::{
+        client::IntoClientRequest,
+        error::UrlError,
+        http::HeaderMap,
+        protocol::{frame::coding::CloseCode, CloseFrame, Message},
+        Error as WsError,
+    },
+    Connector, MaybeTlsStream, WebSocketStream,
+};
+use tracing::{field, Instrument, Span};
+
+/// A live tungstenite WebSocket stream
+pub(crate) type WsStream = WebSocketStream<MaybeTlsStream<Box<dyn Io>>>;
+
+/// Opens WebSocket connections, directly or through a proxy
+#[derive(Debug, Clone, Default)]
+pub(crate) struct WsConnector {
+    proxy: Option<ProxyConfig>,
+    // `None` uses the default `TlsConfig`, trusting the same webpki roots as REST
+    tls: Option<Arc<ClientConfig>>,
+}
+
+impl WsConnector {
+    pub(crate) fn new(proxy: Option<ProxyConfig>, tls: Option<Arc<ClientConfig>>) -> Self {
+        Self { proxy, tls }
+    }
+
+    /// Connect and perform the WebSocket handshake, sending `headers` with the request
+    pub(crate) async fn connect(&self, url: &str, headers: HeaderMap) -> std::result::Result<WsStream, WsError> {
+        let mut request = url.into_client_request()?;
+        request.headers_mut().extend(headers);
+        let uri = request.uri();
+        let host = uri
+            .host()
+            .ok_or(WsError::Url(UrlError::NoHostName))?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let port = uri
+            .port_u16()
+            .unwrap_or(if uri.scheme_str() == Some("wss") { 443 } else { 80 });
+
+        // The proxy and the WebSocket server are verified with the same settings
+        let tls = match &self.tls {
+            Some(tls) => tls.clone(),
+            None => TlsConfig::new().build().map_err(io::Error::other)?,
+        };
+        let stream: Box<dyn Io> = match &self.proxy {
+            Some(proxy) => proxy.connect(&host, port, tls.clone()).await?,
+            None => Box::new(TcpStream::connect((host.as_str(), port)).await?),
+        };
+
+        let connector = Some(Connector::Rustls(tls));
+        let (ws, _) = client_async_tls_with_config(request, stream, None, connector).await?;
+        Ok(ws)
+    }
+}
+
+/// A WebSocket connection, live or replayed from a cassette
+#[derive(Debug)]
+pub struct WebSocket {
+    inner: Connection,
+    cancellation: Option<CancellationToken>,
+    telemetry: Telemetry,
+}
+
+/// Span and metrics for a WebSocket session
+#[derive(Debug)]
+struct Telemetry {
+    endpoint: String,
+    span: Span,
+    metrics: Option<Arc<dyn Metrics>>,
+    opened: Instant,
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self {
+            endpoint: String::new(),
+            span: Span::none(),
+            metrics: None,
+            opened: Instant::now(),
+        }
+    }
+}
+
+impl Telemetry {
+    /// Span for a single message sent or received in the session
+    fn message_span(&self, direction: StreamDirection) -> Span {
+        tracing::debug_span!(parent: &self.span, "hume.message", direction = %direction, bytes = field::Empty)
+    }
+
+    /// Record the size of a message on its span and with the metrics
+    fn record(&self, span: &Span, direction: StreamDirection, message: &Message) {
+        let bytes = message.len();
+        span.record("bytes", bytes);
+        if let Some(metrics) = &self.metrics {
+            metrics.bytes_streamed(&self.endpoint, direction, bytes);
+        }
+    }
+}
+
+enum Connection {
+    Live(WsStream),
+    Recording(WsStream, SocketRecorder),
+    Replay(SocketReplay),
+    #[cfg(feature = "mock")]
+    Scripted(Scripted),
+}
+
+/// In-memory session serving queued messages, used by the `mock` fakes
+#[cfg(feature = "mock")]
+#[derive(Debug)]
+struct Scripted {
+    received: std::collections::VecDeque<String>,
+    sent: Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+impl fmt::Debug for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Connection::Live(_) => f.write_str("Live"),
+            Connection::Recording(_, recorder) => f.debug_tuple("Recording").field(recorder).finish(),
+            Connection::Replay(replay) => f.debug_tuple("Replay").field(replay).finish(),
+            #[cfg(feature = "mock")]
+            Connection::Scripted(scripted) => f.debug_tuple("Scripted").field(scripted).finish(),
+        }
+    }
+}
+
+impl WebSocket {
+    pub(crate) fn live(ws: WsStream) -> Self {
+        Self {
+            inner: Connection::Live(ws),
+            cancellation: None,
+            telemetry: Telemetry::default(),
+        }
+    }
+
+    pub(crate) fn recording(ws: WsStream, recorder: SocketRecorder) -> Self {
+        Self {
+            inner: Connection::Recording(ws, recorder),
+            cancellation: None,
+            telemetry: Telemetry::default(),
+        }
+    }
+
+    pub(crate) fn replay(replay: SocketReplay) -> Self {
+        Self {
+            inner: Connection::Replay(replay),
+            cancellation: None,
+            telemetry: Telemetry::default(),
+        }
+    }
+
+    /// A session that receives `received` in order and appends sent text frames to `sent`
+    #[cfg(feature = "mock")]
+    pub(crate) fn scripted(received: Vec<String>, sent: Arc<std::sync::Mutex<Vec<String>>>) -> Self {
+        Self {
+            inner: Connection::Scripted(Scripted {
+                received: received.into(),
+                sent,
+            }),
+            cancellation: None,
+            telemetry: Telemetry::default(),
+        }
+    }
+
+    /// Close the connection when `token` is cancelled
+    pub fn set_cancellation(&mut self, token: CancellationToken) {
+        self.cancellation = Some(token);
+    }
+
+    /// Attach the session span and report to `metrics`, starting the session's lifetime
+    pub(crate) fn set_telemetry(&mut self, endpoint: String, span: Span, metrics: Option<Arc<dyn Metrics>>) {
+        if let Some(metrics) = &metrics {
+            metrics.socket_opened(&endpoint);
+        }
+        self.telemetry = Telemetry {
+            endpoint,
+            span,
+            metrics,
+            opened: Instant::now(),
+        };
+    }
+
+    /// The `hume.websocket` span of the session
+    ///
+    /// Its `chat_id` field is recorded once the chat has started.
+    pub fn span(&self) -> &Span {
+        &self.telemetry.span
+    }
+
+    /// Send a message
+    pub async fn send(&mut self, message: Message) -> Result<()> {
+        if self.cancellation.as_ref().is_some_and(|token| token.is_cancelled()) {
+            return Err(self.close_cancelled().await);
+        }
+        let span = self.telemetry.message_span(StreamDirection::Sent);
+        self.telemetry.record(&span, StreamDirection::Sent, &message);
+        self.send_message(message).instrument(span).await
+    }
+
+    async fn send_message(&mut self, message: Message) -> Result<()> {
+        match &mut self.inner {
+            Connection::Live(ws) => ws.send(message).await?,
+            Connection::Recording(ws, recorder) => {
+                if let Message::Text(text) = &message {
+                    recorder.sent(text);
+                }
+                ws.send(message).await?;
+            }
+            Connection::Replay(replay) => {
+                if let Message::Text(text) = &message {
+                    replay.send(text)?;
+                }
+            }
+            #[cfg(feature = "mock")]
+            Connection::Scripted(scripted) => {
+                if let Message::Text(text) = message {
+                    scripted.sent.lock().unwrap_or_else(|e| e.into_inner()).push(text);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Receive the next message, or `None` when the connection has ended
+    pub async fn next(&mut self) -> Option<Result<Message>> {
+        let Some(token) = self.cancellation.clone() else {
+            return self.next_message().await;
+        };
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => {}
+            message = self.next_message() => return message,
+        }
+        Some(Err(self.close_cancelled().await))
+    }
+
+    async fn next_message(&mut self) -> Option<Result<Message>> {
+        let span = self.telemetry.message_span(StreamDirection::Received);
+        let message = self.receive_message().instrument(span.clone()).await;
+        if let Some(Ok(message)) = &message {
+            self.telemetry.record(&span, StreamDirection::Received, message);
+        }
+        message
+    }
+
+    async fn receive_message(&mut self) -> Option<Result<Message>> {
+        match &mut self.inner {
+            Connection::Live(ws) => ws.next().await.map(|message| message.map_err(Into::into)),
+            Connection::Recording(ws, recorder) => {
+                let message = ws.next().await;
+                if let Some(Ok(Message::Text(text))) = &message {
+                    recorder.received(text);
+                }
+                message.map(|message| message.map_err(Into::into))
+            }
+            Connection::Replay(replay) => replay.receive().map(|text| Ok(Message::Text(text))),
+            #[cfg(feature = "mock")]
+            Connection::Scripted(scripted) => scripted.received.pop_front().map(|text| Ok(Message::Text(text))),
+        }
+    }
+
+    /// Close the connection
+    pub async fn close(&mut self) -> Result<()> {
+        self.close_with(None).await
+    }
+
+    /// Close the connection after cancellation, returning [`Error::Cancelled`]
+    async fn close_cancelled(&mut self) -> Error {
+        let frame = CloseFrame {
+            code: CloseCode::Normal,
+            reason: "cancelled".into(),
+        };
+        if let Err(e) = self.close_with(Some(frame)).await {
+            tracing::debug!("Failed to close cancelled WebSocket: {}", e);
+        }
+        Error::Cancelled
+    }
+
+    async fn close_with(&mut self, frame: Option<CloseFrame<'static>>) -> Result<()> {
+        match &mut self.inner {
+            Connection::Live(ws) => ws.close(frame).await?,
+            Connection::Recording(ws, recorder) => {
+                ws.close(frame).await?;
+                recorder.finish();
+            }
+            Connection::Replay(_) => {}
+            #[cfg(feature = "mock")]
+            Connection::Scripted(_) => {}
+        }
+        Ok(())
+    }
+}
+
+impl Drop for WebSocket {
+    fn drop(&mut self) {
+        if let Some(metrics) = &self.telemetry.metrics {
+            metrics.socket_closed(&self.telemetry.endpoint, self.telemetry.opened.elapsed());
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! TLS settings shared by REST and WebSocket connections
+//!
+//! A customized [`TlsConfig`] is compiled into a single rustls client
+//! configuration that is used both by the default HTTP transport and by the
+//! `ChatSocket`/`StreamSocket` connections, so extra root certificates,
+//! client certificates and the minimum protocol version apply everywhere.
+
+use crate::core::error::{Error, Result};
+use rustls::{
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+    ClientConfig, RootCertStore, SupportedProtocolVersion,
+};
+use std::{fmt, sync::Arc};
+
+/// Minimum TLS protocol version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TlsVersion {
+    /// TLS 1.2
+    Tls12,
+    /// TLS 1.3
+    Tls13,
+}
+
+/// TLS settings for connections to the Hume API
+#[derive(Clone)]
+pub struct TlsConfig {
+    root_certificates: Vec<Vec<u8>>,
+    built_in_roots: bool,
+    client_identity: Option<(Vec<u8>, Vec<u8>)>,
+    min_version: Option<TlsVersion>,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            root_certificates: Vec::new(),
+            built_in_roots: true,
+            client_identity: None,
+            min_version: None,
+        }
+    }
+}
+
+impl TlsConfig {
+    /// Create the default TLS settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust the PEM-encoded root certificates, e.g. a private CA
+    pub fn add_root_certificate_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates.push(pem.into());
+        self
+    }
+
+    /// Set whether the built-in Mozilla root certificates are trusted (default: true)
+    pub fn built_in_roots(mut self, enabled: bool) -> Self {
+        self.built_in_roots = enabled;
+        self
+    }
+
+    /// Present a client certificate for mutual TLS
+    ///
+    /// Takes a PEM-encoded certificate chain and private key.
+    pub fn client_identity_pem(mut self, cert_chain: impl Into<Vec<u8>>, private_key: impl Into<Vec<u8>>) -> Self {
+        self.client_identity = Some((cert_chain.into(), private_key.into()));
+        self
+    }
+
+    /// Set the minimum TLS protocol version
+    pub fn min_version(mut self, version: TlsVersion) -> Self {
+        self.min_version = Some(version);
+        self
+    }
+
+    /// Check if these are the default settings
+    pub fn is_default(&self) -> bool {
+        self.root_certificates.is_empty()
+            && self.built_in_roots
+            && self.client_identity.is_none()
+            && self.min_version.is_none()
+    }
+
+    /// Build the rustls client configuration
+    pub fn build(&self) -> Result<Arc<ClientConfig>> {
+        let versions: &[&'static SupportedProtocolVersion] = match self.min_version {
+            Some(TlsVersion::Tls13) => &[&rustls::version::TLS13],
+            Some(TlsVersion::Tls12) | None => &[&rustls::version::TLS13, &rustls::version::TLS12],
+        };
+
+        let mut roots = RootCertStore::empty();
+        if self.built_in_roots {
+            roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        }
+        for pem in &self.root_certificates {
+            for certificate in CertificateDer::pem_slice_iter(pem) {
+                let certificate = certificate
+                    .map_err(|e| Error::config(format!("Invalid root certificate: {}", e)))?;
+                roots
+                    .add(certificate)
+                    .map_err(|e| Error::config(format!("Invalid root certificate: {}", e)))?;
+            }
+        }
+        if roots.is_empty() {
+            return Err(Error::config("No root certificates configured"));
+        }
+
+        let builder = ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_protocol_versions(versions)
+            .map_err(|e| Error::config(format!("Invalid TLS configuration: {}", e)))?
+            .with_root_certificates(roots);
+
+        let config = match &self.client_identity {
+            Some((cert_chain, private_key)) => {
+                let certificates = CertificateDer::pem_slice_iter(cert_chain)
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|e| Error::config(format!("Invalid client certificate: {}", e)))?;
+                let key = PrivateKeyDer::from_pem_slice(private_key)
+                    .map_err(|e| Error::config(format!("Invalid client private key: {}", e)))?;
+                builder
+                    .with_client_auth_cert(certificates, key)
+                    .map_err(|e| Error::config(format!("Invalid client identity: {}", e)))?
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        Ok(Arc::new(config))
+    }
+}
+
+impl fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsConfig")
+            .field("root_certificates", &self.root_certificates.len())
+            .field("built_in_roots", &self.built_in_roots)
+            .field("client_identity", &self.client_identity.is_some())
+            .field("min_version", &self.min_version)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_builds() {
+        let config = TlsConfig::new();
+        assert!(config.is_default());
+        assert!(config.build().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_root_certificate() {
+        let pem = "-----BEGIN CERTIFICATE-----\nnot base64!\n-----END CERTIFICATE-----\n";
+        let result = TlsConfig::new().add_root_certificate_pem(pem).build();
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn test_no_roots_is_rejected() {
+        let result = TlsConfig::new().built_in_roots(false).build();
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+
+    #[test]
+    fn test_min_version_tls13() {
+        let config = TlsConfig::new().min_version(TlsVersion::Tls13).build().unwrap();
+        assert!(format!("{:?}", config).contains("TLSv1_3"));
+        assert!(!format!("{:?}", config).contains("TLSv1_2"));
+    }
+}
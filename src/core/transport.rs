@@ -0,0 +1,371 @@
+//! Pluggable HTTP transport layer
+//!
+//! [`HttpClient`](crate::core::http::HttpClient) builds requests and handles
+//! retries and errors, then hands each attempt to a [`Transport`]. The default
+//! is [`ReqwestTransport`]; [`MockTransport`] serves scripted responses from
+//! memory for unit tests.
+
+use crate::core::error::{Error, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Method, StatusCode,
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use url::Url;
+
+/// A stream of response body chunks
+pub type BodyStream = Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>;
+
+/// A fully built HTTP request handed to a [`Transport`]
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    /// HTTP method
+    pub method: Method,
+    /// Absolute request URL, including query parameters
+    pub url: Url,
+    /// Request headers
+    pub headers: HeaderMap,
+    /// Request body
+    pub body: Option<Bytes>,
+    /// Timeout for this attempt
+    pub timeout: Option<Duration>,
+}
+
+impl HttpRequest {
+    /// Create a new request without headers or body
+    pub fn new(method: Method, url: Url) -> Self {
+        Self {
+            method,
+            url,
+            headers: HeaderMap::new(),
+            body: None,
+            timeout: None,
+        }
+    }
+
+    /// Deserialize the request body as JSON
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        let body = self.body.as_deref().unwrap_or_default();
+        Ok(serde_json::from_slice(body)?)
+    }
+}
+
+/// An HTTP response returned by a [`Transport`]
+pub struct HttpResponse {
+    /// HTTP status code
+    pub status: StatusCode,
+    /// Response headers
+    pub headers: HeaderMap,
+    body: BodyStream,
+}
+
+impl HttpResponse {
+    /// Create a response with a complete body
+    pub fn new(status: StatusCode, headers: HeaderMap, body: impl Into<Bytes>) -> Self {
+        let body = body.into();
+        Self::from_stream(status, headers, Box::pin(stream::once(async move { Ok(body) })))
+    }
+
+    /// Create a response with a streaming body
+    pub fn from_stream(status: StatusCode, headers: HeaderMap, body: BodyStream) -> Self {
+        Self {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    /// Get the HTTP status code
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Get the response headers
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Collect the full response body
+    pub async fn bytes(self) -> Result<Bytes> {
+        let chunks: Vec<Bytes> = self.body.try_collect().await?;
+        Ok(chunks.concat().into())
+    }
+
+    /// Collect the full response body as text
+    pub async fn text(self) -> Result<String> {
+        let bytes = self.bytes().await?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Read the full body into memory, keeping it available for later reads
+    ///
+    /// Useful in middleware that needs to inspect a body without consuming it.
+    pub async fn buffer(&mut self) -> Result<Bytes> {
+        let body = std::mem::replace(&mut self.body, Box::pin(stream::empty()));
+        let chunks: Vec<Bytes> = body.try_collect().await?;
+        let bytes = Bytes::from(chunks.concat());
+        let replay = bytes.clone();
+        self.body = Box::pin(stream::once(async move { Ok(replay) }));
+        Ok(bytes)
+    }
+
+    /// Keep `guard` alive until the body has been read or dropped
+    pub(crate) fn hold<G: Send + 'static>(mut self, guard: G) -> Self {
+        self.body = Box::pin(self.body.map(move |chunk| {
+            let _ = &guard;
+            chunk
+        }));
+        self
+    }
+
+    /// Consume the response into a stream of body chunks
+    pub fn into_stream(self) -> BodyStream {
+        self.body
+    }
+}
+
+impl fmt::Debug for HttpResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sends HTTP requests on behalf of [`HttpClient`](crate::core::http::HttpClient)
+///
+/// Implementations perform a single attempt; retries, authentication and
+/// error mapping are handled by the caller.
+#[async_trait]
+pub trait Transport: Send + Sync + fmt::Debug {
+    /// Send a request and return the response
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+/// Default transport backed by [`reqwest::Client`]
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Create a transport with the SDK's default client settings
+    pub fn new() -> Result<Self> {
+        let client = Self::client_builder().build()?;
+        Ok(Self { client })
+    }
+
+    /// Get a reqwest client builder with the SDK's default settings
+    pub fn client_builder() -> reqwest::ClientBuilder {
+        reqwest::Client::builder()
+            .user_agent(format!("hume-rust-sdk/{}", crate::SDK_VERSION))
+            .timeout(Duration::from_secs(30))
+    }
+
+    /// Get the underlying reqwest client
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+}
+
+impl From<reqwest::Client> for ReqwestTransport {
+    fn from(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut builder = self
+            .client
+            .request(request.method, request.url)
+            .headers(request.headers);
+
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        let response = builder.send().await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes_stream().map(|chunk| chunk.map_err(Error::from));
+
+        Ok(HttpResponse::from_stream(status, headers, Box::pin(body)))
+    }
+}
+
+/// Scripted responses keyed by method and path
+type MockRoutes = HashMap<(Method, String), Vec<MockResponse>>;
+
+/// A scripted response served by [`MockTransport`]
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl MockResponse {
+    /// Create an empty response with the given status
+    pub fn new(status: u16) -> Self {
+        Self {
+            status: StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+        }
+    }
+
+    /// Set a JSON body
+    pub fn json(mut self, body: serde_json::Value) -> Self {
+        self.headers.insert(
+            reqwest::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        self.body = body.to_string().into();
+        self
+    }
+
+    /// Set a raw body
+    pub fn body(mut self, body: impl Into<Bytes>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Add a response header
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            self.headers.insert(name, value);
+        }
+        self
+    }
+
+    fn to_response(&self) -> HttpResponse {
+        HttpResponse::new(self.status, self.headers.clone(), self.body.clone())
+    }
+}
+
+/// In-memory transport serving scripted responses, for unit tests
+///
+/// Responses are matched on method and URL path. When several responses are
+/// registered for the same route they are served in order, and the last one
+/// is repeated. Unmatched requests receive a 404.
+///
+/// ```
+/// use hume::core::transport::{MockResponse, MockTransport};
+/// use reqwest::Method;
+///
+/// let transport = MockTransport::new()
+///     .with_response(Method::GET, "/v0/tts/voices", MockResponse::new(500))
+///     .with_response(
+///         Method::GET,
+///         "/v0/tts/voices",
+///         MockResponse::new(200).json(serde_json::json!({ "voices": [] })),
+///     );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+    routes: Arc<Mutex<MockRoutes>>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl MockTransport {
+    /// Create a transport with no scripted responses
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a response for a method and path
+    pub fn with_response(self, method: Method, path: impl Into<String>, response: MockResponse) -> Self {
+        self.push_response(method, path, response);
+        self
+    }
+
+    /// Register a JSON response for a method and path
+    pub fn with_json(self, method: Method, path: impl Into<String>, status: u16, body: serde_json::Value) -> Self {
+        self.with_response(method, path, MockResponse::new(status).json(body))
+    }
+
+    /// Register a response for a method and path on a shared transport
+    pub fn push_response(&self, method: Method, path: impl Into<String>, response: MockResponse) {
+        self.routes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry((method, path.into()))
+            .or_default()
+            .push(response);
+    }
+
+    /// Get all requests received so far
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[async_trait]
+impl Transport for MockTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let key = (request.method.clone(), request.url.path().to_string());
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(request);
+
+        let mut routes = self.routes.lock().unwrap_or_else(|e| e.into_inner());
+        let response = match routes.get_mut(&key) {
+            Some(queue) if queue.len() > 1 => queue.remove(0),
+            Some(queue) if !queue.is_empty() => queue[0].clone(),
+            _ => MockResponse::new(404),
+        };
+
+        Ok(response.to_response())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_transport_serves_in_order() {
+        let transport = MockTransport::new()
+            .with_response(Method::GET, "/v0/test", MockResponse::new(500))
+            .with_json(Method::GET, "/v0/test", 200, serde_json::json!({ "ok": true }));
+
+        let url = Url::parse("https://api.hume.ai/v0/test?page=1").unwrap();
+        let first = transport.send(HttpRequest::new(Method::GET, url.clone())).await.unwrap();
+        let second = transport.send(HttpRequest::new(Method::GET, url.clone())).await.unwrap();
+        let third = transport.send(HttpRequest::new(Method::GET, url)).await.unwrap();
+
+        assert_eq!(first.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(second.status(), StatusCode::OK);
+        assert_eq!(third.text().await.unwrap(), r#"{"ok":true}"#);
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_mock_transport_unmatched_route() {
+        let transport = MockTransport::new();
+        let url = Url::parse("https://api.hume.ai/v0/missing").unwrap();
+        let response = transport.send(HttpRequest::new(Method::POST, url)).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -1,16 +1,20 @@
 //! WebSocket chat client for EVI
 
 use crate::{
-    core::{client::HumeClient, error::Result},
+    core::{
+        cancel::{cancellable, CancellationToken},
+        client::HumeClient,
+        endpoints::Endpoints,
+        error::{ErrorKind, HumeCode, Result},
+        pagination::Paginator,
+        response::WithMeta,
+        socket::WebSocket,
+    },
     evi::models::*,
 };
-use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tokio::net::TcpStream;
-use tokio_tungstenite::{
-    connect_async, tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream,
-};
+use tokio_tungstenite::tungstenite::protocol::Message;
 
 /// Client for EVI chat functionality
 #[derive(Debug, Clone)]
@@ -25,39 +29,35 @@ impl ChatClient {
     }
 
     /// Connect to a chat session
+    ///
+    /// Without a config ID, the profile's default EVI config is used, if any.
     pub async fn connect(
         &self,
         config_id: Option<String>,
         config_version: Option<u32>,
         resumed_chat_group_id: Option<String>,
     ) -> Result<ChatSocket> {
-        let auth = self
-            .client
-            .http
-            .auth
-            .as_ref()
-            .ok_or_else(|| crate::core::error::Error::auth("No authentication configured"))?;
-
-        let (param_name, param_value) = auth.query_param();
-        let mut ws_url = format!(
-            "{}/v0/evi/chat?{}={}",
-            self.client.base_url().replace("https://", "wss://"),
-            param_name,
-            param_value
-        );
-
-        // Add optional parameters
+        let defaults = &self.client.defaults().evi;
+        let (config_id, config_version) = match config_id {
+            Some(id) => (Some(id), config_version),
+            None => (
+                defaults.config_id.clone(),
+                config_version.or(defaults.config_version),
+            ),
+        };
+        let mut query = Vec::new();
         if let Some(id) = &config_id {
-            ws_url.push_str(&format!("&config_id={}", id));
+            query.push(("config_id", id.clone()));
         }
         if let Some(version) = config_version {
-            ws_url.push_str(&format!("&config_version={}", version));
+            query.push(("config_version", version.to_string()));
         }
         if let Some(group_id) = &resumed_chat_group_id {
-            ws_url.push_str(&format!("&resumed_chat_group_id={}", group_id));
+            query.push(("resumed_chat_group_id", group_id.clone()));
         }
 
-        let (ws_stream, _) = connect_async(&ws_url).await?;
+        let url = self.client.endpoints().websocket_url("/v0/evi/chat", query);
+        let ws_stream = self.client.http.connect_websocket(url).await?;
 
         Ok(ChatSocket::new(ws_stream))
     }
@@ -69,6 +69,16 @@ impl ChatClient {
         page_size: Option<u32>,
         ascending_order: Option<bool>,
     ) -> Result<ReturnPagedChats> {
+        Ok(self.list_chats_with_response(page_number, page_size, ascending_order).await?.data)
+    }
+
+    /// List chat history, returning response metadata with the result
+    pub async fn list_chats_with_response(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        ascending_order: Option<bool>,
+    ) -> Result<WithMeta<ReturnPagedChats>> {
         let mut req_options = crate::core::request::RequestOptions::new();
         
         if let Some(page) = page_number {
@@ -83,14 +93,19 @@ impl ChatClient {
 
         self.client
             .http
-            .get("/v0/evi/chats", Some(req_options))
+            .get_with_response("/v0/evi/chats", Some(req_options))
             .await
     }
 
     /// Get a specific chat
     pub async fn get_chat(&self, chat_id: &str) -> Result<Chat> {
-        let path = format!("/v0/evi/chats/{}", chat_id);
-        self.client.http.get(&path, None).await
+        Ok(self.get_chat_with_response(chat_id).await?.data)
+    }
+
+    /// Get a specific chat, returning response metadata with the result
+    pub async fn get_chat_with_response(&self, chat_id: &str) -> Result<WithMeta<Chat>> {
+        let path = Endpoints::path(&["v0", "evi", "chats", chat_id]);
+        self.client.http.get_with_response(&path, None).await
     }
 
     /// List chat groups
@@ -100,6 +115,16 @@ impl ChatClient {
         page_size: Option<u32>,
         ascending_order: Option<bool>,
     ) -> Result<ReturnPagedChatGroups> {
+        Ok(self.list_chat_groups_with_response(page_number, page_size, ascending_order).await?.data)
+    }
+
+    /// List chat groups, returning response metadata with the result
+    pub async fn list_chat_groups_with_response(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        ascending_order: Option<bool>,
+    ) -> Result<WithMeta<ReturnPagedChatGroups>> {
         let mut req_options = crate::core::request::RequestOptions::new();
         
         if let Some(page) = page_number {
@@ -114,7 +139,7 @@ impl ChatClient {
 
         self.client
             .http
-            .get("/v0/evi/chat_groups", Some(req_options))
+            .get_with_response("/v0/evi/chat_groups", Some(req_options))
             .await
     }
 
@@ -125,7 +150,17 @@ impl ChatClient {
         page_number: Option<u32>,
         page_size: Option<u32>,
     ) -> Result<PagedResponse<ChatMessage>> {
-        let path = format!("/v0/evi/chats/{}/messages", chat_id);
+        Ok(self.get_chat_messages_with_response(chat_id, page_number, page_size).await?.data)
+    }
+
+    /// Get chat messages, returning response metadata with the result
+    pub async fn get_chat_messages_with_response(
+        &self,
+        chat_id: &str,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+    ) -> Result<WithMeta<PagedResponse<ChatMessage>>> {
+        let path = Endpoints::path(&["v0", "evi", "chats", chat_id, "messages"]);
         let mut req_options = crate::core::request::RequestOptions::new();
         
         if let Some(page) = page_number {
@@ -135,22 +170,72 @@ impl ChatClient {
             req_options = req_options.with_query("page_size", size.to_string());
         }
 
-        self.client.http.get(&path, Some(req_options)).await
+        self.client.http.get_with_response(&path, Some(req_options)).await
+    }
+
+    /// Page through all chats
+    pub fn paginate_chats(&self, ascending_order: Option<bool>) -> Paginator<ReturnPagedChats> {
+        let client = self.clone();
+        Paginator::new(move |page| {
+            let client = client.clone();
+            async move {
+                client
+                    .list_chats(Some(page.page_number), Some(page.page_size), ascending_order)
+                    .await
+            }
+        })
+    }
+
+    /// Page through all chat groups
+    pub fn paginate_chat_groups(&self, ascending_order: Option<bool>) -> Paginator<ReturnPagedChatGroups> {
+        let client = self.clone();
+        Paginator::new(move |page| {
+            let client = client.clone();
+            async move {
+                client
+                    .list_chat_groups(Some(page.page_number), Some(page.page_size), ascending_order)
+                    .await
+            }
+        })
+    }
+
+    /// Page through all messages of a chat
+    pub fn paginate_chat_messages(&self, chat_id: impl Into<String>) -> Paginator<PagedResponse<ChatMessage>> {
+        let client = self.clone();
+        let chat_id = chat_id.into();
+        Paginator::new(move |page| {
+            let client = client.clone();
+            let chat_id = chat_id.clone();
+            async move {
+                client
+                    .get_chat_messages(&chat_id, Some(page.page_number), Some(page.page_size))
+                    .await
+            }
+        })
     }
 }
 
 /// WebSocket connection for EVI chat
 #[derive(Debug)]
 pub struct ChatSocket {
-    ws: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    ws: WebSocket,
 }
 
 impl ChatSocket {
     /// Create a new chat socket
-    fn new(ws: WebSocketStream<MaybeTlsStream<TcpStream>>) -> Self {
+    pub(crate) fn new(ws: WebSocket) -> Self {
         Self { ws }
     }
 
+    /// Close the socket when `token` is cancelled
+    ///
+    /// Pending and later calls then fail with
+    /// [`Error::Cancelled`](crate::core::error::Error::Cancelled).
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.ws.set_cancellation(token);
+        self
+    }
+
     /// Send session settings
     pub async fn send_session_settings(&mut self, settings: SessionSettings) -> Result<()> {
         let message = ClientMessage::SessionSettings { settings };
@@ -234,10 +319,13 @@ impl ChatSocket {
         match self.ws.next().await {
             Some(Ok(Message::Text(text))) => {
                 let message = serde_json::from_str(&text)?;
+                if let ServerMessage::SessionStarted { chat_id, .. } = &message {
+                    self.ws.span().record("chat_id", chat_id.as_str());
+                }
                 Ok(Some(message))
             }
             Some(Ok(Message::Close(_))) => Ok(None),
-            Some(Err(e)) => Err(e.into()),
+            Some(Err(e)) => Err(e),
             None => Ok(None),
             _ => Ok(Some(ServerMessage::Unknown)),
         }
@@ -245,7 +333,7 @@ impl ChatSocket {
 
     /// Close the connection
     pub async fn close(mut self) -> Result<()> {
-        self.ws.close(None).await?;
+        self.ws.close().await?;
         Ok(())
     }
 }
@@ -408,6 +496,27 @@ pub enum ServerMessage {
     Unknown,
 }
 
+impl ServerMessage {
+    /// Get the Hume code of an error or warning message, e.g. `E0300`
+    pub fn hume_code(&self) -> Option<HumeCode> {
+        match self {
+            Self::Error { code, .. } => code.parse().ok(),
+            Self::Warning { code, .. } => code.as_deref().and_then(|code| code.parse().ok()),
+            _ => None,
+        }
+    }
+
+    /// Classify an error or warning message by its Hume code
+    pub fn error_kind(&self) -> Option<ErrorKind> {
+        match self {
+            Self::Error { .. } | Self::Warning { .. } => {
+                Some(self.hume_code().map(|code| code.kind()).unwrap_or(ErrorKind::Other))
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Builder for chat sessions
 #[derive(Debug)]
 pub struct ChatSessionBuilder {
@@ -415,6 +524,7 @@ pub struct ChatSessionBuilder {
     config_version: Option<u32>,
     resumed_chat_group_id: Option<String>,
     session_settings: Option<SessionSettings>,
+    cancellation: Option<CancellationToken>,
 }
 
 impl ChatSessionBuilder {
@@ -425,6 +535,7 @@ impl ChatSessionBuilder {
             config_version: None,
             resumed_chat_group_id: None,
             session_settings: None,
+            cancellation: None,
         }
     }
 
@@ -452,11 +563,19 @@ impl ChatSessionBuilder {
         self
     }
 
+    /// Cancel connecting, and close the socket once connected, with `token`
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
     /// Connect to the chat
     pub async fn connect(self, client: &ChatClient) -> Result<ChatSocket> {
-        let mut socket = client
-            .connect(self.config_id, self.config_version, self.resumed_chat_group_id)
-            .await?;
+        let connect = client.connect(self.config_id, self.config_version, self.resumed_chat_group_id);
+        let mut socket = cancellable(self.cancellation.as_ref(), connect).await?;
+        if let Some(token) = self.cancellation {
+            socket = socket.with_cancellation(token);
+        }
 
         if let Some(settings) = self.session_settings {
             socket.send_session_settings(settings).await?;
@@ -1,7 +1,10 @@
 //! Configuration management client for EVI
 
 use crate::{
-    core::{client::HumeClient, error::Result, request::RequestOptions},
+    core::{
+        client::HumeClient, endpoints::Endpoints, error::Result, pagination::Paginator,
+        request::RequestOptions, response::WithMeta,
+    },
     evi::models::*,
 };
 use serde::Serialize;
@@ -26,6 +29,16 @@ impl ConfigsClient {
         page_size: Option<u32>,
         options: Option<RequestOptions>,
     ) -> Result<ReturnPagedConfigs> {
+        Ok(self.list_with_response(page_number, page_size, options).await?.data)
+    }
+
+    /// List all configurations, returning response metadata with the result
+    pub async fn list_with_response(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<ReturnPagedConfigs>> {
         let mut req_options = options.unwrap_or_default();
         
         if let Some(page) = page_number {
@@ -38,26 +51,58 @@ impl ConfigsClient {
 
         self.client
             .http
-            .get("/v0/evi/configs", Some(req_options))
+            .get_with_response("/v0/evi/configs", Some(req_options))
             .await
     }
 
+    /// Page through all configurations
+    pub fn paginate(&self, options: Option<RequestOptions>) -> Paginator<ReturnPagedConfigs> {
+        let client = self.clone();
+        Paginator::new(move |page| {
+            let client = client.clone();
+            let options = options.clone();
+            async move {
+                client
+                    .list(Some(page.page_number), Some(page.page_size), options)
+                    .await
+            }
+        })
+    }
+
     /// Create a new configuration
     pub async fn create(
         &self,
         request: CreateConfigRequest,
         options: Option<RequestOptions>,
     ) -> Result<Config> {
+        Ok(self.create_with_response(request, options).await?.data)
+    }
+
+    /// Create a new configuration, returning response metadata with the result
+    pub async fn create_with_response(
+        &self,
+        request: CreateConfigRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Config>> {
         self.client
             .http
-            .post("/v0/evi/configs", request, options)
+            .create_with_response("/v0/evi/configs", request, options)
             .await
     }
 
     /// Get a specific configuration
     pub async fn get(&self, config_id: &str, options: Option<RequestOptions>) -> Result<Config> {
-        let path = format!("/v0/evi/configs/{}", config_id);
-        self.client.http.get(&path, options).await
+        Ok(self.get_with_response(config_id, options).await?.data)
+    }
+
+    /// Get a specific configuration, returning response metadata with the result
+    pub async fn get_with_response(
+        &self,
+        config_id: &str,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Config>> {
+        let path = Endpoints::path(&["v0", "evi", "configs", config_id]);
+        self.client.http.get_with_response(&path, options).await
     }
 
     /// Update a configuration
@@ -67,17 +112,40 @@ impl ConfigsClient {
         request: UpdateConfigRequest,
         options: Option<RequestOptions>,
     ) -> Result<Config> {
-        let path = format!("/v0/evi/configs/{}", config_id);
-        self.client.http.patch(&path, request, options).await
+        Ok(self.update_with_response(config_id, request, options).await?.data)
+    }
+
+    /// Update a configuration, returning response metadata with the result
+    pub async fn update_with_response(
+        &self,
+        config_id: &str,
+        request: UpdateConfigRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Config>> {
+        let path = Endpoints::path(&["v0", "evi", "configs", config_id]);
+        self.client.http.patch_with_response(&path, request, options).await
     }
 
     /// Delete a configuration
     pub async fn delete(&self, config_id: &str, options: Option<RequestOptions>) -> Result<()> {
-        let path = format!("/v0/evi/configs/{}", config_id);
-        let _: serde_json::Value = self.client.http.delete(&path, options).await?;
+        self.delete_with_response(config_id, options).await?;
         Ok(())
     }
 
+    /// Delete a configuration, returning response metadata with the result
+    pub async fn delete_with_response(
+        &self,
+        config_id: &str,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<()>> {
+        let path = Endpoints::path(&["v0", "evi", "configs", config_id]);
+        let response: WithMeta<serde_json::Value> = self
+            .client
+            .http
+            .delete_with_response(&path, options).await?;
+        Ok(response.map(|_| ()))
+    }
+
     /// List configuration versions
     pub async fn list_versions(
         &self,
@@ -86,7 +154,18 @@ impl ConfigsClient {
         page_size: Option<u32>,
         options: Option<RequestOptions>,
     ) -> Result<ReturnPagedConfigs> {
-        let path = format!("/v0/evi/configs/{}/versions", config_id);
+        Ok(self.list_versions_with_response(config_id, page_number, page_size, options).await?.data)
+    }
+
+    /// List configuration versions, returning response metadata with the result
+    pub async fn list_versions_with_response(
+        &self,
+        config_id: &str,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<ReturnPagedConfigs>> {
+        let path = Endpoints::path(&["v0", "evi", "configs", config_id, "versions"]);
         let mut req_options = options.unwrap_or_default();
         
         if let Some(page) = page_number {
@@ -97,7 +176,7 @@ impl ConfigsClient {
             req_options = req_options.with_query("page_size", size.to_string());
         }
 
-        self.client.http.get(&path, Some(req_options)).await
+        self.client.http.get_with_response(&path, Some(req_options)).await
     }
 
     /// Get a specific configuration version
@@ -107,8 +186,18 @@ impl ConfigsClient {
         version: u32,
         options: Option<RequestOptions>,
     ) -> Result<Config> {
-        let path = format!("/v0/evi/configs/{}/versions/{}", config_id, version);
-        self.client.http.get(&path, options).await
+        Ok(self.get_version_with_response(config_id, version, options).await?.data)
+    }
+
+    /// Get a specific configuration version, returning response metadata with the result
+    pub async fn get_version_with_response(
+        &self,
+        config_id: &str,
+        version: u32,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Config>> {
+        let path = Endpoints::path(&["v0", "evi", "configs", config_id, "versions", &version.to_string()]);
+        self.client.http.get_with_response(&path, options).await
     }
 }
 
@@ -1,5 +1,6 @@
 //! Data models for Empathic Voice Interface API
 
+use crate::core::pagination::Page;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -34,6 +35,10 @@ pub struct Tool {
     /// Update timestamp
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
+    
+    /// Fields not known to this SDK version; empty unless the `lenient` feature is enabled
+    #[serde(flatten, default, deserialize_with = "crate::core::response::unknown_fields")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Prompt definition
@@ -63,6 +68,10 @@ pub struct Prompt {
     /// Update timestamp
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
+    
+    /// Fields not known to this SDK version; empty unless the `lenient` feature is enabled
+    #[serde(flatten, default, deserialize_with = "crate::core::response::unknown_fields")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Custom voice definition
@@ -88,6 +97,10 @@ pub struct CustomVoice {
     /// Update timestamp
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
+    
+    /// Fields not known to this SDK version; empty unless the `lenient` feature is enabled
+    #[serde(flatten, default, deserialize_with = "crate::core::response::unknown_fields")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Voice parameters
@@ -149,6 +162,10 @@ pub struct Config {
     /// Update timestamp
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
+    
+    /// Fields not known to this SDK version; empty unless the `lenient` feature is enabled
+    #[serde(flatten, default, deserialize_with = "crate::core::response::unknown_fields")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Prompt specification
@@ -357,6 +374,10 @@ pub struct Chat {
     /// Metadata
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
+    
+    /// Fields not known to this SDK version; empty unless the `lenient` feature is enabled
+    #[serde(flatten, default, deserialize_with = "crate::core::response::unknown_fields")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Chat status
@@ -396,6 +417,10 @@ pub struct ChatGroup {
     
     /// Last updated timestamp
     pub updated_at: DateTime<Utc>,
+    
+    /// Fields not known to this SDK version; empty unless the `lenient` feature is enabled
+    #[serde(flatten, default, deserialize_with = "crate::core::response::unknown_fields")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Chat message
@@ -420,6 +445,10 @@ pub struct ChatMessage {
     /// Emotion inference
     #[serde(skip_serializing_if = "Option::is_none")]
     pub emotion_inference: Option<EmotionInference>,
+    
+    /// Fields not known to this SDK version; empty unless the `lenient` feature is enabled
+    #[serde(flatten, default, deserialize_with = "crate::core::response::unknown_fields")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Message role
@@ -611,4 +640,124 @@ pub struct ReturnPagedChatGroups {
     
     /// List of chat groups
     pub chat_groups_page: Vec<ChatGroup>,
-}
\ No newline at end of file
+}
+
+impl<T: Send + 'static> Page for PagedResponse<T> {
+    type Item = T;
+
+    fn item_count(&self) -> usize {
+        self.items.len()
+    }
+
+    fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+
+    fn total_items(&self) -> Option<u64> {
+        self.total_items
+    }
+}
+
+impl Page for ReturnPagedConfigs {
+    type Item = Config;
+
+    fn item_count(&self) -> usize {
+        self.configs_page.as_ref().map_or(0, Vec::len)
+    }
+
+    fn into_items(self) -> Vec<Config> {
+        self.configs_page.unwrap_or_default()
+    }
+
+    fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+
+    fn total_items(&self) -> Option<u64> {
+        self.total_items
+    }
+}
+
+impl Page for ReturnPagedUserDefinedTools {
+    type Item = Tool;
+
+    fn item_count(&self) -> usize {
+        self.tools_page.len()
+    }
+
+    fn into_items(self) -> Vec<Tool> {
+        self.tools_page.into_iter().flatten().collect()
+    }
+
+    fn total_pages(&self) -> Option<u32> {
+        Some(self.total_pages)
+    }
+}
+
+impl Page for ReturnPagedPrompts {
+    type Item = Prompt;
+
+    fn item_count(&self) -> usize {
+        self.prompts_page.len()
+    }
+
+    fn into_items(self) -> Vec<Prompt> {
+        self.prompts_page.into_iter().flatten().collect()
+    }
+
+    fn total_pages(&self) -> Option<u32> {
+        Some(self.total_pages)
+    }
+}
+
+impl Page for ReturnPagedCustomVoices {
+    type Item = CustomVoice;
+
+    fn item_count(&self) -> usize {
+        self.custom_voices_page.len()
+    }
+
+    fn into_items(self) -> Vec<CustomVoice> {
+        self.custom_voices_page
+    }
+
+    fn total_pages(&self) -> Option<u32> {
+        Some(self.total_pages)
+    }
+}
+
+impl Page for ReturnPagedChats {
+    type Item = Chat;
+
+    fn item_count(&self) -> usize {
+        self.chats_page.len()
+    }
+
+    fn into_items(self) -> Vec<Chat> {
+        self.chats_page
+    }
+
+    fn total_pages(&self) -> Option<u32> {
+        Some(self.total_pages)
+    }
+}
+
+impl Page for ReturnPagedChatGroups {
+    type Item = ChatGroup;
+
+    fn item_count(&self) -> usize {
+        self.chat_groups_page.len()
+    }
+
+    fn into_items(self) -> Vec<ChatGroup> {
+        self.chat_groups_page
+    }
+
+    fn total_pages(&self) -> Option<u32> {
+        Some(self.total_pages)
+    }
+}
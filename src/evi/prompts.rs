@@ -1,7 +1,10 @@
 //! Prompts management client for EVI
 
 use crate::{
-    core::{client::HumeClient, error::Result, request::RequestOptions},
+    core::{
+        client::HumeClient, endpoints::Endpoints, error::Result, pagination::Paginator,
+        request::RequestOptions, response::WithMeta,
+    },
     evi::models::{ReturnPagedPrompts, Prompt},
 };
 use serde::Serialize;
@@ -26,6 +29,16 @@ impl PromptsClient {
         page_size: Option<u32>,
         options: Option<RequestOptions>,
     ) -> Result<ReturnPagedPrompts> {
+        Ok(self.list_with_response(page_number, page_size, options).await?.data)
+    }
+
+    /// List all prompts, returning response metadata with the result
+    pub async fn list_with_response(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<ReturnPagedPrompts>> {
         let mut req_options = options.unwrap_or_default();
         
         if let Some(page) = page_number {
@@ -38,26 +51,58 @@ impl PromptsClient {
 
         self.client
             .http
-            .get("/v0/evi/prompts", Some(req_options))
+            .get_with_response("/v0/evi/prompts", Some(req_options))
             .await
     }
 
+    /// Page through all prompts
+    pub fn paginate(&self, options: Option<RequestOptions>) -> Paginator<ReturnPagedPrompts> {
+        let client = self.clone();
+        Paginator::new(move |page| {
+            let client = client.clone();
+            let options = options.clone();
+            async move {
+                client
+                    .list(Some(page.page_number), Some(page.page_size), options)
+                    .await
+            }
+        })
+    }
+
     /// Create a new prompt
     pub async fn create(
         &self,
         request: CreatePromptRequest,
         options: Option<RequestOptions>,
     ) -> Result<Prompt> {
+        Ok(self.create_with_response(request, options).await?.data)
+    }
+
+    /// Create a new prompt, returning response metadata with the result
+    pub async fn create_with_response(
+        &self,
+        request: CreatePromptRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Prompt>> {
         self.client
             .http
-            .post("/v0/evi/prompts", request, options)
+            .create_with_response("/v0/evi/prompts", request, options)
             .await
     }
 
     /// Get a specific prompt
     pub async fn get(&self, prompt_id: &str, options: Option<RequestOptions>) -> Result<Prompt> {
-        let path = format!("/v0/evi/prompts/{}", prompt_id);
-        self.client.http.get(&path, options).await
+        Ok(self.get_with_response(prompt_id, options).await?.data)
+    }
+
+    /// Get a specific prompt, returning response metadata with the result
+    pub async fn get_with_response(
+        &self,
+        prompt_id: &str,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Prompt>> {
+        let path = Endpoints::path(&["v0", "evi", "prompts", prompt_id]);
+        self.client.http.get_with_response(&path, options).await
     }
 
     /// Update a prompt
@@ -67,17 +112,40 @@ impl PromptsClient {
         request: UpdatePromptRequest,
         options: Option<RequestOptions>,
     ) -> Result<Prompt> {
-        let path = format!("/v0/evi/prompts/{}", prompt_id);
-        self.client.http.patch(&path, request, options).await
+        Ok(self.update_with_response(prompt_id, request, options).await?.data)
+    }
+
+    /// Update a prompt, returning response metadata with the result
+    pub async fn update_with_response(
+        &self,
+        prompt_id: &str,
+        request: UpdatePromptRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Prompt>> {
+        let path = Endpoints::path(&["v0", "evi", "prompts", prompt_id]);
+        self.client.http.patch_with_response(&path, request, options).await
     }
 
     /// Delete a prompt
     pub async fn delete(&self, prompt_id: &str, options: Option<RequestOptions>) -> Result<()> {
-        let path = format!("/v0/evi/prompts/{}", prompt_id);
-        let _: serde_json::Value = self.client.http.delete(&path, options).await?;
+        self.delete_with_response(prompt_id, options).await?;
         Ok(())
     }
 
+    /// Delete a prompt, returning response metadata with the result
+    pub async fn delete_with_response(
+        &self,
+        prompt_id: &str,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<()>> {
+        let path = Endpoints::path(&["v0", "evi", "prompts", prompt_id]);
+        let response: WithMeta<serde_json::Value> = self
+            .client
+            .http
+            .delete_with_response(&path, options).await?;
+        Ok(response.map(|_| ()))
+    }
+
     /// List prompt versions
     pub async fn list_versions(
         &self,
@@ -86,7 +154,18 @@ impl PromptsClient {
         page_size: Option<u32>,
         options: Option<RequestOptions>,
     ) -> Result<ReturnPagedPrompts> {
-        let path = format!("/v0/evi/prompts/{}/versions", prompt_id);
+        Ok(self.list_versions_with_response(prompt_id, page_number, page_size, options).await?.data)
+    }
+
+    /// List prompt versions, returning response metadata with the result
+    pub async fn list_versions_with_response(
+        &self,
+        prompt_id: &str,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<ReturnPagedPrompts>> {
+        let path = Endpoints::path(&["v0", "evi", "prompts", prompt_id, "versions"]);
         let mut req_options = options.unwrap_or_default();
         
         if let Some(page) = page_number {
@@ -97,7 +176,7 @@ impl PromptsClient {
             req_options = req_options.with_query("page_size", size.to_string());
         }
 
-        self.client.http.get(&path, Some(req_options)).await
+        self.client.http.get_with_response(&path, Some(req_options)).await
     }
 
     /// Get a specific prompt version
@@ -107,8 +186,18 @@ impl PromptsClient {
         version: u32,
         options: Option<RequestOptions>,
     ) -> Result<Prompt> {
-        let path = format!("/v0/evi/prompts/{}/versions/{}", prompt_id, version);
-        self.client.http.get(&path, options).await
+        Ok(self.get_version_with_response(prompt_id, version, options).await?.data)
+    }
+
+    /// Get a specific prompt version, returning response metadata with the result
+    pub async fn get_version_with_response(
+        &self,
+        prompt_id: &str,
+        version: u32,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Prompt>> {
+        let path = Endpoints::path(&["v0", "evi", "prompts", prompt_id, "versions", &version.to_string()]);
+        self.client.http.get_with_response(&path, options).await
     }
 
     /// Create a new version of a prompt
@@ -118,8 +207,18 @@ impl PromptsClient {
         request: CreatePromptVersionRequest,
         options: Option<RequestOptions>,
     ) -> Result<Prompt> {
-        let path = format!("/v0/evi/prompts/{}/versions", prompt_id);
-        self.client.http.post(&path, request, options).await
+        Ok(self.create_version_with_response(prompt_id, request, options).await?.data)
+    }
+
+    /// Create a new version of a prompt, returning response metadata with the result
+    pub async fn create_version_with_response(
+        &self,
+        prompt_id: &str,
+        request: CreatePromptVersionRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Prompt>> {
+        let path = Endpoints::path(&["v0", "evi", "prompts", prompt_id, "versions"]);
+        self.client.http.create_with_response(&path, request, options).await
     }
 }
 
@@ -1,7 +1,10 @@
 //! Tools management client for EVI
 
 use crate::{
-    core::{client::HumeClient, error::Result, request::RequestOptions},
+    core::{
+        client::HumeClient, endpoints::Endpoints, error::Result, pagination::Paginator,
+        request::RequestOptions, response::WithMeta,
+    },
     evi::models::{ReturnPagedUserDefinedTools, Tool},
 };
 use serde::Serialize;
@@ -26,6 +29,16 @@ impl ToolsClient {
         page_size: Option<u32>,
         options: Option<RequestOptions>,
     ) -> Result<ReturnPagedUserDefinedTools> {
+        Ok(self.list_with_response(page_number, page_size, options).await?.data)
+    }
+
+    /// List all tools, returning response metadata with the result
+    pub async fn list_with_response(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<ReturnPagedUserDefinedTools>> {
         let mut req_options = options.unwrap_or_default();
         
         if let Some(page) = page_number {
@@ -38,26 +51,58 @@ impl ToolsClient {
 
         self.client
             .http
-            .get("/v0/evi/tools", Some(req_options))
+            .get_with_response("/v0/evi/tools", Some(req_options))
             .await
     }
 
+    /// Page through all tools
+    pub fn paginate(&self, options: Option<RequestOptions>) -> Paginator<ReturnPagedUserDefinedTools> {
+        let client = self.clone();
+        Paginator::new(move |page| {
+            let client = client.clone();
+            let options = options.clone();
+            async move {
+                client
+                    .list(Some(page.page_number), Some(page.page_size), options)
+                    .await
+            }
+        })
+    }
+
     /// Create a new tool
     pub async fn create(
         &self,
         request: CreateToolRequest,
         options: Option<RequestOptions>,
     ) -> Result<Tool> {
+        Ok(self.create_with_response(request, options).await?.data)
+    }
+
+    /// Create a new tool, returning response metadata with the result
+    pub async fn create_with_response(
+        &self,
+        request: CreateToolRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Tool>> {
         self.client
             .http
-            .post("/v0/evi/tools", request, options)
+            .create_with_response("/v0/evi/tools", request, options)
             .await
     }
 
     /// Get a specific tool
     pub async fn get(&self, tool_id: &str, options: Option<RequestOptions>) -> Result<Tool> {
-        let path = format!("/v0/evi/tools/{}", tool_id);
-        self.client.http.get(&path, options).await
+        Ok(self.get_with_response(tool_id, options).await?.data)
+    }
+
+    /// Get a specific tool, returning response metadata with the result
+    pub async fn get_with_response(
+        &self,
+        tool_id: &str,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Tool>> {
+        let path = Endpoints::path(&["v0", "evi", "tools", tool_id]);
+        self.client.http.get_with_response(&path, options).await
     }
 
     /// Update a tool
@@ -67,17 +112,40 @@ impl ToolsClient {
         request: UpdateToolRequest,
         options: Option<RequestOptions>,
     ) -> Result<Tool> {
-        let path = format!("/v0/evi/tools/{}", tool_id);
-        self.client.http.patch(&path, request, options).await
+        Ok(self.update_with_response(tool_id, request, options).await?.data)
+    }
+
+    /// Update a tool, returning response metadata with the result
+    pub async fn update_with_response(
+        &self,
+        tool_id: &str,
+        request: UpdateToolRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Tool>> {
+        let path = Endpoints::path(&["v0", "evi", "tools", tool_id]);
+        self.client.http.patch_with_response(&path, request, options).await
     }
 
     /// Delete a tool
     pub async fn delete(&self, tool_id: &str, options: Option<RequestOptions>) -> Result<()> {
-        let path = format!("/v0/evi/tools/{}", tool_id);
-        let _: serde_json::Value = self.client.http.delete(&path, options).await?;
+        self.delete_with_response(tool_id, options).await?;
         Ok(())
     }
 
+    /// Delete a tool, returning response metadata with the result
+    pub async fn delete_with_response(
+        &self,
+        tool_id: &str,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<()>> {
+        let path = Endpoints::path(&["v0", "evi", "tools", tool_id]);
+        let response: WithMeta<serde_json::Value> = self
+            .client
+            .http
+            .delete_with_response(&path, options).await?;
+        Ok(response.map(|_| ()))
+    }
+
     /// List tool versions
     pub async fn list_versions(
         &self,
@@ -86,7 +154,18 @@ impl ToolsClient {
         page_size: Option<u32>,
         options: Option<RequestOptions>,
     ) -> Result<ReturnPagedUserDefinedTools> {
-        let path = format!("/v0/evi/tools/{}/versions", tool_id);
+        Ok(self.list_versions_with_response(tool_id, page_number, page_size, options).await?.data)
+    }
+
+    /// List tool versions, returning response metadata with the result
+    pub async fn list_versions_with_response(
+        &self,
+        tool_id: &str,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<ReturnPagedUserDefinedTools>> {
+        let path = Endpoints::path(&["v0", "evi", "tools", tool_id, "versions"]);
         let mut req_options = options.unwrap_or_default();
         
         if let Some(page) = page_number {
@@ -97,7 +176,7 @@ impl ToolsClient {
             req_options = req_options.with_query("page_size", size.to_string());
         }
 
-        self.client.http.get(&path, Some(req_options)).await
+        self.client.http.get_with_response(&path, Some(req_options)).await
     }
 
     /// Get a specific tool version
@@ -107,8 +186,18 @@ impl ToolsClient {
         version_id: &str,
         options: Option<RequestOptions>,
     ) -> Result<Tool> {
-        let path = format!("/v0/evi/tools/{}/versions/{}", tool_id, version_id);
-        self.client.http.get(&path, options).await
+        Ok(self.get_version_with_response(tool_id, version_id, options).await?.data)
+    }
+
+    /// Get a specific tool version, returning response metadata with the result
+    pub async fn get_version_with_response(
+        &self,
+        tool_id: &str,
+        version_id: &str,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Tool>> {
+        let path = Endpoints::path(&["v0", "evi", "tools", tool_id, "versions", version_id]);
+        self.client.http.get_with_response(&path, options).await
     }
 }
 
@@ -1,7 +1,10 @@
 //! Custom voices management client for EVI
 
 use crate::{
-    core::{client::HumeClient, error::Result, request::RequestOptions},
+    core::{
+        client::HumeClient, endpoints::Endpoints, error::Result, pagination::Paginator,
+        request::RequestOptions, response::WithMeta,
+    },
     evi::models::{CustomVoice, ReturnPagedCustomVoices, VoiceParameters},
 };
 use serde::Serialize;
@@ -26,6 +29,16 @@ impl VoicesClient {
         page_size: Option<u32>,
         options: Option<RequestOptions>,
     ) -> Result<ReturnPagedCustomVoices> {
+        Ok(self.list_with_response(page_number, page_size, options).await?.data)
+    }
+
+    /// List all custom voices, returning response metadata with the result
+    pub async fn list_with_response(
+        &self,
+        page_number: Option<u32>,
+        page_size: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<ReturnPagedCustomVoices>> {
         let mut req_options = options.unwrap_or_default();
         
         if let Some(page) = page_number {
@@ -38,26 +51,62 @@ impl VoicesClient {
 
         self.client
             .http
-            .get("/v0/evi/custom_voices", Some(req_options))
+            .get_with_response("/v0/evi/custom_voices", Some(req_options))
             .await
     }
 
+    /// Page through all custom voices
+    pub fn paginate(&self, options: Option<RequestOptions>) -> Paginator<ReturnPagedCustomVoices> {
+        let client = self.clone();
+        Paginator::new(move |page| {
+            let client = client.clone();
+            let options = options.clone();
+            async move {
+                client
+                    .list(Some(page.page_number), Some(page.page_size), options)
+                    .await
+            }
+        })
+    }
+
     /// Create a new custom voice
     pub async fn create(
         &self,
         request: CreateCustomVoiceRequest,
         options: Option<RequestOptions>,
     ) -> Result<CustomVoice> {
+        Ok(self.create_with_response(request, options).await?.data)
+    }
+
+    /// Create a new custom voice, returning response metadata with the result
+    pub async fn create_with_response(
+        &self,
+        request: CreateCustomVoiceRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<CustomVoice>> {
         self.client
             .http
-            .post("/v0/evi/custom_voices", request, options)
+            .create_with_response("/v0/evi/custom_voices", request, options)
             .await
     }
 
     /// Get a specific custom voice
-    pub async fn get(&self, voice_id: &str, options: Option<RequestOptions>) -> Result<CustomVoice> {
-        let path = format!("/v0/evi/custom_voices/{}", voice_id);
-        self.client.http.get(&path, options).await
+    pub async fn get(
+        &self,
+        voice_id: &str,
+        options: Option<RequestOptions>,
+    ) -> Result<CustomVoice> {
+        Ok(self.get_with_response(voice_id, options).await?.data)
+    }
+
+    /// Get a specific custom voice, returning response metadata with the result
+    pub async fn get_with_response(
+        &self,
+        voice_id: &str,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<CustomVoice>> {
+        let path = Endpoints::path(&["v0", "evi", "custom_voices", voice_id]);
+        self.client.http.get_with_response(&path, options).await
     }
 
     /// Update a custom voice
@@ -67,16 +116,39 @@ impl VoicesClient {
         request: UpdateCustomVoiceRequest,
         options: Option<RequestOptions>,
     ) -> Result<CustomVoice> {
-        let path = format!("/v0/evi/custom_voices/{}", voice_id);
-        self.client.http.patch(&path, request, options).await
+        Ok(self.update_with_response(voice_id, request, options).await?.data)
+    }
+
+    /// Update a custom voice, returning response metadata with the result
+    pub async fn update_with_response(
+        &self,
+        voice_id: &str,
+        request: UpdateCustomVoiceRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<CustomVoice>> {
+        let path = Endpoints::path(&["v0", "evi", "custom_voices", voice_id]);
+        self.client.http.patch_with_response(&path, request, options).await
     }
 
     /// Delete a custom voice
     pub async fn delete(&self, voice_id: &str, options: Option<RequestOptions>) -> Result<()> {
-        let path = format!("/v0/evi/custom_voices/{}", voice_id);
-        let _: serde_json::Value = self.client.http.delete(&path, options).await?;
+        self.delete_with_response(voice_id, options).await?;
         Ok(())
     }
+
+    /// Delete a custom voice, returning response metadata with the result
+    pub async fn delete_with_response(
+        &self,
+        voice_id: &str,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<()>> {
+        let path = Endpoints::path(&["v0", "evi", "custom_voices", voice_id]);
+        let response: WithMeta<serde_json::Value> = self
+            .client
+            .http
+            .delete_with_response(&path, options).await?;
+        Ok(response.map(|_| ()))
+    }
 }
 
 /// Request to create a new custom voice
@@ -1,7 +1,11 @@
 //! Batch processing client for Expression Measurement API
 
 use crate::{
-    core::{client::HumeClient, error::Result, request::RequestOptions},
+    core::{
+        cancel::cancellable, client::HumeClient, endpoints::Endpoints, error::Result,
+        pagination::Paginator, request::RequestOptions,
+        response::WithMeta,
+    },
     expression_measurement::models::*,
 };
 use std::sync::Arc;
@@ -25,6 +29,16 @@ impl BatchClient {
         offset: Option<u32>,
         options: Option<RequestOptions>,
     ) -> Result<ListJobsResponse> {
+        Ok(self.list_jobs_with_response(limit, offset, options).await?.data)
+    }
+
+    /// List batch jobs, returning response metadata with the result
+    pub async fn list_jobs_with_response(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<ListJobsResponse>> {
         let mut req_options = options.unwrap_or_default();
         
         if let Some(limit) = limit {
@@ -37,23 +51,50 @@ impl BatchClient {
 
         self.client
             .http
-            .get("/v0/batch/jobs", Some(req_options))
+            .get_with_response("/v0/batch/jobs", Some(req_options))
             .await
     }
 
+    /// Page through all batch jobs
+    pub fn paginate_jobs(&self, options: Option<RequestOptions>) -> Paginator<ListJobsResponse> {
+        let client = self.clone();
+        Paginator::new(move |page| {
+            let client = client.clone();
+            let options = options.clone();
+            async move {
+                client
+                    .list_jobs(Some(page.page_size), Some(page.offset()), options)
+                    .await
+            }
+        })
+    }
+
     /// Create a new batch job
     pub async fn create_job(
         &self,
         request: BatchJobRequest,
         options: Option<RequestOptions>,
     ) -> Result<BatchJob> {
-        let job_id_response: JobId = self.client
+        Ok(self.create_job_with_response(request, options).await?.data)
+    }
+
+    /// Create a new batch job, returning response metadata with the result
+    ///
+    /// The metadata is that of the create request; the job itself is fetched
+    /// with a follow-up request.
+    pub async fn create_job_with_response(
+        &self,
+        request: BatchJobRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<BatchJob>> {
+        let job_id_response: WithMeta<JobId> = self.client
             .http
-            .post("/v0/batch/jobs", request, options.clone())
+            .create_with_response("/v0/batch/jobs", request, options.clone())
             .await?;
         
         // Fetch the full job details after creation
-        self.get_job(&job_id_response.job_id, options).await
+        let job = self.get_job(&job_id_response.job_id, options).await?;
+        Ok(WithMeta::new(job, job_id_response.meta))
     }
 
     /// Get job details
@@ -62,8 +103,17 @@ impl BatchClient {
         job_id: &str,
         options: Option<RequestOptions>,
     ) -> Result<BatchJob> {
-        let path = format!("/v0/batch/jobs/{}", job_id);
-        self.client.http.get(&path, options).await
+        Ok(self.get_job_with_response(job_id, options).await?.data)
+    }
+
+    /// Get job details, returning response metadata with the result
+    pub async fn get_job_with_response(
+        &self,
+        job_id: &str,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<BatchJob>> {
+        let path = Endpoints::path(&["v0", "batch", "jobs", job_id]);
+        self.client.http.get_with_response(&path, options).await
     }
 
     /// Get job predictions
@@ -72,8 +122,17 @@ impl BatchClient {
         job_id: &str,
         options: Option<RequestOptions>,
     ) -> Result<PredictionResults> {
-        let path = format!("/v0/batch/jobs/{}/predictions", job_id);
-        self.client.http.get(&path, options).await
+        Ok(self.get_predictions_with_response(job_id, options).await?.data)
+    }
+
+    /// Get job predictions, returning response metadata with the result
+    pub async fn get_predictions_with_response(
+        &self,
+        job_id: &str,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<PredictionResults>> {
+        let path = Endpoints::path(&["v0", "batch", "jobs", job_id, "predictions"]);
+        self.client.http.get_with_response(&path, options).await
     }
 
     /// Get job artifacts
@@ -82,8 +141,17 @@ impl BatchClient {
         job_id: &str,
         options: Option<RequestOptions>,
     ) -> Result<JobArtifacts> {
-        let path = format!("/v0/batch/jobs/{}/artifacts", job_id);
-        self.client.http.get(&path, options).await
+        Ok(self.get_artifacts_with_response(job_id, options).await?.data)
+    }
+
+    /// Get job artifacts, returning response metadata with the result
+    pub async fn get_artifacts_with_response(
+        &self,
+        job_id: &str,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<JobArtifacts>> {
+        let path = Endpoints::path(&["v0", "batch", "jobs", job_id, "artifacts"]);
+        self.client.http.get_with_response(&path, options).await
     }
 
     /// Create a job from files
@@ -161,11 +229,26 @@ impl BatchClient {
         job_id: &str,
         poll_interval: std::time::Duration,
         max_wait: Option<std::time::Duration>,
+    ) -> Result<BatchJob> {
+        self.wait_for_job_completion_with_options(job_id, poll_interval, max_wait, None)
+            .await
+    }
+
+    /// Wait for a job to complete, passing options to each status request
+    ///
+    /// The options' cancellation token also stops the wait between polls.
+    pub async fn wait_for_job_completion_with_options(
+        &self,
+        job_id: &str,
+        poll_interval: std::time::Duration,
+        max_wait: Option<std::time::Duration>,
+        options: Option<RequestOptions>,
     ) -> Result<BatchJob> {
         let start = std::time::Instant::now();
+        let cancellation = options.as_ref().and_then(|options| options.cancellation.clone());
 
         loop {
-            let job = self.get_job(job_id, None).await?;
+            let job = self.get_job(job_id, options.clone()).await?;
 
             match &job.state {
                 StateInference::Completed { .. } | StateInference::Failed { .. } => return Ok(job),
@@ -176,7 +259,11 @@ impl BatchClient {
                         }
                     }
 
-                    tokio::time::sleep(poll_interval).await;
+                    cancellable(cancellation.as_ref(), async {
+                        tokio::time::sleep(poll_interval).await;
+                        Ok(())
+                    })
+                    .await?;
                 }
             }
         }
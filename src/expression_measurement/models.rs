@@ -1,5 +1,6 @@
 //! Data models for Expression Measurement API
 
+use crate::core::pagination::Page;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -235,6 +236,10 @@ pub struct BatchJob {
     /// User ID
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user_id: Option<String>,
+    
+    /// Fields not known to this SDK version; empty unless the `lenient` feature is enabled
+    #[serde(flatten, default, deserialize_with = "crate::core::response::unknown_fields")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Job state information (for other job types)
@@ -549,4 +554,20 @@ pub struct PredictionError {
 pub struct JobArtifacts {
     /// Artifact URLs by type
     pub artifacts: HashMap<String, Vec<String>>,
-}
\ No newline at end of file
+}
+
+impl Page for ListJobsResponse {
+    type Item = BatchJob;
+
+    fn item_count(&self) -> usize {
+        self.jobs.len()
+    }
+
+    fn into_items(self) -> Vec<BatchJob> {
+        self.jobs
+    }
+
+    fn total_items(&self) -> Option<u64> {
+        Some(self.total)
+    }
+}
@@ -1,16 +1,12 @@
 //! Streaming client for Expression Measurement API
 
 use crate::{
-    core::{client::HumeClient, error::Result},
+    core::{cancel::CancellationToken, client::HumeClient, error::Result, socket::WebSocket},
     expression_measurement::models::*,
 };
-use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tokio::net::TcpStream;
-use tokio_tungstenite::{
-    connect_async, tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream,
-};
+use tokio_tungstenite::tungstenite::protocol::Message;
 
 /// Client for streaming expression measurement
 #[derive(Debug, Clone)]
@@ -26,22 +22,11 @@ impl StreamClient {
 
     /// Connect to the streaming WebSocket
     pub async fn connect(&self, models: Models) -> Result<StreamSocket> {
-        let auth = self
+        let url = self
             .client
-            .http
-            .auth
-            .as_ref()
-            .ok_or_else(|| crate::core::error::Error::auth("No authentication configured"))?;
-
-        let (param_name, param_value) = auth.query_param();
-        let ws_url = format!(
-            "{}/v0/stream/models?{}={}",
-            self.client.base_url().replace("https://", "wss://"),
-            param_name,
-            param_value
-        );
-
-        let (ws_stream, _) = connect_async(&ws_url).await?;
+            .endpoints()
+            .websocket_url("/v0/stream/models", std::iter::empty::<(&str, &str)>());
+        let ws_stream = self.client.http.connect_websocket(url).await?;
 
         Ok(StreamSocket::new(ws_stream, models))
     }
@@ -50,16 +35,25 @@ impl StreamClient {
 /// WebSocket connection for streaming
 #[derive(Debug)]
 pub struct StreamSocket {
-    ws: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    ws: WebSocket,
     models: Models,
 }
 
 impl StreamSocket {
     /// Create a new stream socket
-    fn new(ws: WebSocketStream<MaybeTlsStream<TcpStream>>, models: Models) -> Self {
+    pub(crate) fn new(ws: WebSocket, models: Models) -> Self {
         Self { ws, models }
     }
 
+    /// Close the socket when `token` is cancelled
+    ///
+    /// Pending and later calls then fail with
+    /// [`Error::Cancelled`](crate::core::error::Error::Cancelled).
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.ws.set_cancellation(token);
+        self
+    }
+
     /// Send the initial configuration
     pub async fn send_config(&mut self) -> Result<()> {
         let config = StreamConfig {
@@ -110,7 +104,7 @@ impl StreamSocket {
                 Ok(Some(message))
             }
             Some(Ok(Message::Close(_))) => Ok(None),
-            Some(Err(e)) => Err(e.into()),
+            Some(Err(e)) => Err(e),
             None => Ok(None),
             _ => Ok(Some(StreamMessage::Unknown)),
         }
@@ -118,7 +112,7 @@ impl StreamSocket {
 
     /// Close the connection
     pub async fn close(mut self) -> Result<()> {
-        self.ws.close(None).await?;
+        self.ws.close().await?;
         Ok(())
     }
 }
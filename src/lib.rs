@@ -96,6 +96,9 @@
 //! - [`tts`]: Text-to-Speech API
 //! - [`expression`]: Expression Measurement API (also available as `expression_measurement`)
 //! - [`evi`]: Empathic Voice Interface API
+//! - [`api`]: Service traits implemented by the clients, for mocking; fakes are in `mock` with the `mock` feature
+//! - `blocking`: Synchronous clients backed by an internal runtime, with the `blocking` feature
+//! - `test_util`: Local fake Hume server for integration tests, with the `test-util` feature
 //!
 //! ## Examples
 //!
@@ -107,14 +110,27 @@
 //! ## Environment Variables
 //!
 //! - `HUME_API_KEY` - Your Hume API key
+//! - `HUME_SECRET_KEY` - Secret key for client-credentials auth (optional)
+//! - `HUME_AUTH` - `client_credentials` to use the secret key, defaults to `api_key` (optional)
 //! - `HUME_BASE_URL` - Custom API base URL (optional)
+//! - `HUME_PROFILE` - Profile to load from the config file (optional)
+//! - `HUME_CONFIG` - Config file path, defaults to `~/.config/hume/config.toml` (optional)
+//!
+//! See [`core::config`] for the config file format.
 
 #![warn(missing_docs)]
 #![warn(missing_debug_implementations)]
 
+pub mod api;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod core;
 pub mod evi;
 pub mod expression_measurement;
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 pub mod tts;
 
 /// Alias for expression_measurement module for convenience
@@ -123,7 +139,7 @@ pub use expression_measurement as expression;
 // Re-export main types
 pub use crate::core::{
     client::{HumeClient, HumeClientBuilder},
-    error::{Error, Result},
+    error::{Error, ErrorKind, Result},
 };
 
 pub use crate::evi::EviClient;
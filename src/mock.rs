@@ -0,0 +1,491 @@
+//! In-memory fakes for the [`api`](crate::api) traits
+//!
+//! Available with the `mock` feature. Each fake answers a method with the
+//! handler set through its `on_*` method and records the arguments of every
+//! call, readable through the matching `*_calls` method. Calling a method
+//! without a handler fails with [`Error::Other`] naming the method. Fakes are
+//! cheap to clone and clones share handlers and recorded calls, so a test can
+//! keep one handle while the code under test owns another.
+//!
+//! ```no_run
+//! use hume::api::EviToolsApi;
+//! use hume::core::error::Error;
+//! use hume::mock::MockEviTools;
+//!
+//! # async fn example() -> hume::Result<()> {
+//! let tools = MockEviTools::new()
+//!     .on_get(|tool_id, _options| Err(Error::api(404, format!("{} not found", tool_id), None, None)));
+//!
+//! assert!(tools.get("tool-1", None).await.is_err());
+//! assert_eq!(tools.get_calls()[0].0, "tool-1");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! WebSocket sessions are scripted with [`ScriptedSession`]:
+//!
+//! ```no_run
+//! use hume::api::EviChatApi;
+//! use hume::mock::{MockEviChat, ScriptedSession};
+//!
+//! # async fn example() -> hume::Result<()> {
+//! let session = ScriptedSession::new()
+//!     .receive(r#"{"type":"user_message","message_id":"m1","text":"Hello"}"#);
+//! let chat = MockEviChat::new().on_connect({
+//!     let session = session.clone();
+//!     move |_, _, _| Ok(session.chat_socket())
+//! });
+//!
+//! let mut socket = chat.connect(None, None, None).await?;
+//! socket.send_text("Hello".to_string()).await?;
+//! let message = socket.receive().await?;
+//! assert_eq!(session.sent().len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    api::*,
+    core::{
+        error::{Error, Result},
+        request::RequestOptions,
+        socket::WebSocket,
+    },
+    evi::{
+        chat::ChatSocket,
+        configs::{CreateConfigRequest, UpdateConfigRequest},
+        models::*,
+        prompts::{CreatePromptRequest, CreatePromptVersionRequest, UpdatePromptRequest},
+        tools::{CreateToolRequest, UpdateToolRequest},
+        voices::{CreateCustomVoiceRequest, UpdateCustomVoiceRequest},
+    },
+    expression_measurement::{
+        models::{BatchJob, BatchJobRequest, JobArtifacts, ListJobsResponse, Models, PredictionResults},
+        stream::StreamSocket,
+    },
+    tts::models::{TtsRequest, TtsResponse, TtsStreamRequest, TtsStreamResponse, VoicesResponse},
+};
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+type Handler<A, R> = Box<dyn Fn(A) -> Result<R> + Send + Sync>;
+
+/// Handler and recorded calls for one fake method
+struct Stub<A, R> {
+    handler: Mutex<Option<Handler<A, R>>>,
+    calls: Mutex<Vec<A>>,
+}
+
+impl<A: Clone, R> Stub<A, R> {
+    fn set(&self, handler: impl Fn(A) -> Result<R> + Send + Sync + 'static) {
+        *self.handler.lock().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(handler));
+    }
+
+    fn call(&self, method: &str, args: A) -> Result<R> {
+        self.calls.lock().unwrap_or_else(|e| e.into_inner()).push(args.clone());
+        match &*self.handler.lock().unwrap_or_else(|e| e.into_inner()) {
+            Some(handler) => handler(args),
+            None => Err(Error::other(format!("{} has no handler configured", method))),
+        }
+    }
+
+    fn calls(&self) -> Vec<A> {
+        self.calls.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+impl<A, R> Default for Stub<A, R> {
+    fn default() -> Self {
+        Self {
+            handler: Mutex::new(None),
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+/// Define a fake implementing an API trait
+///
+/// Each method lists its parameters as `name: Type => OwnedType`, where the
+/// owned type is what handlers receive and calls are recorded as.
+macro_rules! mock_api {
+    (
+        $(#[$meta:meta])*
+        $name:ident: $api:ident {
+            $(
+                fn $method:ident, $on:ident, $calls:ident($($arg:ident: $ty:ty => $owned:ty),+) -> $ret:ty;
+            )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Default)]
+        pub struct $name {
+            $($method: Arc<Stub<($($owned,)+), $ret>>,)+
+        }
+
+        impl $name {
+            /// Create a fake with no handlers
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            $(
+                #[doc = concat!("Answer `", stringify!($method), "` calls with `handler`")]
+                pub fn $on(self, handler: impl Fn($($owned),+) -> Result<$ret> + Send + Sync + 'static) -> Self {
+                    self.$method.set(move |($($arg,)+)| handler($($arg),+));
+                    self
+                }
+
+                #[doc = concat!("Arguments of every `", stringify!($method), "` call so far")]
+                pub fn $calls(&self) -> Vec<($($owned,)+)> {
+                    self.$method.calls()
+                }
+            )+
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name)).finish_non_exhaustive()
+            }
+        }
+
+        #[async_trait]
+        impl $api for $name {
+            $(
+                async fn $method(&self, $($arg: $ty),+) -> Result<$ret> {
+                    let method = concat!(stringify!($name), "::", stringify!($method));
+                    self.$method.call(method, ($(<$owned>::from($arg),)+))
+                }
+            )+
+        }
+    };
+}
+
+mock_api! {
+    /// Fake [`TtsApi`]
+    MockTts: TtsApi {
+        fn synthesize, on_synthesize, synthesize_calls(
+            request: TtsRequest => TtsRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> TtsResponse;
+        fn synthesize_file, on_synthesize_file, synthesize_file_calls(
+            request: TtsRequest => TtsRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Bytes;
+        fn stream_json, on_stream_json, stream_json_calls(
+            request: TtsStreamRequest => TtsStreamRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ApiStream<TtsStreamResponse>;
+        fn stream_file, on_stream_file, stream_file_calls(
+            request: TtsStreamRequest => TtsStreamRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ApiStream<Bytes>;
+        fn list_voices, on_list_voices, list_voices_calls(
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> VoicesResponse;
+    }
+}
+
+mock_api! {
+    /// Fake [`BatchApi`]
+    MockBatch: BatchApi {
+        fn list_jobs, on_list_jobs, list_jobs_calls(
+            limit: Option<u32> => Option<u32>,
+            offset: Option<u32> => Option<u32>,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ListJobsResponse;
+        fn create_job, on_create_job, create_job_calls(
+            request: BatchJobRequest => BatchJobRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> BatchJob;
+        fn get_job, on_get_job, get_job_calls(
+            job_id: &str => String,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> BatchJob;
+        fn get_predictions, on_get_predictions, get_predictions_calls(
+            job_id: &str => String,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> PredictionResults;
+        fn get_artifacts, on_get_artifacts, get_artifacts_calls(
+            job_id: &str => String,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> JobArtifacts;
+    }
+}
+
+mock_api! {
+    /// Fake [`ExpressionStreamApi`], typically answering with a [`ScriptedSession`]
+    MockExpressionStream: ExpressionStreamApi {
+        fn connect, on_connect, connect_calls(models: Models => Models) -> StreamSocket;
+    }
+}
+
+mock_api! {
+    /// Fake [`EviChatApi`], typically answering `connect` with a [`ScriptedSession`]
+    MockEviChat: EviChatApi {
+        fn connect, on_connect, connect_calls(
+            config_id: Option<String> => Option<String>,
+            config_version: Option<u32> => Option<u32>,
+            resumed_chat_group_id: Option<String> => Option<String>
+        ) -> ChatSocket;
+        fn list_chats, on_list_chats, list_chats_calls(
+            page_number: Option<u32> => Option<u32>,
+            page_size: Option<u32> => Option<u32>,
+            ascending_order: Option<bool> => Option<bool>
+        ) -> ReturnPagedChats;
+        fn get_chat, on_get_chat, get_chat_calls(chat_id: &str => String) -> Chat;
+        fn list_chat_groups, on_list_chat_groups, list_chat_groups_calls(
+            page_number: Option<u32> => Option<u32>,
+            page_size: Option<u32> => Option<u32>,
+            ascending_order: Option<bool> => Option<bool>
+        ) -> ReturnPagedChatGroups;
+        fn get_chat_messages, on_get_chat_messages, get_chat_messages_calls(
+            chat_id: &str => String,
+            page_number: Option<u32> => Option<u32>,
+            page_size: Option<u32> => Option<u32>
+        ) -> PagedResponse<ChatMessage>;
+    }
+}
+
+mock_api! {
+    /// Fake [`EviConfigsApi`]
+    MockEviConfigs: EviConfigsApi {
+        fn list, on_list, list_calls(
+            page_number: Option<u32> => Option<u32>,
+            page_size: Option<u32> => Option<u32>,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ReturnPagedConfigs;
+        fn create, on_create, create_calls(
+            request: CreateConfigRequest => CreateConfigRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Config;
+        fn get, on_get, get_calls(
+            config_id: &str => String,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Config;
+        fn update, on_update, update_calls(
+            config_id: &str => String,
+            request: UpdateConfigRequest => UpdateConfigRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Config;
+        fn delete, on_delete, delete_calls(
+            config_id: &str => String,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ();
+        fn list_versions, on_list_versions, list_versions_calls(
+            config_id: &str => String,
+            page_number: Option<u32> => Option<u32>,
+            page_size: Option<u32> => Option<u32>,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ReturnPagedConfigs;
+        fn get_version, on_get_version, get_version_calls(
+            config_id: &str => String,
+            version: u32 => u32,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Config;
+    }
+}
+
+mock_api! {
+    /// Fake [`EviToolsApi`]
+    MockEviTools: EviToolsApi {
+        fn list, on_list, list_calls(
+            page_number: Option<u32> => Option<u32>,
+            page_size: Option<u32> => Option<u32>,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ReturnPagedUserDefinedTools;
+        fn create, on_create, create_calls(
+            request: CreateToolRequest => CreateToolRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Tool;
+        fn get, on_get, get_calls(
+            tool_id: &str => String,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Tool;
+        fn update, on_update, update_calls(
+            tool_id: &str => String,
+            request: UpdateToolRequest => UpdateToolRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Tool;
+        fn delete, on_delete, delete_calls(
+            tool_id: &str => String,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ();
+        fn list_versions, on_list_versions, list_versions_calls(
+            tool_id: &str => String,
+            page_number: Option<u32> => Option<u32>,
+            page_size: Option<u32> => Option<u32>,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ReturnPagedUserDefinedTools;
+        fn get_version, on_get_version, get_version_calls(
+            tool_id: &str => String,
+            version_id: &str => String,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Tool;
+    }
+}
+
+mock_api! {
+    /// Fake [`EviPromptsApi`]
+    MockEviPrompts: EviPromptsApi {
+        fn list, on_list, list_calls(
+            page_number: Option<u32> => Option<u32>,
+            page_size: Option<u32> => Option<u32>,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ReturnPagedPrompts;
+        fn create, on_create, create_calls(
+            request: CreatePromptRequest => CreatePromptRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Prompt;
+        fn get, on_get, get_calls(
+            prompt_id: &str => String,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Prompt;
+        fn update, on_update, update_calls(
+            prompt_id: &str => String,
+            request: UpdatePromptRequest => UpdatePromptRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Prompt;
+        fn delete, on_delete, delete_calls(
+            prompt_id: &str => String,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ();
+        fn list_versions, on_list_versions, list_versions_calls(
+            prompt_id: &str => String,
+            page_number: Option<u32> => Option<u32>,
+            page_size: Option<u32> => Option<u32>,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ReturnPagedPrompts;
+        fn get_version, on_get_version, get_version_calls(
+            prompt_id: &str => String,
+            version: u32 => u32,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Prompt;
+        fn create_version, on_create_version, create_version_calls(
+            prompt_id: &str => String,
+            request: CreatePromptVersionRequest => CreatePromptVersionRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> Prompt;
+    }
+}
+
+mock_api! {
+    /// Fake [`EviVoicesApi`]
+    MockEviVoices: EviVoicesApi {
+        fn list, on_list, list_calls(
+            page_number: Option<u32> => Option<u32>,
+            page_size: Option<u32> => Option<u32>,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ReturnPagedCustomVoices;
+        fn create, on_create, create_calls(
+            request: CreateCustomVoiceRequest => CreateCustomVoiceRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> CustomVoice;
+        fn get, on_get, get_calls(
+            voice_id: &str => String,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> CustomVoice;
+        fn update, on_update, update_calls(
+            voice_id: &str => String,
+            request: UpdateCustomVoiceRequest => UpdateCustomVoiceRequest,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> CustomVoice;
+        fn delete, on_delete, delete_calls(
+            voice_id: &str => String,
+            options: Option<RequestOptions> => Option<RequestOptions>
+        ) -> ();
+    }
+}
+
+/// Scripted WebSocket session for [`MockEviChat`] and [`MockExpressionStream`]
+///
+/// Sockets opened from the session receive the queued messages in order,
+/// then end. Text frames they send are collected, shared by all clones of
+/// the session.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedSession {
+    received: Vec<String>,
+    sent: Arc<Mutex<Vec<String>>>,
+}
+
+impl ScriptedSession {
+    /// Create a session with no queued messages
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a message for the socket to receive, as JSON text
+    pub fn receive(mut self, message: impl Into<String>) -> Self {
+        self.received.push(message.into());
+        self
+    }
+
+    /// Queue a JSON message for the socket to receive
+    pub fn receive_json(self, message: serde_json::Value) -> Self {
+        self.receive(message.to_string())
+    }
+
+    /// Text frames sent through sockets opened from this session
+    pub fn sent(&self) -> Vec<String> {
+        self.sent.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Open an EVI chat socket on the session
+    pub fn chat_socket(&self) -> ChatSocket {
+        ChatSocket::new(self.socket())
+    }
+
+    /// Open an expression measurement stream socket on the session
+    pub fn stream_socket(&self, models: Models) -> StreamSocket {
+        StreamSocket::new(self.socket(), models)
+    }
+
+    fn socket(&self) -> WebSocket {
+        WebSocket::scripted(self.received.clone(), self.sent.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evi::chat::ServerMessage;
+
+    #[tokio::test]
+    async fn test_mock_records_calls() {
+        let voices = MockEviVoices::new().on_delete(|_, _| Ok(()));
+        let handle = voices.clone();
+
+        voices.delete("voice-1", None).await.unwrap();
+        assert_eq!(handle.delete_calls()[0].0, "voice-1");
+
+        let error = voices.get("voice-1", None).await.unwrap_err();
+        assert!(error.to_string().contains("MockEviVoices::get"));
+    }
+
+    #[tokio::test]
+    async fn test_scripted_session() {
+        let session = ScriptedSession::new().receive_json(serde_json::json!({
+            "type": "user_message",
+            "message_id": "m1",
+            "text": "Hello",
+        }));
+        let chat = MockEviChat::new().on_connect({
+            let session = session.clone();
+            move |_, _, _| Ok(session.chat_socket())
+        });
+
+        let mut socket = chat.connect(Some("config-1".into()), None, None).await.unwrap();
+        socket.send_text("Hello".into()).await.unwrap();
+        assert!(matches!(
+            socket.receive().await.unwrap(),
+            Some(ServerMessage::UserMessage { .. })
+        ));
+        assert!(socket.receive().await.unwrap().is_none());
+
+        assert_eq!(session.sent(), vec![r#"{"type":"user_input","text":"Hello"}"#]);
+        assert_eq!(chat.connect_calls()[0].0.as_deref(), Some("config-1"));
+    }
+}
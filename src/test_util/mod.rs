@@ -0,0 +1,285 @@
+//! Local fake Hume server for integration tests
+//!
+//! Available with the `test-util` feature. [`FakeHumeServer`] listens on a
+//! local port and implements the REST endpoints and WebSockets the SDK calls,
+//! so a client built with [`HumeClientBuilder::base_url`] pointing at it runs
+//! its real request, retry and decoding paths:
+//!
+//! - TTS synthesis, file and streaming endpoints return the utterance text as
+//!   the audio bytes, so tests can check what was synthesized.
+//! - Batch jobs move from `QUEUED` to `IN_PROGRESS` to `COMPLETED` by one step
+//!   each time they are fetched, or can be completed and failed explicitly.
+//! - EVI configs, prompts, tools and custom voices support create, get, list,
+//!   update and delete. Updates to configs, prompts and tools add a version.
+//! - EVI chat sessions are recorded as chats and chat groups, with the user
+//!   and assistant messages exchanged on the socket.
+//! - The EVI chat and expression measurement WebSockets answer with
+//!   [`SocketScript`]s, echoing user input by default.
+//!
+//! Requests must carry credentials, as the real API requires. Every request
+//! is recorded and readable through [`FakeHumeServer::requests`].
+//!
+//! ```no_run
+//! use hume::test_util::FakeHumeServer;
+//! use hume::tts::models::TtsRequestBuilder;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let server = FakeHumeServer::start().await?;
+//! let client = server.client()?;
+//!
+//! let request = TtsRequestBuilder::new().utterance("Hello")?.build();
+//! let response = client.tts().synthesize(request, None).await?;
+//! assert_eq!(server.requests()[0].path, "/v0/tts");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`HumeClientBuilder::base_url`]: crate::HumeClientBuilder::base_url
+
+mod rest;
+mod socket;
+
+pub use socket::SocketScript;
+
+use crate::{
+    core::{cancel::CancellationToken, error::Result},
+    HumeClient, HumeClientBuilder,
+};
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::{body::Incoming, server::conn::http1, service::service_fn, Request, Response};
+use hyper_util::rt::TokioIo;
+use reqwest::{header::HeaderMap, Method, StatusCode};
+use serde_json::Value;
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tokio::net::TcpListener;
+
+/// API key accepted by the server and used by [`FakeHumeServer::client`]
+pub const TEST_API_KEY: &str = "test-api-key";
+
+/// A request received by a [`FakeHumeServer`]
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    /// Request method
+    pub method: Method,
+    /// Request path, without the query
+    pub path: String,
+    /// Decoded query parameters
+    pub query: Vec<(String, String)>,
+    /// Request headers
+    pub headers: HeaderMap,
+    /// Request body
+    pub body: Bytes,
+}
+
+impl RecordedRequest {
+    /// Get the first value of a query parameter
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Decode the body as JSON, `Value::Null` if it is empty or not JSON
+    pub fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap_or(Value::Null)
+    }
+}
+
+/// Local fake of the Hume REST and WebSocket APIs
+///
+/// The server stops when dropped. See the [module docs](self) for the
+/// endpoints it implements.
+#[derive(Debug)]
+pub struct FakeHumeServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<rest::State>>,
+    shutdown: CancellationToken,
+}
+
+impl FakeHumeServer {
+    /// Start a server on a free local port
+    pub async fn start() -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(rest::State::default()));
+        let shutdown = CancellationToken::new();
+        tokio::spawn(serve(listener, state.clone(), shutdown.clone()));
+
+        Ok(Self { addr, state, shutdown })
+    }
+
+    /// Base URL to pass to [`HumeClientBuilder::base_url`]
+    ///
+    /// WebSocket URLs derived from it point at the same server.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Address the server listens on
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Build a client for this server, authenticated with [`TEST_API_KEY`]
+    pub fn client(&self) -> Result<HumeClient> {
+        self.client_builder().build()
+    }
+
+    /// Client builder pointed at this server, for further configuration
+    pub fn client_builder(&self) -> HumeClientBuilder {
+        HumeClientBuilder::new(TEST_API_KEY).base_url(self.base_url())
+    }
+
+    /// Requests received so far, oldest first, including WebSocket handshakes
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state().requests.clone()
+    }
+
+    /// Answer the next request to `path` with `status` and an error body
+    ///
+    /// Failures queue up, so calling this twice fails the next two requests
+    /// to `path`. Useful to exercise retries against the real endpoints.
+    pub fn fail_next(&self, path: &str, status: u16) {
+        self.state().failures.push((path.to_string(), status));
+    }
+
+    /// Stop batch jobs at `IN_PROGRESS` until completed or failed explicitly
+    ///
+    /// By default jobs complete on their third fetch.
+    pub fn hold_jobs(&self, hold: bool) {
+        self.state().hold_jobs = hold;
+    }
+
+    /// Complete a batch job with the given `predictions` array
+    ///
+    /// Returns `false` if the job does not exist.
+    pub fn complete_job(&self, job_id: &str, predictions: Value) -> bool {
+        self.state().complete_job(job_id, Some(predictions))
+    }
+
+    /// Fail a batch job with `message`
+    ///
+    /// Returns `false` if the job does not exist.
+    pub fn fail_job(&self, job_id: &str, message: &str) -> bool {
+        self.state().fail_job(job_id, message)
+    }
+
+    /// Script the EVI chat WebSocket for sessions opened from now on
+    pub fn script_chat(&self, script: SocketScript) {
+        self.state().chat_script = script;
+    }
+
+    /// Script the expression measurement WebSocket for sessions opened from now on
+    pub fn script_stream(&self, script: SocketScript) {
+        self.state().stream_script = script;
+    }
+
+    /// JSON messages received on EVI chat sockets, oldest first
+    pub fn chat_inputs(&self) -> Vec<Value> {
+        self.state().chat_inputs.clone()
+    }
+
+    /// JSON messages received on expression measurement sockets, oldest first
+    pub fn stream_inputs(&self) -> Vec<Value> {
+        self.state().stream_inputs.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, rest::State> {
+        lock(&self.state)
+    }
+}
+
+impl Drop for FakeHumeServer {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
+    }
+}
+
+/// Lock the server state, ignoring poisoning from a panicked handler
+fn lock(state: &Mutex<rest::State>) -> MutexGuard<'_, rest::State> {
+    state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+async fn serve(listener: TcpListener, state: Arc<Mutex<rest::State>>, shutdown: CancellationToken) {
+    loop {
+        let stream = tokio::select! {
+            _ = shutdown.cancelled() => return,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(_) => continue,
+            },
+        };
+
+        let state = state.clone();
+        let shutdown = shutdown.clone();
+        tokio::spawn(async move {
+            let service = service_fn({
+                let shutdown = shutdown.clone();
+                move |request| handle(request, state.clone(), shutdown.clone())
+            });
+            let connection = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .with_upgrades();
+            tokio::select! {
+                _ = shutdown.cancelled() => {}
+                _ = connection => {}
+            }
+        });
+    }
+}
+
+async fn handle(
+    mut request: Request<Incoming>,
+    state: Arc<Mutex<rest::State>>,
+    shutdown: CancellationToken,
+) -> std::result::Result<Response<Full<Bytes>>, Infallible> {
+    let upgrade = socket::is_upgrade(&request).then(|| hyper::upgrade::on(&mut request));
+    let (parts, body) = request.into_parts();
+    let body = body.collect().await.map(|body| body.to_bytes()).unwrap_or_default();
+    let recorded = RecordedRequest {
+        method: parts.method,
+        path: parts.uri.path().to_string(),
+        query: parts
+            .uri
+            .query()
+            .map(|query| url::form_urlencoded::parse(query.as_bytes()).into_owned().collect())
+            .unwrap_or_default(),
+        headers: parts.headers,
+        body,
+    };
+    lock(&state).requests.push(recorded.clone());
+
+    if let Some(response) = lock(&state).injected_failure(&recorded.path) {
+        return Ok(response);
+    }
+    if recorded.path != "/oauth2-cc/token" && !is_authenticated(&recorded) {
+        return Ok(rest::error(StatusCode::UNAUTHORIZED, "Missing or invalid credentials"));
+    }
+
+    let response = match upgrade {
+        Some(upgrade) => socket::accept(&recorded, upgrade, state, shutdown),
+        None => lock(&state).route(&recorded),
+    };
+    Ok(response)
+}
+
+/// Whether a request carries credentials, in headers or in the socket URL
+fn is_authenticated(request: &RecordedRequest) -> bool {
+    let header = |name: &str| {
+        request
+            .headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| !value.is_empty())
+    };
+    header("x-hume-api-key")
+        || header("authorization")
+        || request.query_param("api_key").is_some()
+        || request.query_param("access_token").is_some()
+}
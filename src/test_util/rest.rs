@@ -0,0 +1,645 @@
+//! REST endpoints and state of the fake server
+
+use super::{socket::SocketScript, RecordedRequest};
+use crate::expression_measurement::models::JobStatus;
+use base64::Engine;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use http_body_util::Full;
+use hyper::Response;
+use percent_encoding::percent_decode_str;
+use reqwest::{header::CONTENT_TYPE, Method, StatusCode};
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+/// Page size used when a list request does not set one
+const DEFAULT_PAGE_SIZE: usize = 10;
+
+/// Batch jobs listed when a list request does not set a limit
+const DEFAULT_JOB_LIMIT: usize = 50;
+
+/// Voices listed by `GET /v0/tts/voices`
+const VOICES: [(&str, &str); 2] = [
+    ("5bb7de05-c8fe-426a-8fcc-ba4fc4ce9f9c", "Ito"),
+    ("9e068547-5ba4-4c8e-8e03-69282a008f04", "Kora"),
+];
+
+/// Server state shared by the REST handlers and socket sessions
+#[derive(Debug, Default)]
+pub(super) struct State {
+    pub(super) requests: Vec<RecordedRequest>,
+    pub(super) failures: Vec<(String, u16)>,
+    pub(super) hold_jobs: bool,
+    pub(super) chat_script: SocketScript,
+    pub(super) stream_script: SocketScript,
+    pub(super) chat_inputs: Vec<Value>,
+    pub(super) stream_inputs: Vec<Value>,
+    jobs: Vec<Job>,
+    configs: Vec<Resource>,
+    prompts: Vec<Resource>,
+    tools: Vec<Resource>,
+    voices: Vec<Resource>,
+    chats: Vec<ChatRecord>,
+}
+
+/// EVI resource collections served under `/v0/evi`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Configs,
+    Prompts,
+    Tools,
+    Voices,
+}
+
+impl Kind {
+    fn from_segment(segment: &str) -> Option<Self> {
+        match segment {
+            "configs" => Some(Kind::Configs),
+            "prompts" => Some(Kind::Prompts),
+            "tools" => Some(Kind::Tools),
+            "custom_voices" => Some(Kind::Voices),
+            _ => None,
+        }
+    }
+
+    /// Field holding the items of a list response
+    fn page_field(self) -> &'static str {
+        match self {
+            Kind::Configs => "configs_page",
+            Kind::Prompts => "prompts_page",
+            Kind::Tools => "tools_page",
+            Kind::Voices => "custom_voices_page",
+        }
+    }
+
+    /// Whether updates add a version instead of replacing the resource
+    fn is_versioned(self) -> bool {
+        self != Kind::Voices
+    }
+
+    fn set_version(self, resource: &mut Map<String, Value>, version: usize) {
+        match self {
+            Kind::Configs | Kind::Prompts => {
+                resource.insert("version".to_string(), json!(version));
+            }
+            Kind::Tools => {
+                resource.insert("version_id".to_string(), json!(version.to_string()));
+            }
+            Kind::Voices => {}
+        }
+    }
+}
+
+/// An EVI resource with its versions, oldest first
+#[derive(Debug)]
+struct Resource {
+    id: String,
+    versions: Vec<Value>,
+}
+
+impl Resource {
+    fn latest(&self) -> &Value {
+        self.versions.last().expect("resources have at least one version")
+    }
+}
+
+#[derive(Debug)]
+struct Job {
+    id: String,
+    request: Value,
+    status: JobStatus,
+    created_ms: i64,
+    started_ms: Option<i64>,
+    ended_ms: Option<i64>,
+    message: Option<String>,
+    predictions: Value,
+}
+
+impl Job {
+    fn to_json(&self) -> Value {
+        let state = match self.status {
+            JobStatus::Queued => json!({
+                "status": "QUEUED",
+                "created_timestamp_ms": self.created_ms,
+            }),
+            JobStatus::InProgress => json!({
+                "status": "IN_PROGRESS",
+                "created_timestamp_ms": self.created_ms,
+                "started_timestamp_ms": self.started_ms,
+            }),
+            JobStatus::Completed => json!({
+                "status": "COMPLETED",
+                "created_timestamp_ms": self.created_ms,
+                "started_timestamp_ms": self.started_ms,
+                "ended_timestamp_ms": self.ended_ms,
+            }),
+            JobStatus::Failed => json!({
+                "status": "FAILED",
+                "created_timestamp_ms": self.created_ms,
+                "started_timestamp_ms": self.started_ms,
+                "ended_timestamp_ms": self.ended_ms,
+                "message": self.message,
+            }),
+        };
+        json!({
+            "job_id": self.id,
+            "type": "INFERENCE",
+            "request": self.request,
+            "state": state,
+        })
+    }
+
+    fn start(&mut self) {
+        self.status = JobStatus::InProgress;
+        self.started_ms.get_or_insert_with(now_ms);
+    }
+
+    fn end(&mut self, status: JobStatus) {
+        self.started_ms.get_or_insert_with(now_ms);
+        self.ended_ms = Some(now_ms());
+        self.status = status;
+    }
+
+    /// One prediction with empty results per source of the request
+    fn default_predictions(&self) -> Value {
+        let sources = self.request["sources"].as_array().cloned().unwrap_or_default();
+        sources
+            .iter()
+            .map(|source| {
+                let mut info = json!({ "type": source["type"] });
+                if let Some(url) = source.get("url") {
+                    info["url"] = url.clone();
+                }
+                if let Some(filename) = source.pointer("/file/filename") {
+                    info["filename"] = filename.clone();
+                }
+                json!({ "source": info, "results": {} })
+            })
+            .collect()
+    }
+}
+
+/// An EVI chat session and the messages exchanged in it
+#[derive(Debug)]
+struct ChatRecord {
+    id: String,
+    group_id: String,
+    config_id: String,
+    config_version: u32,
+    created_at: DateTime<Utc>,
+    ended_at: Option<DateTime<Utc>>,
+    messages: Vec<Value>,
+}
+
+impl ChatRecord {
+    fn to_json(&self) -> Value {
+        let status = if self.ended_at.is_some() { "ended" } else { "active" };
+        json!({
+            "id": self.id,
+            "chat_group_id": self.group_id,
+            "config_id": self.config_id,
+            "config_version": self.config_version,
+            "created_at": self.created_at,
+            "ended_at": self.ended_at,
+            "status": status,
+        })
+    }
+}
+
+/// A chat session opened on the EVI socket
+#[derive(Debug)]
+pub(super) struct OpenedChat {
+    pub(super) chat_id: String,
+    pub(super) chat_group_id: String,
+    pub(super) config: Value,
+}
+
+impl State {
+    /// Take the failure queued for `path` with `fail_next`, if any
+    pub(super) fn injected_failure(&mut self, path: &str) -> Option<Response<Full<Bytes>>> {
+        let index = self.failures.iter().position(|(failing, _)| failing == path)?;
+        let (_, status) = self.failures.remove(index);
+        let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        Some(error(status, "Injected failure"))
+    }
+
+    pub(super) fn complete_job(&mut self, job_id: &str, predictions: Option<Value>) -> bool {
+        let Some(job) = self.jobs.iter_mut().find(|job| job.id == job_id) else {
+            return false;
+        };
+        job.predictions = predictions.unwrap_or_else(|| job.default_predictions());
+        job.end(JobStatus::Completed);
+        true
+    }
+
+    pub(super) fn fail_job(&mut self, job_id: &str, message: &str) -> bool {
+        let Some(job) = self.jobs.iter_mut().find(|job| job.id == job_id) else {
+            return false;
+        };
+        job.message = Some(message.to_string());
+        job.end(JobStatus::Failed);
+        true
+    }
+
+    /// Record a new chat, joining the group being resumed if there is one
+    pub(super) fn open_chat(&mut self, request: &RecordedRequest) -> OpenedChat {
+        let config_id = request.query_param("config_id").map(str::to_string);
+        let config_version = request
+            .query_param("config_version")
+            .and_then(|version| version.parse::<usize>().ok());
+        let config = config_id
+            .as_deref()
+            .and_then(|id| self.configs.iter().find(|config| config.id == id))
+            .and_then(|config| match config_version {
+                Some(version) => config.versions.get(version),
+                None => Some(config.latest()),
+            })
+            .cloned()
+            .unwrap_or_else(|| {
+                json!({
+                    "id": config_id.as_deref().unwrap_or("default"),
+                    "name": "Default",
+                    "version": config_version.unwrap_or(0),
+                })
+            });
+
+        let chat_id = Uuid::new_v4().to_string();
+        let chat_group_id = request
+            .query_param("resumed_chat_group_id")
+            .map(str::to_string)
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        self.chats.push(ChatRecord {
+            id: chat_id.clone(),
+            group_id: chat_group_id.clone(),
+            config_id: config["id"].as_str().unwrap_or_default().to_string(),
+            config_version: config["version"].as_u64().unwrap_or_default() as u32,
+            created_at: Utc::now(),
+            ended_at: None,
+            messages: Vec::new(),
+        });
+
+        OpenedChat {
+            chat_id,
+            chat_group_id,
+            config,
+        }
+    }
+
+    /// Add a message to the history of a chat
+    pub(super) fn record_chat_message(&mut self, chat_id: &str, role: &str, content: &str) {
+        if let Some(chat) = self.chats.iter_mut().find(|chat| chat.id == chat_id) {
+            chat.messages.push(json!({
+                "id": Uuid::new_v4().to_string(),
+                "role": role,
+                "content": content,
+                "timestamp": Utc::now(),
+            }));
+        }
+    }
+
+    pub(super) fn close_chat(&mut self, chat_id: &str) {
+        if let Some(chat) = self.chats.iter_mut().find(|chat| chat.id == chat_id) {
+            chat.ended_at.get_or_insert_with(Utc::now);
+        }
+    }
+
+    /// Answer a REST request
+    pub(super) fn route(&mut self, request: &RecordedRequest) -> Response<Full<Bytes>> {
+        let segments: Vec<String> = request
+            .path
+            .trim_matches('/')
+            .split('/')
+            .map(|segment| percent_decode_str(segment).decode_utf8_lossy().into_owned())
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let method = &request.method;
+
+        match segments.as_slice() {
+            ["oauth2-cc", "token"] if method == Method::POST => json_response(
+                StatusCode::OK,
+                json!({
+                    "access_token": format!("fake-token-{}", Uuid::new_v4()),
+                    "token_type": "Bearer",
+                    "expires_in": 3600,
+                }),
+            ),
+            ["v0", "tts", rest @ ..] => tts(method, rest, request),
+            ["v0", "batch", "jobs", rest @ ..] => self.batch(method, rest, request),
+            ["v0", "evi", "chats", rest @ ..] => self.chats(method, rest, request),
+            ["v0", "evi", "chat_groups"] if method == Method::GET => self.chat_groups(request),
+            ["v0", "evi", kind, rest @ ..] => match Kind::from_segment(kind) {
+                Some(kind) => self.resources(kind, method, rest, request),
+                None => not_found(request),
+            },
+            _ => not_found(request),
+        }
+    }
+
+    fn batch(&mut self, method: &Method, path: &[&str], request: &RecordedRequest) -> Response<Full<Bytes>> {
+        match (method.clone(), path) {
+            (Method::POST, []) => {
+                let body = request.json();
+                if !body.is_object() {
+                    return error(StatusCode::BAD_REQUEST, "Expected a JSON job request");
+                }
+                let job_id = Uuid::new_v4().to_string();
+                self.jobs.push(Job {
+                    id: job_id.clone(),
+                    request: body,
+                    status: JobStatus::Queued,
+                    created_ms: now_ms(),
+                    started_ms: None,
+                    ended_ms: None,
+                    message: None,
+                    predictions: json!([]),
+                });
+                json_response(StatusCode::OK, json!({ "job_id": job_id }))
+            }
+            (Method::GET, []) => {
+                let limit = query_number(request, "limit").unwrap_or(DEFAULT_JOB_LIMIT);
+                let offset = query_number(request, "offset").unwrap_or(0);
+                let jobs: Vec<Value> = self.jobs.iter().skip(offset).take(limit).map(Job::to_json).collect();
+                json_response(StatusCode::OK, json!({ "jobs": jobs, "total": self.jobs.len() }))
+            }
+            (Method::GET, [job_id]) => {
+                let hold = self.hold_jobs;
+                let Some(job) = self.jobs.iter_mut().find(|job| job.id == *job_id) else {
+                    return not_found(request);
+                };
+                let body = job.to_json();
+                match job.status {
+                    JobStatus::Queued => job.start(),
+                    JobStatus::InProgress if !hold => {
+                        job.predictions = job.default_predictions();
+                        job.end(JobStatus::Completed);
+                    }
+                    _ => {}
+                }
+                json_response(StatusCode::OK, body)
+            }
+            (Method::GET, [job_id, "predictions"]) => {
+                let Some(job) = self.jobs.iter().find(|job| job.id == *job_id) else {
+                    return not_found(request);
+                };
+                match job.status {
+                    JobStatus::Completed => json_response(
+                        StatusCode::OK,
+                        json!({ "predictions": job.predictions, "errors": [] }),
+                    ),
+                    JobStatus::Failed => json_response(
+                        StatusCode::OK,
+                        json!({ "predictions": [], "errors": [{ "message": job.message }] }),
+                    ),
+                    _ => error(StatusCode::BAD_REQUEST, "Job has not finished"),
+                }
+            }
+            (Method::GET, [job_id, "artifacts"]) => match self.jobs.iter().find(|job| job.id == *job_id) {
+                Some(_) => json_response(StatusCode::OK, json!({ "artifacts": {} })),
+                None => not_found(request),
+            },
+            _ => not_found(request),
+        }
+    }
+
+    fn collection(&mut self, kind: Kind) -> &mut Vec<Resource> {
+        match kind {
+            Kind::Configs => &mut self.configs,
+            Kind::Prompts => &mut self.prompts,
+            Kind::Tools => &mut self.tools,
+            Kind::Voices => &mut self.voices,
+        }
+    }
+
+    fn resources(
+        &mut self,
+        kind: Kind,
+        method: &Method,
+        path: &[&str],
+        request: &RecordedRequest,
+    ) -> Response<Full<Bytes>> {
+        let body = request.json();
+        let collection = self.collection(kind);
+
+        match (method.clone(), path) {
+            (Method::GET, []) => {
+                let items = collection.iter().map(|resource| resource.latest().clone()).collect();
+                json_response(StatusCode::OK, paged(request, kind.page_field(), items))
+            }
+            (Method::POST, []) => {
+                let Some(fields) = body.as_object() else {
+                    return error(StatusCode::BAD_REQUEST, "Expected a JSON object");
+                };
+                let id = Uuid::new_v4().to_string();
+                let mut resource = fields.clone();
+                resource.insert("id".to_string(), json!(id));
+                kind.set_version(&mut resource, 0);
+                resource.insert("created_at".to_string(), json!(Utc::now()));
+                resource.insert("updated_at".to_string(), json!(Utc::now()));
+                let resource = Value::Object(resource);
+                collection.push(Resource {
+                    id,
+                    versions: vec![resource.clone()],
+                });
+                json_response(StatusCode::CREATED, resource)
+            }
+            (method, [id, rest @ ..]) => {
+                let Some(index) = collection.iter().position(|resource| resource.id == *id) else {
+                    return not_found(request);
+                };
+                let resource = &mut collection[index];
+
+                match (method, rest) {
+                    (Method::GET, []) => json_response(StatusCode::OK, resource.latest().clone()),
+                    (Method::DELETE, []) => {
+                        collection.remove(index);
+                        json_response(StatusCode::OK, json!({}))
+                    }
+                    (Method::PATCH, []) | (Method::POST, ["versions"]) if kind.is_versioned() || rest.is_empty() => {
+                        let Some(fields) = body.as_object() else {
+                            return error(StatusCode::BAD_REQUEST, "Expected a JSON object");
+                        };
+                        let mut updated = resource.latest().as_object().cloned().unwrap_or_default();
+                        for (key, value) in fields.iter().filter(|(_, value)| !value.is_null()) {
+                            updated.insert(key.clone(), value.clone());
+                        }
+                        updated.insert("updated_at".to_string(), json!(Utc::now()));
+                        if kind.is_versioned() {
+                            kind.set_version(&mut updated, resource.versions.len());
+                            resource.versions.push(Value::Object(updated));
+                        } else {
+                            resource.versions = vec![Value::Object(updated)];
+                        }
+                        json_response(StatusCode::OK, resource.latest().clone())
+                    }
+                    (Method::GET, ["versions"]) if kind.is_versioned() => {
+                        json_response(StatusCode::OK, paged(request, kind.page_field(), resource.versions.clone()))
+                    }
+                    (Method::GET, ["versions", version]) if kind.is_versioned() => {
+                        match version.parse::<usize>().ok().and_then(|version| resource.versions.get(version)) {
+                            Some(version) => json_response(StatusCode::OK, version.clone()),
+                            None => not_found(request),
+                        }
+                    }
+                    _ => not_found(request),
+                }
+            }
+            _ => not_found(request),
+        }
+    }
+
+    fn chats(&mut self, method: &Method, path: &[&str], request: &RecordedRequest) -> Response<Full<Bytes>> {
+        if method != Method::GET {
+            return not_found(request);
+        }
+        match path {
+            [] => {
+                let ascending = request.query_param("ascending_order") != Some("false");
+                let mut chats: Vec<Value> = self.chats.iter().map(ChatRecord::to_json).collect();
+                if !ascending {
+                    chats.reverse();
+                }
+                let mut page = paged(request, "chats_page", chats);
+                page["pagination_direction"] = json!(if ascending { "ASC" } else { "DESC" });
+                json_response(StatusCode::OK, page)
+            }
+            [chat_id] => match self.chats.iter().find(|chat| chat.id == *chat_id) {
+                Some(chat) => json_response(StatusCode::OK, chat.to_json()),
+                None => not_found(request),
+            },
+            [chat_id, "messages"] => match self.chats.iter().find(|chat| chat.id == *chat_id) {
+                Some(chat) => json_response(StatusCode::OK, paged(request, "items", chat.messages.clone())),
+                None => not_found(request),
+            },
+            _ => not_found(request),
+        }
+    }
+
+    fn chat_groups(&self, request: &RecordedRequest) -> Response<Full<Bytes>> {
+        let mut groups: Vec<&str> = Vec::new();
+        for chat in &self.chats {
+            if !groups.contains(&chat.group_id.as_str()) {
+                groups.push(&chat.group_id);
+            }
+        }
+
+        let ascending = request.query_param("ascending_order") != Some("false");
+        let mut groups: Vec<Value> = groups
+            .into_iter()
+            .map(|group_id| {
+                let chats: Vec<&ChatRecord> = self.chats.iter().filter(|chat| chat.group_id == group_id).collect();
+                let first = chats[0];
+                let last = chats[chats.len() - 1];
+                json!({
+                    "id": group_id,
+                    "first_chat_id": first.id,
+                    "most_recent_chat_id": last.id,
+                    "num_chats": chats.len(),
+                    "is_active": last.ended_at.is_none(),
+                    "created_at": first.created_at,
+                    "updated_at": last.ended_at.unwrap_or(last.created_at),
+                })
+            })
+            .collect();
+        if !ascending {
+            groups.reverse();
+        }
+        let mut page = paged(request, "chat_groups_page", groups);
+        page["pagination_direction"] = json!(if ascending { "ASC" } else { "DESC" });
+        json_response(StatusCode::OK, page)
+    }
+}
+
+/// TTS endpoints, synthesizing the request text as the audio bytes
+fn tts(method: &Method, path: &[&str], request: &RecordedRequest) -> Response<Full<Bytes>> {
+    let text = synthesized_text(&request.json());
+    let encode = |text: &str| base64::engine::general_purpose::STANDARD.encode(text);
+
+    match (method.clone(), path) {
+        (Method::POST, []) => {
+            let utterances = request.json()["utterances"].as_array().cloned().unwrap_or_default();
+            let generations: Vec<Value> = utterances
+                .iter()
+                .map(|utterance| {
+                    json!({
+                        "generation_id": Uuid::new_v4().to_string(),
+                        "data": encode(utterance["text"].as_str().unwrap_or_default()),
+                    })
+                })
+                .collect();
+            json_response(StatusCode::OK, json!({ "generations": generations }))
+        }
+        (Method::POST, ["file"]) | (Method::POST, ["stream", "file"]) => Response::builder()
+            .header(CONTENT_TYPE, "audio/mpeg")
+            .body(Full::new(Bytes::from(text)))
+            .expect("valid response"),
+        (Method::POST, ["stream", "json"]) => json_response(
+            StatusCode::OK,
+            json!({ "index": 0, "data": encode(&text), "is_final": true }),
+        ),
+        (Method::GET, ["voices"]) => {
+            let voices: Vec<Value> = VOICES
+                .iter()
+                .map(|(id, name)| json!({ "id": id, "name": name, "is_custom": false }))
+                .collect();
+            json_response(StatusCode::OK, json!({ "voices": voices }))
+        }
+        _ => not_found(request),
+    }
+}
+
+/// Text of a TTS request: its utterances joined by spaces, or its `text`
+fn synthesized_text(body: &Value) -> String {
+    match body["utterances"].as_array() {
+        Some(utterances) => utterances
+            .iter()
+            .filter_map(|utterance| utterance["text"].as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        None => body["text"].as_str().unwrap_or_default().to_string(),
+    }
+}
+
+/// A page of `items` in the EVI list format, from `page_number` and `page_size`
+fn paged(request: &RecordedRequest, field: &str, items: Vec<Value>) -> Value {
+    let page_number = query_number(request, "page_number").unwrap_or(0);
+    let page_size = query_number(request, "page_size").unwrap_or(DEFAULT_PAGE_SIZE).max(1);
+    let total_items = items.len();
+    let page: Vec<Value> = items.into_iter().skip(page_number * page_size).take(page_size).collect();
+
+    let mut body = json!({
+        "page_number": page_number,
+        "page_size": page_size,
+        "total_pages": total_items.div_ceil(page_size),
+        "total_items": total_items,
+    });
+    body[field] = Value::Array(page);
+    body
+}
+
+fn query_number(request: &RecordedRequest, name: &str) -> Option<usize> {
+    request.query_param(name).and_then(|value| value.parse().ok())
+}
+
+fn now_ms() -> i64 {
+    Utc::now().timestamp_millis()
+}
+
+pub(super) fn json_response(status: StatusCode, body: Value) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Full::new(Bytes::from(body.to_string())))
+        .expect("valid response")
+}
+
+/// Error response in the API's `{"message": ...}` format
+pub(super) fn error(status: StatusCode, message: &str) -> Response<Full<Bytes>> {
+    json_response(status, json!({ "message": message }))
+}
+
+fn not_found(request: &RecordedRequest) -> Response<Full<Bytes>> {
+    error(
+        StatusCode::NOT_FOUND,
+        &format!("No resource at {} {}", request.method, request.path),
+    )
+}
@@ -0,0 +1,267 @@
+//! WebSocket sessions of the fake server
+
+use super::{lock, rest, RecordedRequest};
+use crate::core::cancel::CancellationToken;
+use bytes::Bytes;
+use futures_util::{SinkExt, StreamExt};
+use http_body_util::Full;
+use hyper::{upgrade::OnUpgrade, Request, Response};
+use hyper_util::rt::TokioIo;
+use reqwest::{
+    header::{CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, UPGRADE},
+    StatusCode,
+};
+use serde_json::{json, Value};
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+use tokio_tungstenite::{
+    tungstenite::{handshake::derive_accept_key, protocol::Role, Message},
+    WebSocketStream,
+};
+use uuid::Uuid;
+
+type Reply = dyn Fn(&Value) -> Vec<Value> + Send + Sync;
+
+/// Scripted behaviour of a fake WebSocket endpoint
+///
+/// Without a [`reply`](Self::reply) function the EVI chat socket answers
+/// `user_input` with a `user_message`, an `assistant_message` echoing the
+/// text and an `assistant_end`, and `assistant_input` with an
+/// `assistant_message`. The expression measurement socket answers each data
+/// message with empty predictions.
+///
+/// ```no_run
+/// use hume::test_util::{FakeHumeServer, SocketScript};
+/// use serde_json::json;
+///
+/// # async fn example(server: FakeHumeServer) {
+/// server.script_chat(SocketScript::new().reply(|message| {
+///     if message["type"] == "user_input" {
+///         vec![json!({
+///             "type": "tool_call",
+///             "tool_call_id": "call-1",
+///             "name": "get_weather",
+///             "parameters": {"city": "Paris"},
+///         })]
+///     } else {
+///         Vec::new()
+///     }
+/// }));
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct SocketScript {
+    on_open: Vec<Value>,
+    reply: Option<Arc<Reply>>,
+}
+
+impl SocketScript {
+    /// Create a script with the default replies
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send `message` when a session opens
+    ///
+    /// On the EVI chat socket scripted messages follow `session_started`.
+    pub fn send_on_open(mut self, message: Value) -> Self {
+        self.on_open.push(message);
+        self
+    }
+
+    /// Answer each JSON message received with the messages `reply` returns
+    ///
+    /// Replaces the default replies.
+    pub fn reply(mut self, reply: impl Fn(&Value) -> Vec<Value> + Send + Sync + 'static) -> Self {
+        self.reply = Some(Arc::new(reply));
+        self
+    }
+}
+
+impl fmt::Debug for SocketScript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SocketScript")
+            .field("on_open", &self.on_open)
+            .field("reply", &self.reply.as_ref().map(|_| "Fn"))
+            .finish()
+    }
+}
+
+/// WebSocket endpoints of the fake server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Endpoint {
+    Chat,
+    Stream,
+}
+
+impl Endpoint {
+    fn from_path(path: &str) -> Option<Self> {
+        match path {
+            "/v0/evi/chat" => Some(Endpoint::Chat),
+            "/v0/stream/models" => Some(Endpoint::Stream),
+            _ => None,
+        }
+    }
+
+    fn default_reply(self, message: &Value) -> Vec<Value> {
+        let text = message["text"].as_str().unwrap_or_default();
+        match (self, message["type"].as_str()) {
+            (Endpoint::Chat, Some("user_input")) => vec![
+                json!({ "type": "user_message", "message_id": Uuid::new_v4().to_string(), "text": text }),
+                assistant_message(&format!("You said: {}", text)),
+                json!({ "type": "assistant_end" }),
+            ],
+            (Endpoint::Chat, Some("assistant_input")) => {
+                vec![assistant_message(text), json!({ "type": "assistant_end" })]
+            }
+            (Endpoint::Stream, Some(_)) => vec![json!({ "type": "predictions", "predictions": {} })],
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn assistant_message(text: &str) -> Value {
+    json!({
+        "type": "assistant_message",
+        "message_id": Uuid::new_v4().to_string(),
+        "text": text,
+        "is_final": true,
+    })
+}
+
+/// Whether a request asks for a WebSocket upgrade
+pub(super) fn is_upgrade<B>(request: &Request<B>) -> bool {
+    request
+        .headers()
+        .get(UPGRADE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("websocket"))
+}
+
+/// Complete the handshake and run the session in the background
+pub(super) fn accept(
+    request: &RecordedRequest,
+    upgrade: OnUpgrade,
+    state: Arc<Mutex<rest::State>>,
+    shutdown: CancellationToken,
+) -> Response<Full<Bytes>> {
+    let Some(endpoint) = Endpoint::from_path(&request.path) else {
+        return rest::error(StatusCode::NOT_FOUND, &format!("No WebSocket at {}", request.path));
+    };
+    let Some(key) = request.headers.get(SEC_WEBSOCKET_KEY) else {
+        return rest::error(StatusCode::BAD_REQUEST, "Missing Sec-WebSocket-Key");
+    };
+    let accept_key = derive_accept_key(key.as_bytes());
+
+    let request = request.clone();
+    tokio::spawn(async move {
+        if let Ok(upgraded) = upgrade.await {
+            let socket = WebSocketStream::from_raw_socket(TokioIo::new(upgraded), Role::Server, None).await;
+            run(socket, endpoint, &request, &state, &shutdown).await;
+        }
+    });
+
+    Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .header(UPGRADE, "websocket")
+        .header(CONNECTION, "Upgrade")
+        .header(SEC_WEBSOCKET_ACCEPT, accept_key)
+        .body(Full::default())
+        .expect("valid response")
+}
+
+type Socket = WebSocketStream<TokioIo<hyper::upgrade::Upgraded>>;
+
+async fn run(
+    mut socket: Socket,
+    endpoint: Endpoint,
+    request: &RecordedRequest,
+    state: &Mutex<rest::State>,
+    shutdown: &CancellationToken,
+) {
+    let (script, chat) = {
+        let mut state = lock(state);
+        match endpoint {
+            Endpoint::Chat => (state.chat_script.clone(), Some(state.open_chat(request))),
+            Endpoint::Stream => (state.stream_script.clone(), None),
+        }
+    };
+    let chat_id = chat.as_ref().map(|chat| chat.chat_id.as_str());
+
+    let mut opening = Vec::new();
+    if let Some(chat) = &chat {
+        opening.push(json!({
+            "type": "session_started",
+            "session_id": Uuid::new_v4().to_string(),
+            "chat_id": chat.chat_id,
+            "chat_group_id": chat.chat_group_id,
+            "config": chat.config,
+        }));
+    }
+    opening.extend(script.on_open.iter().cloned());
+
+    if send_all(&mut socket, state, chat_id, opening).await {
+        loop {
+            let message = tokio::select! {
+                _ = shutdown.cancelled() => break,
+                message = socket.next() => message,
+            };
+            let text = match message {
+                Some(Ok(Message::Text(text))) => text,
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            };
+            let input: Value = serde_json::from_str(&text).unwrap_or(Value::String(text));
+
+            {
+                let mut state = lock(state);
+                match chat_id {
+                    Some(chat_id) => {
+                        if input["type"] == "user_input" {
+                            let text = input["text"].as_str().unwrap_or_default();
+                            state.record_chat_message(chat_id, "user", text);
+                        }
+                        state.chat_inputs.push(input.clone());
+                    }
+                    None => state.stream_inputs.push(input.clone()),
+                }
+            }
+
+            let replies = match &script.reply {
+                Some(reply) => reply(&input),
+                None => endpoint.default_reply(&input),
+            };
+            if !send_all(&mut socket, state, chat_id, replies).await {
+                break;
+            }
+        }
+    }
+
+    if let Some(chat_id) = chat_id {
+        lock(state).close_chat(chat_id);
+    }
+    let _ = socket.close(None).await;
+}
+
+/// Send `messages`, recording assistant messages in the chat history
+///
+/// Returns `false` once the client has gone away.
+async fn send_all(
+    socket: &mut Socket,
+    state: &Mutex<rest::State>,
+    chat_id: Option<&str>,
+    messages: Vec<Value>,
+) -> bool {
+    for message in messages {
+        if let (Some(chat_id), Some("assistant_message")) = (chat_id, message["type"].as_str()) {
+            let text = message["text"].as_str().unwrap_or_default();
+            lock(state).record_chat_message(chat_id, "assistant", text);
+        }
+        if socket.send(Message::Text(message.to_string())).await.is_err() {
+            return false;
+        }
+    }
+    true
+}
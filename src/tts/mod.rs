@@ -2,12 +2,20 @@
 
 pub mod models;
 
-use crate::core::{client::HumeClient, error::Result, request::RequestOptions};
+use crate::core::{
+    client::HumeClient,
+    error::Result,
+    request::RequestOptions,
+    response::{decode_json, WithMeta},
+};
 use bytes::Bytes;
 use futures_util::{Stream, StreamExt};
 use std::{pin::Pin, sync::Arc};
 
 /// Client for the Text-to-Speech API
+///
+/// Utterances and stream requests without a voice use the profile's default
+/// TTS voice, if any.
 #[derive(Debug, Clone)]
 pub struct TtsClient {
     client: Arc<HumeClient>,
@@ -25,9 +33,18 @@ impl TtsClient {
         request: models::TtsRequest,
         options: Option<RequestOptions>,
     ) -> Result<models::TtsResponse> {
+        Ok(self.synthesize_with_response(request, options).await?.data)
+    }
+
+    /// Synthesize speech from text, returning response metadata with the result
+    pub async fn synthesize_with_response(
+        &self,
+        request: models::TtsRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<models::TtsResponse>> {
         self.client
             .http
-            .post("/v0/tts", request, options)
+            .post_with_response("/v0/tts", self.with_default_voice(request), options)
             .await
     }
 
@@ -37,12 +54,21 @@ impl TtsClient {
         request: models::TtsRequest,
         options: Option<RequestOptions>,
     ) -> Result<Bytes> {
+        Ok(self.synthesize_file_with_response(request, options).await?.data)
+    }
+
+    /// Synthesize speech as raw audio bytes, returning response metadata with the result
+    pub async fn synthesize_file_with_response(
+        &self,
+        request: models::TtsRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<Bytes>> {
         self.client
             .http
-            .request_bytes(
+            .request_bytes_with_response(
                 reqwest::Method::POST,
                 "/v0/tts/file",
-                Some(request),
+                Some(self.with_default_voice(request)),
                 options,
             )
             .await
@@ -60,15 +86,13 @@ impl TtsClient {
             .request_stream(
                 reqwest::Method::POST,
                 "/v0/tts/stream/json",
-                Some(request),
+                Some(self.with_default_stream_voice(request)),
                 options,
             )
             .await?;
 
         let mapped_stream = stream.map(|result| {
-            result.and_then(|bytes| {
-                serde_json::from_slice(&bytes).map_err(crate::core::error::Error::from)
-            })
+            result.and_then(|bytes| decode_json(&bytes))
         });
 
         Ok(Box::pin(mapped_stream))
@@ -85,7 +109,7 @@ impl TtsClient {
             .request_stream(
                 reqwest::Method::POST,
                 "/v0/tts/stream/file",
-                Some(request),
+                Some(self.with_default_stream_voice(request)),
                 options,
             )
             .await
@@ -96,10 +120,20 @@ impl TtsClient {
         &self,
         options: Option<RequestOptions>,
     ) -> Result<models::VoicesResponse> {
-        self.client.http.get("/v0/tts/voices", options).await
+        Ok(self.list_voices_with_response(options).await?.data)
+    }
+
+    /// List available voices, returning response metadata with the result
+    pub async fn list_voices_with_response(
+        &self,
+        options: Option<RequestOptions>,
+    ) -> Result<WithMeta<models::VoicesResponse>> {
+        self.client.http.get_with_response("/v0/tts/voices", options).await
     }
 
     /// Convenience method to synthesize with default settings
+    ///
+    /// Without a voice name, the profile's default TTS voice is used, if any.
     pub async fn synthesize_simple(
         &self,
         text: impl Into<String>,
@@ -108,8 +142,8 @@ impl TtsClient {
         let request = models::TtsRequest {
             utterances: vec![models::Utterance {
                 text: text.into(),
-                voice: voice_name.map(|v| models::VoiceSpec::Name {
-                    name: v.into(),
+                voice: voice_name.map(|name| models::VoiceSpec::Name {
+                    name: name.into(),
                     provider: None,
                 }),
                 ..Default::default()
@@ -119,6 +153,34 @@ impl TtsClient {
 
         self.synthesize_file(request, None).await
     }
+
+    /// The profile's default voice, if any
+    fn default_voice(&self) -> Option<models::VoiceSpec> {
+        self.client
+            .defaults()
+            .tts
+            .voice
+            .clone()
+            .map(|name| models::VoiceSpec::Name { name, provider: None })
+    }
+
+    /// Give utterances without a voice the default voice
+    fn with_default_voice(&self, mut request: models::TtsRequest) -> models::TtsRequest {
+        if let Some(voice) = self.default_voice() {
+            for utterance in request.utterances.iter_mut().filter(|utterance| utterance.voice.is_none()) {
+                utterance.voice = Some(voice.clone());
+            }
+        }
+        request
+    }
+
+    /// Give a stream request without a voice the default voice
+    fn with_default_stream_voice(&self, mut request: models::TtsStreamRequest) -> models::TtsStreamRequest {
+        if request.voice.is_none() {
+            request.voice = self.default_voice();
+        }
+        request
+    }
 }
 
 impl From<HumeClient> for TtsClient {
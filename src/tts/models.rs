@@ -185,6 +185,10 @@ pub struct Generation {
     /// Voice used
     #[serde(skip_serializing_if = "Option::is_none")]
     pub voice: Option<String>,
+    
+    /// Fields not known to this SDK version; empty unless the `lenient` feature is enabled
+    #[serde(flatten, default, deserialize_with = "crate::core::response::unknown_fields")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Request for streaming TTS
@@ -288,6 +292,10 @@ pub struct Voice {
     /// Voice tags
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
+    
+    /// Fields not known to this SDK version; empty unless the `lenient` feature is enabled
+    #[serde(flatten, default, deserialize_with = "crate::core::response::unknown_fields")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Builder for TTS requests
@@ -62,7 +62,7 @@ fn test_auth_token_from_response() {
     use hume::core::auth::AccessTokenResponse;
     
     let response = AccessTokenResponse {
-        access_token: "new-token".to_string(),
+        access_token: "new-token".into(),
         token_type: "Bearer".to_string(),
         expires_in: 7200,
     };
@@ -72,4 +72,24 @@ fn test_auth_token_from_response() {
     assert_eq!(token.token_type, "Bearer");
     assert_eq!(token.expires_in, 7200);
     assert!(!token.is_expired());
-}
\ No newline at end of file
+}
+#[test]
+fn test_auth_token_expires_within() {
+    let token = AuthToken::new(
+        "test-token".to_string(),
+        "Bearer".to_string(),
+        120,
+    );
+
+    assert!(!token.expires_within(std::time::Duration::from_secs(60)));
+    assert!(token.expires_within(std::time::Duration::from_secs(180)));
+}
+
+#[test]
+fn test_auth_client_credentials() {
+    let auth = Auth::client_credentials("test-key", "test-secret");
+
+    // No token has been generated yet
+    assert!(auth.header_value().is_none());
+    assert!(!auth.is_expired());
+}
@@ -0,0 +1,72 @@
+//! Tests for the blocking clients in `hume::blocking`
+//!
+//! Run with `cargo test --features blocking,test-util`.
+
+#![cfg(all(feature = "blocking", feature = "test-util"))]
+
+use hume::evi::configs::{CreateConfigRequestBuilder, UpdateConfigRequest};
+use hume::expression_measurement::models::{Models, StateInference};
+use hume::test_util::FakeHumeServer;
+use hume::tts::models::{TtsRequestBuilder, TtsStreamRequest};
+use std::time::Duration;
+
+/// Start a fake server on a runtime of its own, as a synchronous program would
+fn start_server() -> (tokio::runtime::Runtime, FakeHumeServer) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(FakeHumeServer::start()).unwrap();
+    (runtime, server)
+}
+
+#[test]
+fn test_blocking_tts() {
+    let (_runtime, server) = start_server();
+    let client = server.client_builder().build_blocking().unwrap();
+    let tts = client.tts();
+
+    let request = TtsRequestBuilder::new().utterance("Hello").unwrap().build();
+    assert_eq!(&tts.synthesize_file(request, None).unwrap()[..], b"Hello");
+    assert_eq!(tts.list_voices(None).unwrap().voices.len(), 2);
+
+    let request = TtsStreamRequest {
+        text: "Hi".to_string(),
+        ..Default::default()
+    };
+    let chunks: Vec<_> = tts.stream_file(request, None).unwrap().collect::<Result<_, _>>().unwrap();
+    assert_eq!(chunks.concat(), b"Hi");
+}
+
+#[test]
+fn test_blocking_batch_job() {
+    let (_runtime, server) = start_server();
+    let batch = server.client_builder().build_blocking().unwrap().expression().batch();
+
+    let job = batch
+        .create_job_from_text(Models::default(), vec!["I am happy".to_string()], None, None, None)
+        .unwrap();
+    let job = batch
+        .wait_for_job_completion(&job.job_id, Duration::from_millis(1), None)
+        .unwrap();
+    assert!(matches!(job.state, StateInference::Completed { .. }));
+    assert_eq!(batch.paginate_jobs(None).count(), 1);
+}
+
+#[test]
+fn test_blocking_evi_configs() {
+    let (_runtime, server) = start_server();
+    let configs = server.client_builder().build_blocking().unwrap().evi().configs();
+
+    let config = configs
+        .create(CreateConfigRequestBuilder::new("Support").build(), None)
+        .unwrap();
+    let update = UpdateConfigRequest {
+        name: Some("Support v2".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(configs.update(&config.id, update, None).unwrap().version, 1);
+
+    let names: Vec<String> = configs.paginate(None).map(|config| config.unwrap().name).collect();
+    assert_eq!(names, ["Support v2"]);
+
+    configs.delete(&config.id, None).unwrap();
+    assert_eq!(configs.get(&config.id, None).unwrap_err().status_code(), Some(404));
+}
@@ -55,4 +55,1019 @@ fn test_client_new_shorthand() {
         .expect("Failed to create client");
     
     assert_eq!(client.base_url(), "https://api.hume.ai");
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_client_with_mock_transport() {
+    use hume::core::transport::{MockResponse, MockTransport};
+    use reqwest::Method;
+
+    let transport = MockTransport::new()
+        .with_response(
+            Method::GET,
+            "/v0/tts/voices",
+            MockResponse::new(429).header("retry-after", "0"),
+        )
+        .with_json(
+            Method::GET,
+            "/v0/tts/voices",
+            200,
+            serde_json::json!({ "voices": [] }),
+        );
+
+    let client = HumeClientBuilder::new("test-api-key")
+        .transport(transport.clone())
+        .build()
+        .expect("Failed to build client");
+
+    let voices = client.tts().list_voices(None).await.unwrap();
+    assert!(voices.voices.is_empty());
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].headers["X-Hume-Api-Key"], "test-api-key");
+}
+
+#[tokio::test]
+async fn test_client_with_middleware() {
+    use hume::core::{
+        middleware::Middleware,
+        transport::{HttpRequest, MockTransport},
+        Result,
+    };
+    use reqwest::Method;
+
+    #[derive(Debug)]
+    struct Gateway;
+
+    #[async_trait::async_trait]
+    impl Middleware for Gateway {
+        async fn before_send(&self, request: &mut HttpRequest) -> Result<()> {
+            let path = format!("/gateway{}", request.url.path());
+            request.url.set_path(&path);
+            request.headers.insert("X-Tenant", "acme".parse().unwrap());
+            Ok(())
+        }
+    }
+
+    let transport = MockTransport::new().with_json(
+        Method::GET,
+        "/gateway/v0/tts/voices",
+        200,
+        serde_json::json!({ "voices": [] }),
+    );
+
+    let client = HumeClientBuilder::new("test-api-key")
+        .transport(transport.clone())
+        .middleware(Gateway)
+        .build()
+        .expect("Failed to build client");
+
+    client.tts().list_voices(None).await.unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].headers["X-Tenant"], "acme");
+}
+
+#[tokio::test]
+async fn test_client_with_retry_classifier() {
+    use hume::core::{
+        request::RequestOptions,
+        retry::{RetryClassifier, RetryContext, RetryPolicyBuilder},
+        transport::{MockResponse, MockTransport},
+        Error,
+    };
+    use reqwest::Method;
+
+    #[derive(Debug)]
+    struct RetryConflicts;
+
+    impl RetryClassifier for RetryConflicts {
+        fn should_retry(&self, error: &Error, context: &RetryContext) -> bool {
+            context.path == "/v0/tts/voices" && error.status_code() == Some(409)
+        }
+    }
+
+    #[derive(Debug)]
+    struct NeverRetry;
+
+    impl RetryClassifier for NeverRetry {
+        fn should_retry(&self, _error: &Error, _context: &RetryContext) -> bool {
+            false
+        }
+    }
+
+    let build_client = |transport: &MockTransport| {
+        let retry_config = RetryPolicyBuilder::new()
+            .max_retries(2)
+            .initial_interval(Duration::from_millis(1))
+            .build();
+
+        HumeClientBuilder::new("test-api-key")
+            .transport(transport.clone())
+            .retry_config(retry_config)
+            .retry_classifier(RetryConflicts)
+            .build()
+            .expect("Failed to build client")
+    };
+    let conflict_then_ok = || {
+        MockTransport::new()
+            .with_response(Method::GET, "/v0/tts/voices", MockResponse::new(409))
+            .with_json(
+                Method::GET,
+                "/v0/tts/voices",
+                200,
+                serde_json::json!({ "voices": [] }),
+            )
+    };
+
+    // The client classifier retries the 409
+    let transport = conflict_then_ok();
+    let client = build_client(&transport);
+    assert!(client.tts().list_voices(None).await.is_ok());
+    assert_eq!(transport.requests().len(), 2);
+
+    // A per-request classifier overrides the client default
+    let transport = conflict_then_ok();
+    let client = build_client(&transport);
+    let options = RequestOptions::new().with_retry_classifier(NeverRetry);
+    let result = client.tts().list_voices(Some(options)).await;
+    assert_eq!(result.unwrap_err().status_code(), Some(409));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_client_deadline_skips_long_retry_after() {
+    use hume::core::{
+        request::RequestOptions,
+        transport::{MockResponse, MockTransport},
+    };
+    use reqwest::Method;
+
+    let rate_limited = || MockResponse::new(429).header("retry-after", "5");
+    let transport = MockTransport::new()
+        .with_response(Method::GET, "/v0/tts/voices", rate_limited())
+        .with_response(Method::GET, "/v0/tts/voices", rate_limited());
+    let client = HumeClientBuilder::new("test-api-key")
+        .transport(transport.clone())
+        .deadline(Duration::from_secs(1))
+        .build()
+        .expect("Failed to build client");
+
+    let started = std::time::Instant::now();
+    let result = client.tts().list_voices(None).await;
+    assert!(result.unwrap_err().is_rate_limit());
+    assert_eq!(transport.requests().len(), 1);
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    // A per-request deadline overrides the client default
+    let options = RequestOptions::new().with_deadline(Duration::from_millis(10));
+    let result = client.tts().list_voices(Some(options)).await;
+    assert!(result.unwrap_err().is_rate_limit());
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_client_with_rate_limiter() {
+    use hume::core::{
+        rate_limit::{RateLimit, RateLimiter},
+        transport::MockTransport,
+    };
+    use reqwest::Method;
+
+    let transport = MockTransport::new().with_json(
+        Method::GET,
+        "/v0/tts/voices",
+        200,
+        serde_json::json!({ "voices": [] }),
+    );
+
+    let client = HumeClientBuilder::new("test-api-key")
+        .transport(transport)
+        .rate_limiter(RateLimiter::new().with_endpoint("/v0/tts", RateLimit::per_second(20.0)))
+        .build()
+        .expect("Failed to build client");
+
+    let start = std::time::Instant::now();
+    for _ in 0..3 {
+        client.tts().list_voices(None).await.unwrap();
+    }
+    assert!(start.elapsed() >= Duration::from_millis(90));
+}
+
+#[tokio::test]
+async fn test_rate_limiter_concurrency_covers_open_streams() {
+    use futures::StreamExt;
+    use hume::core::{
+        rate_limit::{RateLimit, RateLimiter},
+        transport::{MockResponse, MockTransport},
+    };
+    use hume::tts::models::TtsStreamRequest;
+    use reqwest::Method;
+
+    let transport = MockTransport::new()
+        .with_response(Method::POST, "/v0/tts/stream/file", MockResponse::new(200).body("first"))
+        .with_response(Method::POST, "/v0/tts/stream/file", MockResponse::new(200).body("second"));
+    let limit = RateLimit::per_second(1000.0).burst(10).max_concurrency(1);
+    let client = HumeClientBuilder::new("test-api-key")
+        .transport(transport)
+        .rate_limiter(RateLimiter::new().with_endpoint("/v0/tts", limit))
+        .build()
+        .unwrap();
+    let tts = client.tts();
+    let request = || TtsStreamRequest {
+        text: "Hi".to_string(),
+        ..Default::default()
+    };
+
+    let first = tts.stream_file(request(), None).await.unwrap();
+    let blocked = tokio::time::timeout(Duration::from_millis(50), tts.stream_file(request(), None)).await;
+    assert!(blocked.is_err(), "second stream started while the first was open");
+
+    drop(first);
+    let mut second = tts.stream_file(request(), None).await.unwrap();
+    assert_eq!(&second.next().await.unwrap().unwrap()[..], b"second");
+}
+
+#[tokio::test]
+async fn test_client_with_circuit_breaker() {
+    use hume::core::{
+        circuit_breaker::{CircuitBreakerConfig, CircuitState},
+        transport::MockTransport,
+    };
+    use reqwest::Method;
+
+    let transport = MockTransport::new().with_json(
+        Method::GET,
+        "/v0/tts/voices",
+        503,
+        serde_json::json!({ "message": "unavailable" }),
+    );
+
+    let client = HumeClientBuilder::new("test-api-key")
+        .transport(transport.clone())
+        .max_retries(0)
+        .circuit_breaker(CircuitBreakerConfig::new(2, Duration::from_secs(60)))
+        .build()
+        .expect("Failed to build client");
+
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+    for _ in 0..2 {
+        let error = client.tts().list_voices(None).await.unwrap_err();
+        assert_eq!(error.status_code(), Some(503));
+    }
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+
+    // Open circuit fails fast without reaching the transport
+    let error = client.tts().list_voices(None).await.unwrap_err();
+    assert!(error.is_circuit_open());
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_client_cassette_record_and_replay() {
+    use hume::core::{cassette::Cassette, transport::MockTransport};
+    use reqwest::Method;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("voices.json");
+
+    let transport = MockTransport::new().with_json(
+        Method::GET,
+        "/v0/tts/voices",
+        200,
+        serde_json::json!({ "voices": [{ "name": "Ava", "id": "voice-1" }] }),
+    );
+    let recording = HumeClientBuilder::new("real-api-key")
+        .transport(transport)
+        .cassette(Cassette::record(&path))
+        .build()
+        .expect("Failed to build client");
+    let recorded = recording.tts().list_voices(None).await.unwrap();
+    recording.http().cassette().unwrap().save().unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(!contents.contains("real-api-key"));
+
+    // Replay with no transport responses scripted
+    let replaying = HumeClientBuilder::new("other-key")
+        .transport(MockTransport::new())
+        .cassette(Cassette::replay(&path).unwrap())
+        .build()
+        .expect("Failed to build client");
+    let replayed = replaying.tts().list_voices(None).await.unwrap();
+
+    assert_eq!(recorded.voices.len(), 1);
+    assert_eq!(replayed.voices[0].id, recorded.voices[0].id);
+}
+
+#[tokio::test]
+async fn test_chat_socket_cassette_replay() {
+    use hume::{core::cassette::Cassette, evi::chat::ServerMessage};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("chat.json");
+    let cassette = serde_json::json!({
+        "sockets": [{
+            "path": "/v0/evi/chat",
+            "query": [["config_id", "config-1"]],
+            "sent": [r#"{"type":"user_input","text":"Hello"}"#],
+            "received": [r#"{"type":"user_message","message_id":"m1","text":"Hello"}"#]
+        }]
+    });
+    std::fs::write(&path, cassette.to_string()).unwrap();
+
+    let client = std::sync::Arc::new(
+        HumeClientBuilder::new("test-api-key")
+            .cassette(Cassette::replay(&path).unwrap())
+            .build()
+            .expect("Failed to build client"),
+    );
+    let mut socket = hume::evi::chat::ChatClient::new(client)
+        .connect(Some("config-1".to_string()), None, None)
+        .await
+        .unwrap();
+
+    socket.send_text("Hello".to_string()).await.unwrap();
+    match socket.receive().await.unwrap() {
+        Some(ServerMessage::UserMessage { text, .. }) => assert_eq!(text, "Hello"),
+        other => panic!("Unexpected message: {:?}", other),
+    }
+    assert!(socket.receive().await.unwrap().is_none());
+    assert!(socket.send_text("Unrecorded".to_string()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_client_response_metadata() {
+    use hume::core::{
+        retry::RetryPolicyBuilder,
+        transport::{MockResponse, MockTransport},
+    };
+    use reqwest::Method;
+
+    let transport = MockTransport::new()
+        .with_response(Method::GET, "/v0/tts/voices", MockResponse::new(503))
+        .with_response(
+            Method::GET,
+            "/v0/tts/voices",
+            MockResponse::new(200)
+                .json(serde_json::json!({ "voices": [] }))
+                .header("x-request-id", "req-123"),
+        )
+        .with_response(
+            Method::GET,
+            "/v0/batch/jobs/missing",
+            MockResponse::new(404)
+                .json(serde_json::json!({ "message": "Job not found" }))
+                .header("x-request-id", "req-404"),
+        );
+
+    let client = HumeClientBuilder::new("test-api-key")
+        .transport(transport)
+        .retry_config(
+            RetryPolicyBuilder::new()
+                .max_retries(2)
+                .initial_interval(Duration::from_millis(1))
+                .build(),
+        )
+        .build()
+        .expect("Failed to build client");
+
+    let voices = client.tts().list_voices_with_response(None).await.unwrap();
+    assert!(voices.voices.is_empty());
+    assert_eq!(voices.meta.status, reqwest::StatusCode::OK);
+    assert_eq!(voices.meta.request_id.as_deref(), Some("req-123"));
+    assert_eq!(voices.meta.attempts, 2);
+
+    let error = client
+        .expression()
+        .batch()
+        .get_job_with_response("missing", None)
+        .await
+        .unwrap_err();
+    assert_eq!(error.status_code(), Some(404));
+    assert_eq!(error.request_id(), Some("req-404"));
+    assert_eq!(error.meta().unwrap().attempts, 1);
+}
+
+#[tokio::test]
+async fn test_evi_response_metadata() {
+    use hume::core::transport::{MockResponse, MockTransport};
+    use reqwest::Method;
+
+    let transport = MockTransport::new()
+        .with_response(
+            Method::DELETE,
+            "/v0/evi/configs/config-1",
+            MockResponse::new(200)
+                .json(serde_json::json!({}))
+                .header("x-request-id", "req-delete"),
+        )
+        .with_response(
+            Method::GET,
+            "/v0/evi/chat_groups",
+            MockResponse::new(200)
+                .json(serde_json::json!({
+                    "page_number": 0,
+                    "page_size": 10,
+                    "total_pages": 0,
+                    "pagination_direction": "ASC",
+                    "chat_groups_page": [],
+                }))
+                .header("x-request-id", "req-groups"),
+        );
+
+    let client = HumeClientBuilder::new("test-api-key")
+        .transport(transport)
+        .build()
+        .expect("Failed to build client");
+
+    let deleted = client
+        .evi()
+        .configs()
+        .delete_with_response("config-1", None)
+        .await
+        .unwrap();
+    assert_eq!(deleted.meta.request_id.as_deref(), Some("req-delete"));
+
+    let groups = client
+        .evi()
+        .chat()
+        .list_chat_groups_with_response(None, None, None)
+        .await
+        .unwrap();
+    assert!(groups.chat_groups_page.is_empty());
+    assert_eq!(groups.meta.request_id.as_deref(), Some("req-groups"));
+}
+
+#[tokio::test]
+async fn test_client_paginates_configs() {
+    use futures::StreamExt;
+    use hume::core::transport::MockTransport;
+    use reqwest::Method;
+
+    let page = |number: u32, ids: &[&str]| {
+        serde_json::json!({
+            "page_number": number,
+            "page_size": 2,
+            "total_pages": 2,
+            "configs_page": ids
+                .iter()
+                .map(|id| serde_json::json!({ "id": id, "name": id, "version": 0 }))
+                .collect::<Vec<_>>(),
+        })
+    };
+    let transport = MockTransport::new()
+        .with_json(Method::GET, "/v0/evi/configs", 200, page(0, &["a", "b"]))
+        .with_json(Method::GET, "/v0/evi/configs", 200, page(1, &["c"]));
+
+    let client = HumeClientBuilder::new("test-api-key")
+        .transport(transport.clone())
+        .build()
+        .expect("Failed to build client");
+
+    let ids: Vec<String> = client
+        .evi()
+        .configs()
+        .paginate(None)
+        .page_size(2)
+        .into_stream()
+        .map(|config| config.unwrap().id)
+        .collect()
+        .await;
+    assert_eq!(ids, vec!["a", "b", "c"]);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    let mut query: Vec<_> = requests[1].url.query_pairs().into_owned().collect();
+    query.sort();
+    assert_eq!(
+        query,
+        vec![
+            ("page_number".to_string(), "1".to_string()),
+            ("page_size".to_string(), "2".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_client_rest_urls_use_endpoints() {
+    use hume::core::{request::RequestOptions, transport::MockTransport};
+    use reqwest::Method;
+
+    let transport = MockTransport::new().with_json(
+        Method::GET,
+        "/gateway/v0/evi/configs/a%2Fb",
+        200,
+        serde_json::json!({ "id": "a/b", "name": "Support", "version": 0 }),
+    );
+    let client = HumeClientBuilder::new("test-api-key")
+        .base_url("https://proxy.example.com/gateway/")
+        .transport(transport.clone())
+        .build()
+        .expect("Failed to build client");
+
+    let options = RequestOptions::new().with_query("tag", "a&b=c");
+    let config = client.evi().configs().get("a/b", Some(options)).await.unwrap();
+    assert_eq!(config.id, "a/b");
+    assert_eq!(
+        transport.requests()[0].url.as_str(),
+        "https://proxy.example.com/gateway/v0/evi/configs/a%2Fb?tag=a%26b%3Dc"
+    );
+}
+
+#[tokio::test]
+async fn test_token_url_uses_endpoints() {
+    use hume::core::transport::MockTransport;
+    use reqwest::Method;
+
+    let token = || serde_json::json!({ "access_token": "token", "token_type": "Bearer", "expires_in": 3600 });
+    let transport = MockTransport::new()
+        .with_json(Method::POST, "/gateway/oauth2-cc/token", 200, token())
+        .with_json(Method::POST, "/gateway/oauth2-cc/token", 200, token())
+        .with_json(Method::GET, "/gateway/v0/tts/voices", 200, serde_json::json!({ "voices": [] }));
+    let client = HumeClientBuilder::default()
+        .client_credentials("test-api-key", "test-secret-key")
+        .base_url("https://proxy.example.com/gateway/")
+        .transport(transport.clone())
+        .build()
+        .expect("Failed to build client");
+
+    client.tts().list_voices(None).await.unwrap();
+    client.generate_access_token("test-api-key", "test-secret-key").await.unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].url.as_str(), "https://proxy.example.com/gateway/oauth2-cc/token");
+    assert_eq!(requests[2].url.as_str(), "https://proxy.example.com/gateway/oauth2-cc/token");
+}
+
+#[tokio::test]
+async fn test_client_profile_defaults() {
+    use hume::core::{config::HumeConfig, transport::MockTransport};
+    use reqwest::Method;
+
+    let config = HumeConfig::parse(
+        r#"
+        [profiles.dev]
+        api_key = "dev-key"
+        base_url = "https://dev.example.com"
+
+        [profiles.dev.tts]
+        voice = "Ava Song"
+        "#,
+    )
+    .unwrap();
+
+    let transport = MockTransport::new().with_response(
+        Method::POST,
+        "/v0/tts/file",
+        hume::core::transport::MockResponse::new(200).body("audio"),
+    );
+    let client = HumeClientBuilder::default()
+        .profile(config.profile("dev").unwrap())
+        .transport(transport.clone())
+        .build()
+        .expect("Failed to build client");
+
+    assert_eq!(client.base_url(), "https://dev.example.com");
+    client.tts().synthesize_simple("Hello", None::<String>).await.unwrap();
+
+    let request: serde_json::Value = transport.requests()[0].json().unwrap();
+    assert_eq!(request["utterances"][0]["voice"]["name"], "Ava Song");
+    assert_eq!(transport.requests()[0].headers["x-hume-api-key"], "dev-key");
+}
+
+#[tokio::test]
+async fn test_chat_socket_through_http_proxy() {
+    use futures_util::{SinkExt, StreamExt};
+    use hume::evi::chat::ServerMessage;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+    use tokio_tungstenite::tungstenite::Message;
+
+    // WebSocket server that echoes the user input back as a user message
+    let server = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let server_addr = server.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (stream, _) = server.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        if let Some(Ok(Message::Text(_))) = ws.next().await {
+            let reply = r#"{"type":"user_message","message_id":"m1","text":"Hello"}"#;
+            ws.send(Message::Text(reply.to_string())).await.unwrap();
+        }
+    });
+
+    // HTTP proxy that records the CONNECT request and tunnels to the target
+    let proxy = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let proxy_addr = proxy.local_addr().unwrap();
+    let proxy_task = tokio::spawn(async move {
+        let (mut client, _) = proxy.accept().await.unwrap();
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(client.read_u8().await.unwrap());
+        }
+        let head = String::from_utf8(head).unwrap();
+        let target = head.split_whitespace().nth(1).unwrap().to_string();
+        let mut upstream = TcpStream::connect(&target).await.unwrap();
+        client.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n").await.unwrap();
+        let _ = tokio::io::copy_bidirectional(&mut client, &mut upstream).await;
+        head
+    });
+
+    let client = std::sync::Arc::new(
+        HumeClientBuilder::new("test-api-key")
+            .base_url(format!("http://{}", server_addr))
+            .proxy(format!("http://{}", proxy_addr))
+            .proxy_basic_auth("user", "pass")
+            .build()
+            .expect("Failed to build client"),
+    );
+    let mut socket = hume::evi::chat::ChatClient::new(client)
+        .connect(Some("config-1".to_string()), None, None)
+        .await
+        .unwrap();
+
+    socket.send_text("Hello".to_string()).await.unwrap();
+    match socket.receive().await.unwrap() {
+        Some(ServerMessage::UserMessage { text, .. }) => assert_eq!(text, "Hello"),
+        other => panic!("Unexpected message: {:?}", other),
+    }
+    drop(socket);
+
+    let head = proxy_task.await.unwrap();
+    assert!(head.starts_with(&format!("CONNECT {} HTTP/1.1\r\n", server_addr)));
+    assert!(head.contains("Proxy-Authorization: Basic dXNlcjpwYXNz\r\n"));
+}
+
+#[test]
+fn test_client_builder_tls_and_proxy_options() {
+    use hume::core::tls::TlsVersion;
+
+    HumeClientBuilder::new("test-api-key")
+        .proxy("socks5h://127.0.0.1:1080")
+        .min_tls_version(TlsVersion::Tls13)
+        .build()
+        .expect("Failed to build client");
+
+    let invalid_proxy = HumeClientBuilder::new("test-api-key").proxy("ftp://proxy.local").build();
+    assert!(matches!(invalid_proxy, Err(hume::Error::Config(_))));
+
+    let invalid_root = HumeClientBuilder::new("test-api-key")
+        .add_root_certificate_pem("-----BEGIN CERTIFICATE-----\nnot base64!\n-----END CERTIFICATE-----\n")
+        .build();
+    assert!(matches!(invalid_root, Err(hume::Error::Config(_))));
+}
+
+#[test]
+fn test_client_endpoints() {
+    let client = HumeClientBuilder::new("test-api-key")
+        .base_url("http://localhost:8080/")
+        .build()
+        .expect("Failed to build client");
+    assert_eq!(client.endpoints().websocket_base().as_str(), "ws://localhost:8080/");
+
+    let client = HumeClientBuilder::new("test-api-key")
+        .websocket_base_url("https://stream.example.com")
+        .build()
+        .expect("Failed to build client");
+    let url = client
+        .endpoints()
+        .websocket_url("/v0/evi/chat", [("config_id", "a b&c")]);
+    assert_eq!(url.as_str(), "wss://stream.example.com/v0/evi/chat?config_id=a+b%26c");
+    assert_eq!(client.endpoints().rest_base().as_str(), "https://api.hume.ai/");
+
+    let invalid = HumeClientBuilder::new("test-api-key").base_url("api.hume.ai").build();
+    assert!(matches!(invalid, Err(hume::Error::Config(_))));
+}
+
+#[tokio::test]
+async fn test_chat_socket_credentials() {
+    use hume::core::auth::WebSocketAuth;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+
+    async fn handshake(mode: WebSocketAuth) -> Request {
+        let server = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let handshake = tokio::spawn(async move {
+            let (stream, _) = server.accept().await.unwrap();
+            let mut captured = None;
+            let _ws = tokio_tungstenite::accept_hdr_async(stream, |request: &Request, response: Response| {
+                captured = Some(request.clone());
+                Ok(response)
+            })
+            .await
+            .unwrap();
+            captured.unwrap()
+        });
+
+        let client = std::sync::Arc::new(
+            HumeClientBuilder::new("secret-key")
+                .base_url(format!("http://{}", addr))
+                .websocket_auth(mode)
+                .build()
+                .expect("Failed to build client"),
+        );
+        hume::evi::chat::ChatClient::new(client)
+            .connect(Some("config-1".to_string()), None, None)
+            .await
+            .unwrap();
+        handshake.await.unwrap()
+    }
+
+    let request = handshake(WebSocketAuth::Headers).await;
+    assert_eq!(request.uri().to_string(), "/v0/evi/chat?config_id=config-1");
+    assert_eq!(request.headers()["x-hume-api-key"], "secret-key");
+
+    let request = handshake(WebSocketAuth::QueryParam).await;
+    assert_eq!(request.uri().to_string(), "/v0/evi/chat?config_id=config-1&api_key=secret-key");
+    assert!(!request.headers().contains_key("x-hume-api-key"));
+}
+
+#[test]
+fn test_client_debug_redacts_credentials() {
+    let builder = HumeClientBuilder::new("secret-api-key")
+        .client_credentials("secret-api-key", "secret-secret-key")
+        .proxy("http://proxy.local:3128")
+        .proxy_basic_auth("user", "secret-password");
+    let debug = format!("{:?}", builder);
+    assert!(!debug.contains("secret-"));
+
+    let client = builder.build().unwrap();
+    assert!(!format!("{:?}", client).contains("secret-"));
+
+    let client = HumeClientBuilder::new("secret-api-key")
+        .with_access_token("secret-token")
+        .build()
+        .unwrap();
+    assert!(!format!("{:?}", client).contains("secret-"));
+    assert_eq!(
+        client.auth().unwrap().header_value(),
+        Some(("Authorization", "Bearer secret-token".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_chat_socket_cancellation_sends_close_frame() {
+    use futures::StreamExt;
+    use hume::core::CancellationToken;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::{protocol::frame::coding::CloseCode, Message};
+
+    let server = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = server.local_addr().unwrap();
+    let close_frame = tokio::spawn(async move {
+        let (stream, _) = server.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        while let Some(Ok(message)) = ws.next().await {
+            if let Message::Close(frame) = message {
+                return frame;
+            }
+        }
+        None
+    });
+
+    let client = std::sync::Arc::new(
+        HumeClientBuilder::new("test-api-key")
+            .base_url(format!("http://{}", addr))
+            .build()
+            .expect("Failed to build client"),
+    );
+    let token = CancellationToken::new();
+    let mut socket = hume::evi::chat::ChatSessionBuilder::new()
+        .cancellation(token.clone())
+        .connect(&hume::evi::chat::ChatClient::new(client))
+        .await
+        .unwrap();
+
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        canceller.cancel();
+    });
+    assert!(socket.receive().await.unwrap_err().is_cancelled());
+    assert!(socket.send_text("hello".to_string()).await.unwrap_err().is_cancelled());
+
+    let frame = close_frame.await.unwrap().expect("Expected a close frame");
+    assert_eq!(frame.code, CloseCode::Normal);
+}
+
+#[tokio::test]
+async fn test_request_cancellation_stops_retries() {
+    use hume::core::{request::RequestOptions, transport::MockTransport, CancellationToken};
+    use reqwest::Method;
+
+    let transport = MockTransport::new();
+    for _ in 0..5 {
+        transport.push_response(
+            Method::GET,
+            "/v0/tts/voices",
+            hume::core::transport::MockResponse::new(503),
+        );
+    }
+    let client = HumeClientBuilder::new("test-api-key")
+        .transport(transport.clone())
+        .retry_config(
+            hume::core::retry::RetryPolicyBuilder::new()
+                .max_retries(4)
+                .initial_interval(Duration::from_secs(5))
+                .build(),
+        )
+        .build()
+        .expect("Failed to build client");
+
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        canceller.cancel();
+    });
+    let started = std::time::Instant::now();
+    let options = RequestOptions::new().with_cancellation(token);
+    let result = client.tts().list_voices(Some(options)).await;
+
+    assert!(result.unwrap_err().is_cancelled());
+    assert_eq!(transport.requests().len(), 1);
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_create_retries_with_stable_idempotency_key() {
+    use hume::core::{
+        request::RequestOptions,
+        retry::RetryPolicyBuilder,
+        transport::{MockResponse, MockTransport},
+    };
+    use hume::evi::tools::CreateToolRequestBuilder;
+    use reqwest::Method;
+
+    let unavailable_then_created = || {
+        MockTransport::new()
+            .with_response(Method::POST, "/v0/evi/tools", MockResponse::new(503))
+            .with_json(
+                Method::POST,
+                "/v0/evi/tools",
+                200,
+                serde_json::json!({ "id": "tool-1", "name": "lookup", "description": "", "parameters": {} }),
+            )
+    };
+    let build_client = |transport: &MockTransport, idempotency_keys: bool| {
+        HumeClientBuilder::new("test-api-key")
+            .transport(transport.clone())
+            .retry_config(RetryPolicyBuilder::new().initial_interval(Duration::from_millis(1)).build())
+            .idempotency_keys(idempotency_keys)
+            .build()
+            .expect("Failed to build client")
+    };
+    let request = || CreateToolRequestBuilder::new("lookup", "Look up an order").build();
+    let key = |request: &hume::core::transport::HttpRequest| {
+        request.headers["idempotency-key"].to_str().unwrap().to_string()
+    };
+
+    // A generated key is reused by the retry
+    let transport = unavailable_then_created();
+    let client = build_client(&transport, true);
+    assert!(client.evi().tools().create(request(), None).await.is_ok());
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(key(&requests[0]), key(&requests[1]));
+
+    // A caller-supplied key replaces the generated one
+    let transport = unavailable_then_created();
+    let client = build_client(&transport, true);
+    let options = RequestOptions::new().with_idempotency_key("create-lookup-tool");
+    assert!(client.evi().tools().create(request(), Some(options)).await.is_ok());
+    assert!(transport.requests().iter().all(|request| key(request) == "create-lookup-tool"));
+
+    // Without a key the create is not retried
+    let transport = unavailable_then_created();
+    let client = build_client(&transport, false);
+    let result = client.evi().tools().create(request(), None).await;
+    assert_eq!(result.unwrap_err().status_code(), Some(503));
+    assert_eq!(transport.requests().len(), 1);
+    assert!(!transport.requests()[0].headers.contains_key("idempotency-key"));
+}
+
+#[tokio::test]
+async fn test_synthesis_is_not_retried_by_default() {
+    use hume::core::transport::{MockResponse, MockTransport};
+    use hume::tts::models::TtsRequestBuilder;
+    use reqwest::Method;
+
+    let transport = MockTransport::new()
+        .with_response(Method::POST, "/v0/tts", MockResponse::new(503))
+        .with_json(Method::POST, "/v0/tts", 200, serde_json::json!({ "generations": [] }));
+    let client = HumeClientBuilder::new("test-api-key")
+        .transport(transport.clone())
+        .build()
+        .expect("Failed to build client");
+
+    let request = TtsRequestBuilder::new().utterance("Hello").unwrap().build();
+    let result = client.tts().synthesize(request, None).await;
+    assert_eq!(result.unwrap_err().status_code(), Some(503));
+    assert_eq!(transport.requests().len(), 1);
+    assert!(!transport.requests()[0].headers.contains_key("idempotency-key"));
+}
+
+#[tokio::test]
+async fn test_client_reports_metrics() {
+    use hume::core::{
+        cassette::Cassette,
+        metrics::{Metrics, StreamDirection},
+        retry::RetryPolicyBuilder,
+        transport::{MockResponse, MockTransport},
+    };
+    use reqwest::Method;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Default)]
+    struct Recorded {
+        requests: Vec<(String, String, Option<u16>)>,
+        retries: Vec<u32>,
+        bytes: Vec<(StreamDirection, usize)>,
+        sockets: Vec<String>,
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct RecordingMetrics(Arc<Mutex<Recorded>>);
+
+    impl Metrics for RecordingMetrics {
+        fn request_completed(&self, method: &Method, endpoint: &str, status: Option<u16>, _latency: Duration) {
+            let mut recorded = self.0.lock().unwrap();
+            recorded.requests.push((method.to_string(), endpoint.to_string(), status));
+        }
+
+        fn request_retried(&self, _method: &Method, _endpoint: &str, attempt: u32) {
+            self.0.lock().unwrap().retries.push(attempt);
+        }
+
+        fn bytes_streamed(&self, _endpoint: &str, direction: StreamDirection, bytes: usize) {
+            self.0.lock().unwrap().bytes.push((direction, bytes));
+        }
+
+        fn socket_opened(&self, endpoint: &str) {
+            self.0.lock().unwrap().sockets.push(format!("opened {}", endpoint));
+        }
+
+        fn socket_closed(&self, endpoint: &str, _lifetime: Duration) {
+            self.0.lock().unwrap().sockets.push(format!("closed {}", endpoint));
+        }
+    }
+
+    let job_id = "2b3c6f7e-8a5d-4b1e-9f0a-1c2d3e4f5a6b";
+    let path = format!("/v0/batch/jobs/{}/predictions", job_id);
+    let transport = MockTransport::new()
+        .with_response(Method::GET, &path, MockResponse::new(503))
+        .with_json(Method::GET, &path, 200, serde_json::json!([]));
+    let metrics = RecordingMetrics::default();
+    let client = HumeClientBuilder::new("test-api-key")
+        .transport(transport)
+        .retry_config(RetryPolicyBuilder::new().initial_interval(Duration::from_millis(1)).build())
+        .metrics(metrics.clone())
+        .build()
+        .expect("Failed to build client");
+
+    client.http().get::<serde_json::Value>(&path, None).await.unwrap();
+    {
+        let recorded = metrics.0.lock().unwrap();
+        let endpoint = "/v0/batch/jobs/{id}/predictions".to_string();
+        assert_eq!(recorded.requests, vec![("GET".to_string(), endpoint, Some(200))]);
+        assert_eq!(recorded.retries, vec![2]);
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let cassette_path = dir.path().join("chat.json");
+    let cassette = serde_json::json!({
+        "sockets": [{
+            "path": "/v0/evi/chat",
+            "query": [],
+            "sent": [r#"{"type":"user_input","text":"Hi"}"#],
+            "received": [r#"{"type":"user_message","message_id":"m1","text":"Hi"}"#]
+        }]
+    });
+    std::fs::write(&cassette_path, cassette.to_string()).unwrap();
+    let client = Arc::new(
+        HumeClientBuilder::new("test-api-key")
+            .cassette(Cassette::replay(&cassette_path).unwrap())
+            .metrics(metrics.clone())
+            .build()
+            .expect("Failed to build client"),
+    );
+
+    let mut socket = hume::evi::chat::ChatClient::new(client)
+        .connect(None, None, None)
+        .await
+        .unwrap();
+    socket.send_text("Hi".to_string()).await.unwrap();
+    socket.receive().await.unwrap();
+    socket.close().await.unwrap();
+
+    let recorded = metrics.0.lock().unwrap();
+    assert_eq!(
+        recorded.bytes,
+        vec![(StreamDirection::Sent, 33), (StreamDirection::Received, 53)]
+    );
+    assert_eq!(recorded.sockets, vec!["opened /v0/evi/chat", "closed /v0/evi/chat"]);
+}
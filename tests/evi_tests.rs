@@ -39,6 +39,7 @@ fn test_tool_creation() {
         version_id: Some("v1".to_string()),
         created_at: None,
         updated_at: None,
+        extra: Default::default(),
     };
     
     assert_eq!(tool.id, "tool-123");
@@ -74,6 +75,7 @@ fn test_prompt_creation() {
         version_description: Some("Initial version".to_string()),
         created_at: None,
         updated_at: None,
+        extra: Default::default(),
     };
     
     assert_eq!(prompt.id, "prompt-123");
@@ -104,6 +106,7 @@ fn test_custom_voice_creation() {
         }),
         created_at: None,
         updated_at: None,
+        extra: Default::default(),
     };
     
     assert_eq!(voice.id, "voice-123");
@@ -151,6 +154,7 @@ fn test_config_creation() {
         timeouts: None,
         created_at: None,
         updated_at: None,
+        extra: Default::default(),
     };
     
     assert_eq!(config.id, "config-123");
@@ -227,4 +231,50 @@ fn test_audio_encoding_serialization() {
 fn test_context_type_serialization() {
     assert_eq!(serde_json::to_string(&ContextType::Persistent).unwrap(), r#""persistent""#);
     assert_eq!(serde_json::to_string(&ContextType::Temporary).unwrap(), r#""temporary""#);
-}
\ No newline at end of file
+}
+#[test]
+fn test_server_message_error_kind() {
+    use hume::core::error::{ErrorKind, HumeCode};
+    use hume::evi::chat::ServerMessage;
+
+    let message: ServerMessage = serde_json::from_str(
+        r#"{"type": "error", "message": "Out of credits", "code": "E0300"}"#,
+    )
+    .unwrap();
+    assert_eq!(message.hume_code(), Some(HumeCode::OutOfCredits));
+    assert_eq!(message.error_kind(), Some(ErrorKind::QuotaExceeded));
+
+    let message: ServerMessage =
+        serde_json::from_str(r#"{"type": "warning", "message": "Inactive", "code": "W0101"}"#).unwrap();
+    assert_eq!(message.hume_code(), Some(HumeCode::InactivityWarning));
+    assert_eq!(message.error_kind(), Some(ErrorKind::SessionLimit));
+}
+
+#[cfg(feature = "lenient")]
+#[test]
+fn test_lenient_keeps_unknown_fields() {
+    let prompt: Prompt = serde_json::from_value(serde_json::json!({
+        "id": "prompt-123",
+        "name": "assistant",
+        "text": "You are helpful",
+        "new_field": {"nested": true}
+    }))
+    .unwrap();
+    assert_eq!(prompt.extra["new_field"]["nested"], true);
+
+    let value = serde_json::to_value(&prompt).unwrap();
+    assert_eq!(value["new_field"]["nested"], true);
+}
+
+#[cfg(not(feature = "lenient"))]
+#[test]
+fn test_unknown_fields_are_dropped() {
+    let prompt: Prompt = serde_json::from_value(serde_json::json!({
+        "id": "prompt-123",
+        "name": "assistant",
+        "text": "You are helpful",
+        "new_field": {"nested": true}
+    }))
+    .unwrap();
+    assert!(prompt.extra.is_empty());
+}
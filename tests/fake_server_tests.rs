@@ -0,0 +1,221 @@
+//! Tests for the fake Hume server in `hume::test_util`
+//!
+//! Run with `cargo test --features test-util`.
+
+#![cfg(feature = "test-util")]
+
+use base64::Engine;
+use futures::StreamExt;
+use hume::core::retry::RetryPolicyBuilder;
+use hume::evi::chat::ServerMessage;
+use hume::evi::configs::{CreateConfigRequestBuilder, UpdateConfigRequest};
+use hume::evi::prompts::{CreatePromptRequestBuilder, CreatePromptVersionRequest};
+use hume::expression_measurement::models::{Models, StateInference};
+use hume::expression_measurement::stream::StreamMessage;
+use hume::test_util::{FakeHumeServer, SocketScript, TEST_API_KEY};
+use hume::tts::models::{TtsRequestBuilder, TtsStreamRequest};
+use serde_json::json;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_tts_endpoints() {
+    let server = FakeHumeServer::start().await.unwrap();
+    let tts = server.client().unwrap().tts();
+
+    let request = TtsRequestBuilder::new().utterance("Hello").unwrap().build();
+    let response = tts.synthesize(request.clone(), None).await.unwrap();
+    let audio = base64::engine::general_purpose::STANDARD
+        .decode(&response.generations[0].data)
+        .unwrap();
+    assert_eq!(audio, b"Hello");
+
+    let file = tts.synthesize_file(request, None).await.unwrap();
+    assert_eq!(&file[..], b"Hello");
+
+    let stream_request = TtsStreamRequest {
+        text: "Hi".to_string(),
+        ..Default::default()
+    };
+    let mut chunks = tts.stream_json(stream_request, None).await.unwrap();
+    let chunk = chunks.next().await.unwrap().unwrap();
+    assert!(chunk.is_final);
+
+    let voices = tts.list_voices(None).await.unwrap();
+    assert_eq!(voices.voices.len(), 2);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 4);
+    assert_eq!(requests[0].path, "/v0/tts");
+    assert_eq!(requests[0].headers["x-hume-api-key"], TEST_API_KEY);
+}
+
+#[tokio::test]
+async fn test_requests_require_credentials() {
+    let server = FakeHumeServer::start().await.unwrap();
+
+    let response = reqwest::get(format!("{}/v0/tts/voices", server.base_url())).await.unwrap();
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn test_injected_failures_are_retried() {
+    let server = FakeHumeServer::start().await.unwrap();
+    let client = server
+        .client_builder()
+        .retry_config(
+            RetryPolicyBuilder::new()
+                .initial_interval(Duration::from_millis(1))
+                .build(),
+        )
+        .build()
+        .unwrap();
+
+    server.fail_next("/v0/tts/voices", 503);
+    client.tts().list_voices(None).await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_batch_job_lifecycle() {
+    let server = FakeHumeServer::start().await.unwrap();
+    let batch = server.client().unwrap().expression().batch();
+
+    let job = batch
+        .create_job_from_text(Models::default(), vec!["I am happy".to_string()], None, None, None)
+        .await
+        .unwrap();
+    assert!(matches!(job.state, StateInference::Queued { .. }));
+
+    let job = batch
+        .wait_for_job_completion(&job.job_id, Duration::from_millis(1), None)
+        .await
+        .unwrap();
+    assert!(matches!(job.state, StateInference::Completed { .. }));
+
+    let predictions = batch.get_predictions(&job.job_id, None).await.unwrap();
+    assert_eq!(predictions.predictions.len(), 1);
+    assert_eq!(predictions.predictions[0].source.source_type, "text");
+
+    let jobs = batch.list_jobs(None, None, None).await.unwrap();
+    assert_eq!(jobs.total, 1);
+}
+
+#[tokio::test]
+async fn test_batch_job_can_be_failed() {
+    let server = FakeHumeServer::start().await.unwrap();
+    server.hold_jobs(true);
+    let batch = server.client().unwrap().expression().batch();
+
+    let job = batch
+        .create_job_from_urls(Models::default(), vec!["https://example.com/a.mp4".to_string()], None, None, None)
+        .await
+        .unwrap();
+    for _ in 0..3 {
+        let job = batch.get_job(&job.job_id, None).await.unwrap();
+        assert!(matches!(job.state, StateInference::InProgress { .. }));
+    }
+
+    assert!(server.fail_job(&job.job_id, "Unsupported media"));
+    let job = batch.get_job(&job.job_id, None).await.unwrap();
+    match job.state {
+        StateInference::Failed { message, .. } => assert_eq!(message, "Unsupported media"),
+        state => panic!("expected a failed job, got {:?}", state),
+    }
+}
+
+#[tokio::test]
+async fn test_config_crud_and_versions() {
+    let server = FakeHumeServer::start().await.unwrap();
+    let configs = server.client().unwrap().evi().configs();
+
+    let config = configs
+        .create(CreateConfigRequestBuilder::new("Support").build(), None)
+        .await
+        .unwrap();
+    assert_eq!(config.version, 0);
+
+    let update = UpdateConfigRequest {
+        name: Some("Support v2".to_string()),
+        ..Default::default()
+    };
+    let updated = configs.update(&config.id, update, None).await.unwrap();
+    assert_eq!((updated.name.as_str(), updated.version), ("Support v2", 1));
+
+    let versions = configs.list_versions(&config.id, None, None, None).await.unwrap();
+    assert_eq!(versions.configs_page.unwrap().len(), 2);
+    let first = configs.get_version(&config.id, 0, None).await.unwrap();
+    assert_eq!(first.name, "Support");
+
+    let listed = configs.list(None, None, None).await.unwrap();
+    assert_eq!(listed.configs_page.unwrap()[0].version, 1);
+
+    configs.delete(&config.id, None).await.unwrap();
+    let error = configs.get(&config.id, None).await.unwrap_err();
+    assert_eq!(error.status_code(), Some(404));
+}
+
+#[tokio::test]
+async fn test_prompt_versions() {
+    let server = FakeHumeServer::start().await.unwrap();
+    let prompts = server.client().unwrap().evi().prompts();
+
+    let prompt = prompts
+        .create(CreatePromptRequestBuilder::new("Greeter", "Say hello").build(), None)
+        .await
+        .unwrap();
+    let version = CreatePromptVersionRequest {
+        text: "Say hello warmly".to_string(),
+        version_description: None,
+    };
+    let latest = prompts.create_version(&prompt.id, version, None).await.unwrap();
+    assert_eq!(latest.version, Some(1));
+    assert_eq!(prompts.get(&prompt.id, None).await.unwrap().text, "Say hello warmly");
+}
+
+#[tokio::test]
+async fn test_chat_session_is_recorded() {
+    let server = FakeHumeServer::start().await.unwrap();
+    let chat = server.client().unwrap().evi().chat();
+
+    let mut socket = chat.connect(None, None, None).await.unwrap();
+    let chat_id = match socket.receive().await.unwrap() {
+        Some(ServerMessage::SessionStarted { chat_id, .. }) => chat_id,
+        message => panic!("expected session_started, got {:?}", message),
+    };
+
+    socket.send_text("Hello".to_string()).await.unwrap();
+    assert!(matches!(socket.receive().await.unwrap(), Some(ServerMessage::UserMessage { .. })));
+    match socket.receive().await.unwrap() {
+        Some(ServerMessage::AssistantMessage { text, .. }) => assert_eq!(text, "You said: Hello"),
+        message => panic!("expected assistant_message, got {:?}", message),
+    }
+    socket.close().await.unwrap();
+    assert_eq!(server.chat_inputs()[0]["type"], "user_input");
+
+    let messages = chat.get_chat_messages(&chat_id, None, None).await.unwrap();
+    assert_eq!(messages.items.len(), 2);
+
+    let groups = chat.list_chat_groups(None, None, None).await.unwrap();
+    assert_eq!(groups.chat_groups_page[0].first_chat_id, chat_id);
+}
+
+#[tokio::test]
+async fn test_scripted_stream_socket() {
+    let server = FakeHumeServer::start().await.unwrap();
+    server.script_stream(
+        SocketScript::new()
+            .send_on_open(json!({ "type": "job_details", "job_id": "job-1" }))
+            .reply(|message| vec![json!({ "type": "warning", "message": message["text"] })]),
+    );
+    let stream = server.client().unwrap().expression().stream();
+
+    let mut socket = stream.connect(Models::default()).await.unwrap();
+    assert!(matches!(socket.receive().await.unwrap(), Some(StreamMessage::JobDetails { .. })));
+
+    socket.send_text("Great news".to_string()).await.unwrap();
+    match socket.receive().await.unwrap() {
+        Some(StreamMessage::Warning { message }) => assert_eq!(message, "Great news"),
+        message => panic!("expected a warning, got {:?}", message),
+    }
+    assert_eq!(server.stream_inputs().len(), 1);
+}
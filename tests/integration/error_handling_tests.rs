@@ -133,8 +133,8 @@ async fn test_network_error_retry() {
     
     assert!(result.is_err());
     match result.unwrap_err() {
-        Error::Http(_) => {},
-        _ => panic!("Expected network error"),
+        Error::Connect(_) => {},
+        e => panic!("Expected connection error, got {:?}", e),
     }
 }
 
@@ -244,10 +244,9 @@ async fn test_timeout_error() {
     let result = tts.list_voices(Some(options)).await;
     
     assert!(result.is_err());
-    // Timeout errors are wrapped as network errors
     match result.unwrap_err() {
-        Error::Http(_) => {},
-        _ => panic!("Expected network/timeout error"),
+        Error::Timeout => {},
+        e => panic!("Expected timeout error, got {:?}", e),
     }
 }
 
@@ -272,9 +271,9 @@ async fn test_json_decode_error() {
     let result = tts.list_voices(None).await;
     
     assert!(result.is_err());
-    // JSON decode errors are wrapped as HTTP errors
+    // Response bodies are decoded by the SDK, not the transport
     match result.unwrap_err() {
-        Error::Http(_) => {},
-        _ => panic!("Expected HTTP error"),
+        Error::Decode(decode) => assert_eq!(decode.body, "not json"),
+        e => panic!("Expected decode error, got {:?}", e),
     }
 }
\ No newline at end of file
@@ -189,4 +189,94 @@ async fn test_error_handling() {
     let error = result.unwrap_err();
     assert!(error.is_api_error());
     assert_eq!(error.status_code(), Some(401));
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_client_credentials_token_is_cached() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth2-cc/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "generated-token",
+            "token_type": "Bearer",
+            "expires_in": 3600
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v0/tts/voices"))
+        .and(header("Authorization", "Bearer generated-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "voices": []
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let client = HumeClientBuilder::default()
+        .client_credentials("test-key", "test-secret")
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let tts_client = hume::TtsClient::from(client);
+    tts_client.list_voices(None).await.unwrap();
+    tts_client.list_voices(None).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_client_credentials_refresh_on_unauthorized() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth2-cc/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "revoked-token",
+            "token_type": "Bearer",
+            "expires_in": 3600
+        })))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/oauth2-cc/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": "fresh-token",
+            "token_type": "Bearer",
+            "expires_in": 3600
+        })))
+        .with_priority(2)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v0/tts/voices"))
+        .and(header("Authorization", "Bearer revoked-token"))
+        .respond_with(ResponseTemplate::new(401))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/v0/tts/voices"))
+        .and(header("Authorization", "Bearer fresh-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "voices": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = HumeClientBuilder::default()
+        .client_credentials("test-key", "test-secret")
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    let tts_client = hume::TtsClient::from(client);
+    tts_client.list_voices(None).await.unwrap();
+}
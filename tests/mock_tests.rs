@@ -0,0 +1,365 @@
+//! Tests for the fakes in `hume::mock`
+//!
+//! Run with `cargo test --features mock`.
+
+#![cfg(feature = "mock")]
+
+use bytes::Bytes;
+use futures::StreamExt;
+use hume::api::*;
+use hume::evi::configs::{CreateConfigRequestBuilder, UpdateConfigRequest};
+use hume::evi::prompts::{CreatePromptRequestBuilder, CreatePromptVersionRequest, UpdatePromptRequest};
+use hume::evi::tools::{CreateToolRequestBuilder, UpdateToolRequest};
+use hume::evi::voices::{CreateCustomVoiceRequestBuilder, UpdateCustomVoiceRequest};
+use hume::expression_measurement::models::{BatchJobRequest, Models};
+use hume::mock::*;
+use hume::tts::models::{TtsRequestBuilder, TtsStreamRequest};
+use hume::Error;
+use serde_json::json;
+
+/// Message of a result that must be an error
+fn error_message<T>(result: hume::Result<T>) -> String {
+    match result {
+        Ok(_) => panic!("expected an error"),
+        Err(error) => error.to_string(),
+    }
+}
+
+/// Assert that a call failed because its fake method has no handler
+fn assert_unconfigured<T>(result: hume::Result<T>, method: &str) {
+    assert_eq!(error_message(result), format!("{} has no handler configured", method));
+}
+
+/// Error a handler answers with, naming the call so tests can tell which handler ran
+fn answered(call: String) -> Error {
+    Error::other(call)
+}
+
+#[tokio::test]
+async fn test_mock_tts() {
+    let tts = MockTts::new();
+    let request = || TtsRequestBuilder::new().utterance("Hello").unwrap().build();
+    let stream_request = || TtsStreamRequest {
+        text: "Hi".to_string(),
+        ..Default::default()
+    };
+
+    let api: &dyn TtsApi = &tts;
+    assert_unconfigured(api.synthesize(request(), None).await, "MockTts::synthesize");
+    assert_unconfigured(api.synthesize_file(request(), None).await, "MockTts::synthesize_file");
+    assert_unconfigured(api.stream_json(stream_request(), None).await, "MockTts::stream_json");
+    assert_unconfigured(api.stream_file(stream_request(), None).await, "MockTts::stream_file");
+    assert_unconfigured(api.list_voices(None).await, "MockTts::list_voices");
+
+    let tts = tts
+        .on_synthesize(|request, _| Err(answered(format!("synthesize {}", request.utterances.len()))))
+        .on_synthesize_file(|_, _| Ok(Bytes::from_static(b"audio")))
+        .on_stream_json(|request, _| Err(answered(format!("stream_json {}", request.text))))
+        .on_stream_file(|request, _| {
+            Ok(Box::pin(futures::stream::iter(vec![Ok(Bytes::from(request.text))])) as ApiStream<Bytes>)
+        })
+        .on_list_voices(|_| Ok(serde_json::from_value(json!({"voices": []})).unwrap()));
+
+    let api: &dyn TtsApi = &tts;
+    assert_eq!(error_message(api.synthesize(request(), None).await), "synthesize 1");
+    assert_eq!(&api.synthesize_file(request(), None).await.unwrap()[..], b"audio");
+    assert_eq!(error_message(api.stream_json(stream_request(), None).await), "stream_json Hi");
+    let chunks: Vec<_> = api.stream_file(stream_request(), None).await.unwrap().collect().await;
+    assert_eq!(&chunks[0].as_ref().unwrap()[..], b"Hi");
+    assert!(api.list_voices(None).await.unwrap().voices.is_empty());
+
+    assert_eq!(tts.synthesize_calls().len(), 2);
+    assert_eq!(tts.synthesize_file_calls().len(), 2);
+    assert_eq!(tts.stream_json_calls()[1].0.text, "Hi");
+    assert_eq!(tts.stream_file_calls()[1].0.text, "Hi");
+    assert_eq!(tts.list_voices_calls().len(), 2);
+}
+
+#[tokio::test]
+async fn test_mock_batch() {
+    let batch = MockBatch::new();
+    let request = || BatchJobRequest {
+        models: Models::default(),
+        sources: Vec::new(),
+        callback_url: None,
+        notify: None,
+    };
+
+    let api: &dyn BatchApi = &batch;
+    assert_unconfigured(api.list_jobs(Some(10), None, None).await, "MockBatch::list_jobs");
+    assert_unconfigured(api.create_job(request(), None).await, "MockBatch::create_job");
+    assert_unconfigured(api.get_job("job-1", None).await, "MockBatch::get_job");
+    assert_unconfigured(api.get_predictions("job-1", None).await, "MockBatch::get_predictions");
+    assert_unconfigured(api.get_artifacts("job-1", None).await, "MockBatch::get_artifacts");
+
+    let batch = batch
+        .on_list_jobs(|limit, offset, _| Err(answered(format!("list_jobs {:?} {:?}", limit, offset))))
+        .on_create_job(|request, _| Err(answered(format!("create_job {}", request.sources.len()))))
+        .on_get_job(|job_id, _| Err(answered(format!("get_job {}", job_id))))
+        .on_get_predictions(|job_id, _| Err(answered(format!("get_predictions {}", job_id))))
+        .on_get_artifacts(|job_id, _| Err(answered(format!("get_artifacts {}", job_id))));
+
+    let api: &dyn BatchApi = &batch;
+    assert_eq!(error_message(api.list_jobs(Some(10), None, None).await), "list_jobs Some(10) None");
+    assert_eq!(error_message(api.create_job(request(), None).await), "create_job 0");
+    assert_eq!(error_message(api.get_job("job-1", None).await), "get_job job-1");
+    assert_eq!(error_message(api.get_predictions("job-1", None).await), "get_predictions job-1");
+    assert_eq!(error_message(api.get_artifacts("job-1", None).await), "get_artifacts job-1");
+
+    assert_eq!(batch.list_jobs_calls()[1].0, Some(10));
+    assert_eq!(batch.create_job_calls().len(), 2);
+    assert_eq!(batch.get_job_calls()[1].0, "job-1");
+    assert_eq!(batch.get_predictions_calls()[1].0, "job-1");
+    assert_eq!(batch.get_artifacts_calls()[1].0, "job-1");
+}
+
+#[tokio::test]
+async fn test_mock_expression_stream() {
+    let stream = MockExpressionStream::new();
+
+    let api: &dyn ExpressionStreamApi = &stream;
+    assert_unconfigured(api.connect(Models::default()).await, "MockExpressionStream::connect");
+
+    let session = ScriptedSession::new();
+    let stream = stream.on_connect({
+        let session = session.clone();
+        move |models| Ok(session.stream_socket(models))
+    });
+
+    let api: &dyn ExpressionStreamApi = &stream;
+    assert!(api.connect(Models::default()).await.is_ok());
+    assert_eq!(stream.connect_calls().len(), 2);
+}
+
+#[tokio::test]
+async fn test_mock_evi_chat() {
+    let chat = MockEviChat::new();
+
+    let api: &dyn EviChatApi = &chat;
+    assert_unconfigured(api.connect(None, None, None).await, "MockEviChat::connect");
+    assert_unconfigured(api.list_chats(Some(1), None, None).await, "MockEviChat::list_chats");
+    assert_unconfigured(api.get_chat("chat-1").await, "MockEviChat::get_chat");
+    assert_unconfigured(api.list_chat_groups(Some(1), None, None).await, "MockEviChat::list_chat_groups");
+    assert_unconfigured(api.get_chat_messages("chat-1", None, None).await, "MockEviChat::get_chat_messages");
+
+    let session = ScriptedSession::new();
+    let chat = chat
+        .on_connect({
+            let session = session.clone();
+            move |_, _, _| Ok(session.chat_socket())
+        })
+        .on_list_chats(|page_number, _, _| Err(answered(format!("list_chats {:?}", page_number))))
+        .on_get_chat(|chat_id| Err(answered(format!("get_chat {}", chat_id))))
+        .on_list_chat_groups(|page_number, _, _| Err(answered(format!("list_chat_groups {:?}", page_number))))
+        .on_get_chat_messages(|chat_id, _, _| Err(answered(format!("get_chat_messages {}", chat_id))));
+
+    let api: &dyn EviChatApi = &chat;
+    assert!(api.connect(Some("config-1".to_string()), Some(2), None).await.is_ok());
+    assert_eq!(error_message(api.list_chats(Some(1), None, None).await), "list_chats Some(1)");
+    assert_eq!(error_message(api.get_chat("chat-1").await), "get_chat chat-1");
+    assert_eq!(error_message(api.list_chat_groups(Some(1), None, None).await), "list_chat_groups Some(1)");
+    assert_eq!(error_message(api.get_chat_messages("chat-1", None, None).await), "get_chat_messages chat-1");
+
+    assert_eq!(chat.connect_calls()[1], (Some("config-1".to_string()), Some(2), None));
+    assert_eq!(chat.list_chats_calls()[1], (Some(1), None, None));
+    assert_eq!(chat.get_chat_calls()[1].0, "chat-1");
+    assert_eq!(chat.list_chat_groups_calls()[1], (Some(1), None, None));
+    assert_eq!(chat.get_chat_messages_calls()[1].0, "chat-1");
+}
+
+#[tokio::test]
+async fn test_mock_evi_configs() {
+    let configs = MockEviConfigs::new();
+    let request = || CreateConfigRequestBuilder::new("Support").build();
+    let update = || UpdateConfigRequest {
+        name: Some("Renamed".to_string()),
+        ..Default::default()
+    };
+
+    let api: &dyn EviConfigsApi = &configs;
+    assert_unconfigured(api.list(Some(1), None, None).await, "MockEviConfigs::list");
+    assert_unconfigured(api.create(request(), None).await, "MockEviConfigs::create");
+    assert_unconfigured(api.get("config-1", None).await, "MockEviConfigs::get");
+    assert_unconfigured(api.update("config-1", update(), None).await, "MockEviConfigs::update");
+    assert_unconfigured(api.delete("config-1", None).await, "MockEviConfigs::delete");
+    assert_unconfigured(api.list_versions("config-1", None, None, None).await, "MockEviConfigs::list_versions");
+    assert_unconfigured(api.get_version("config-1", 2, None).await, "MockEviConfigs::get_version");
+
+    let configs = configs
+        .on_list(|page_number, _, _| Err(answered(format!("list {:?}", page_number))))
+        .on_create(|request, _| Err(answered(format!("create {}", request.name))))
+        .on_get(|config_id, _| Err(answered(format!("get {}", config_id))))
+        .on_update(|config_id, request, _| Err(answered(format!("update {} {:?}", config_id, request.name))))
+        .on_delete(|_, _| Ok(()))
+        .on_list_versions(|config_id, _, _, _| Err(answered(format!("list_versions {}", config_id))))
+        .on_get_version(|config_id, version, _| Err(answered(format!("get_version {} {}", config_id, version))));
+
+    let api: &dyn EviConfigsApi = &configs;
+    assert_eq!(error_message(api.list(Some(1), None, None).await), "list Some(1)");
+    assert_eq!(error_message(api.create(request(), None).await), "create Support");
+    assert_eq!(error_message(api.get("config-1", None).await), "get config-1");
+    assert_eq!(
+        error_message(api.update("config-1", update(), None).await),
+        r#"update config-1 Some("Renamed")"#
+    );
+    api.delete("config-1", None).await.unwrap();
+    assert_eq!(error_message(api.list_versions("config-1", None, None, None).await), "list_versions config-1");
+    assert_eq!(error_message(api.get_version("config-1", 2, None).await), "get_version config-1 2");
+
+    assert_eq!(configs.list_calls()[1].0, Some(1));
+    assert_eq!(configs.create_calls()[1].0.name, "Support");
+    assert_eq!(configs.get_calls()[1].0, "config-1");
+    assert_eq!(configs.update_calls()[1].1.name.as_deref(), Some("Renamed"));
+    assert_eq!(configs.delete_calls()[1].0, "config-1");
+    assert_eq!(configs.list_versions_calls()[1].0, "config-1");
+    assert_eq!(configs.get_version_calls()[1].1, 2);
+}
+
+#[tokio::test]
+async fn test_mock_evi_tools() {
+    let tools = MockEviTools::new();
+    let request = || CreateToolRequestBuilder::new("lookup", "Look up an order").build();
+    let update = || UpdateToolRequest {
+        name: Some("Renamed".to_string()),
+        ..Default::default()
+    };
+
+    let api: &dyn EviToolsApi = &tools;
+    assert_unconfigured(api.list(Some(1), None, None).await, "MockEviTools::list");
+    assert_unconfigured(api.create(request(), None).await, "MockEviTools::create");
+    assert_unconfigured(api.get("tool-1", None).await, "MockEviTools::get");
+    assert_unconfigured(api.update("tool-1", update(), None).await, "MockEviTools::update");
+    assert_unconfigured(api.delete("tool-1", None).await, "MockEviTools::delete");
+    assert_unconfigured(api.list_versions("tool-1", None, None, None).await, "MockEviTools::list_versions");
+    assert_unconfigured(api.get_version("tool-1", "v2", None).await, "MockEviTools::get_version");
+
+    let tools = tools
+        .on_list(|page_number, _, _| Err(answered(format!("list {:?}", page_number))))
+        .on_create(|request, _| Err(answered(format!("create {}", request.name))))
+        .on_get(|tool_id, _| Err(answered(format!("get {}", tool_id))))
+        .on_update(|tool_id, request, _| Err(answered(format!("update {} {:?}", tool_id, request.name))))
+        .on_delete(|_, _| Ok(()))
+        .on_list_versions(|tool_id, _, _, _| Err(answered(format!("list_versions {}", tool_id))))
+        .on_get_version(|tool_id, version_id, _| Err(answered(format!("get_version {} {}", tool_id, version_id))));
+
+    let api: &dyn EviToolsApi = &tools;
+    assert_eq!(error_message(api.list(Some(1), None, None).await), "list Some(1)");
+    assert_eq!(error_message(api.create(request(), None).await), "create lookup");
+    assert_eq!(error_message(api.get("tool-1", None).await), "get tool-1");
+    assert_eq!(
+        error_message(api.update("tool-1", update(), None).await),
+        r#"update tool-1 Some("Renamed")"#
+    );
+    api.delete("tool-1", None).await.unwrap();
+    assert_eq!(error_message(api.list_versions("tool-1", None, None, None).await), "list_versions tool-1");
+    assert_eq!(error_message(api.get_version("tool-1", "v2", None).await), "get_version tool-1 v2");
+
+    assert_eq!(tools.list_calls()[1].0, Some(1));
+    assert_eq!(tools.create_calls()[1].0.name, "lookup");
+    assert_eq!(tools.get_calls()[1].0, "tool-1");
+    assert_eq!(tools.update_calls()[1].1.name.as_deref(), Some("Renamed"));
+    assert_eq!(tools.delete_calls()[1].0, "tool-1");
+    assert_eq!(tools.list_versions_calls()[1].0, "tool-1");
+    assert_eq!(tools.get_version_calls()[1].1, "v2");
+}
+
+#[tokio::test]
+async fn test_mock_evi_prompts() {
+    let prompts = MockEviPrompts::new();
+    let request = || CreatePromptRequestBuilder::new("Greeter", "Say hello").build();
+    let update = || UpdatePromptRequest {
+        name: Some("Renamed".to_string()),
+        ..Default::default()
+    };
+    let version = || CreatePromptVersionRequest {
+        text: "Say hi".to_string(),
+        version_description: None,
+    };
+
+    let api: &dyn EviPromptsApi = &prompts;
+    assert_unconfigured(api.list(Some(1), None, None).await, "MockEviPrompts::list");
+    assert_unconfigured(api.create(request(), None).await, "MockEviPrompts::create");
+    assert_unconfigured(api.get("prompt-1", None).await, "MockEviPrompts::get");
+    assert_unconfigured(api.update("prompt-1", update(), None).await, "MockEviPrompts::update");
+    assert_unconfigured(api.delete("prompt-1", None).await, "MockEviPrompts::delete");
+    assert_unconfigured(api.list_versions("prompt-1", None, None, None).await, "MockEviPrompts::list_versions");
+    assert_unconfigured(api.get_version("prompt-1", 2, None).await, "MockEviPrompts::get_version");
+    assert_unconfigured(api.create_version("prompt-1", version(), None).await, "MockEviPrompts::create_version");
+
+    let prompts = prompts
+        .on_list(|page_number, _, _| Err(answered(format!("list {:?}", page_number))))
+        .on_create(|request, _| Err(answered(format!("create {}", request.name))))
+        .on_get(|prompt_id, _| Err(answered(format!("get {}", prompt_id))))
+        .on_update(|prompt_id, request, _| Err(answered(format!("update {} {:?}", prompt_id, request.name))))
+        .on_delete(|_, _| Ok(()))
+        .on_list_versions(|prompt_id, _, _, _| Err(answered(format!("list_versions {}", prompt_id))))
+        .on_get_version(|prompt_id, version, _| Err(answered(format!("get_version {} {}", prompt_id, version))))
+        .on_create_version(|prompt_id, request, _| {
+            Err(answered(format!("create_version {} {}", prompt_id, request.text)))
+        });
+
+    let api: &dyn EviPromptsApi = &prompts;
+    assert_eq!(error_message(api.list(Some(1), None, None).await), "list Some(1)");
+    assert_eq!(error_message(api.create(request(), None).await), "create Greeter");
+    assert_eq!(error_message(api.get("prompt-1", None).await), "get prompt-1");
+    assert_eq!(
+        error_message(api.update("prompt-1", update(), None).await),
+        r#"update prompt-1 Some("Renamed")"#
+    );
+    api.delete("prompt-1", None).await.unwrap();
+    assert_eq!(error_message(api.list_versions("prompt-1", None, None, None).await), "list_versions prompt-1");
+    assert_eq!(error_message(api.get_version("prompt-1", 2, None).await), "get_version prompt-1 2");
+    assert_eq!(
+        error_message(api.create_version("prompt-1", version(), None).await),
+        "create_version prompt-1 Say hi"
+    );
+
+    assert_eq!(prompts.list_calls()[1].0, Some(1));
+    assert_eq!(prompts.create_calls()[1].0.name, "Greeter");
+    assert_eq!(prompts.get_calls()[1].0, "prompt-1");
+    assert_eq!(prompts.update_calls()[1].1.name.as_deref(), Some("Renamed"));
+    assert_eq!(prompts.delete_calls()[1].0, "prompt-1");
+    assert_eq!(prompts.list_versions_calls()[1].0, "prompt-1");
+    assert_eq!(prompts.get_version_calls()[1].1, 2);
+    assert_eq!(prompts.create_version_calls()[1].1.text, "Say hi");
+}
+
+#[tokio::test]
+async fn test_mock_evi_voices() {
+    let voices = MockEviVoices::new();
+    let request = || CreateCustomVoiceRequestBuilder::new("Narrator", "ITO").build();
+    let update = || UpdateCustomVoiceRequest {
+        name: Some("Renamed".to_string()),
+        ..Default::default()
+    };
+
+    let api: &dyn EviVoicesApi = &voices;
+    assert_unconfigured(api.list(Some(1), None, None).await, "MockEviVoices::list");
+    assert_unconfigured(api.create(request(), None).await, "MockEviVoices::create");
+    assert_unconfigured(api.get("voice-1", None).await, "MockEviVoices::get");
+    assert_unconfigured(api.update("voice-1", update(), None).await, "MockEviVoices::update");
+    assert_unconfigured(api.delete("voice-1", None).await, "MockEviVoices::delete");
+
+    let voices = voices
+        .on_list(|page_number, _, _| Err(answered(format!("list {:?}", page_number))))
+        .on_create(|request, _| Err(answered(format!("create {}", request.name))))
+        .on_get(|voice_id, _| Err(answered(format!("get {}", voice_id))))
+        .on_update(|voice_id, request, _| Err(answered(format!("update {} {:?}", voice_id, request.name))))
+        .on_delete(|_, _| Ok(()));
+
+    let api: &dyn EviVoicesApi = &voices;
+    assert_eq!(error_message(api.list(Some(1), None, None).await), "list Some(1)");
+    assert_eq!(error_message(api.create(request(), None).await), "create Narrator");
+    assert_eq!(error_message(api.get("voice-1", None).await), "get voice-1");
+    assert_eq!(
+        error_message(api.update("voice-1", update(), None).await),
+        r#"update voice-1 Some("Renamed")"#
+    );
+    api.delete("voice-1", None).await.unwrap();
+
+    assert_eq!(voices.list_calls()[1].0, Some(1));
+    assert_eq!(voices.create_calls()[1].0.name, "Narrator");
+    assert_eq!(voices.get_calls()[1].0, "voice-1");
+    assert_eq!(voices.update_calls()[1].1.name.as_deref(), Some("Renamed"));
+    assert_eq!(voices.delete_calls()[1].0, "voice-1");
+}
@@ -116,4 +116,47 @@ fn test_sample_rate() {
     // Test serialization
     let json = serde_json::to_string(&SampleRate::HZ_44100).unwrap();
     assert_eq!(json, "44100");
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_profile_voice_applies_to_requests_without_a_voice() {
+    use futures::StreamExt;
+    use hume::core::config::{ApiDefaults, TtsDefaults};
+    use hume::core::transport::{MockResponse, MockTransport};
+    use hume::HumeClientBuilder;
+    use reqwest::Method;
+
+    let transport = MockTransport::new()
+        .with_response(Method::POST, "/v0/tts/file", MockResponse::new(200).body("audio"))
+        .with_response(Method::POST, "/v0/tts/stream/file", MockResponse::new(200).body("audio"));
+    let client = HumeClientBuilder::new("test-key")
+        .transport(transport.clone())
+        .defaults(ApiDefaults {
+            tts: TtsDefaults {
+                voice: Some("Ava Song".to_string()),
+            },
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+    let tts = client.tts();
+
+    let request = TtsRequestBuilder::new()
+        .utterance("Hello")
+        .unwrap()
+        .utterance_with_voice("Hi", "Kora")
+        .unwrap()
+        .build();
+    tts.synthesize_file(request, None).await.unwrap();
+    let stream_request = TtsStreamRequest {
+        text: "Hello".to_string(),
+        ..Default::default()
+    };
+    let _ = tts.stream_file(stream_request, None).await.unwrap().next().await;
+
+    let requests = transport.requests();
+    let body: serde_json::Value = requests[0].json().unwrap();
+    assert_eq!(body["utterances"][0]["voice"]["name"], "Ava Song");
+    assert_eq!(body["utterances"][1]["voice"]["name"], "Kora");
+    let body: serde_json::Value = requests[1].json().unwrap();
+    assert_eq!(body["voice"]["name"], "Ava Song");
+}
@@ -6,7 +6,7 @@ mod tests {
         auth::Auth,
         error::Error,
         http::{HttpClient, HttpClientBuilder},
-        retry::{retry_with_backoff, RetryConfig},
+        retry::{retry_with_backoff, RetryConfig, RetryPolicyBuilder},
         request::RequestOptions,
     };
     use std::time::Duration;
@@ -47,7 +47,7 @@ mod tests {
         assert_eq!(header_value, "test-key-123");
 
         let auth = Auth::AccessToken(hume::core::auth::AuthToken {
-            access_token: "token-456".to_string(),
+            access_token: "token-456".into(),
             token_type: "Bearer".to_string(),
             expires_in: 3600,
             created_at: chrono::Utc::now(),
@@ -65,7 +65,7 @@ mod tests {
         assert_eq!(param_value, "test-key");
 
         let auth = Auth::AccessToken(hume::core::auth::AuthToken {
-            access_token: "token".to_string(),
+            access_token: "token".into(),
             token_type: "Bearer".to_string(),
             expires_in: 3600,
             created_at: chrono::Utc::now(),
@@ -80,12 +80,12 @@ mod tests {
         let attempts = Arc::new(AtomicU32::new(0));
         let attempts_clone = attempts.clone();
 
-        let config = RetryConfig {
-            max_retries: 3,
-            initial_backoff: Duration::from_millis(10),
-            max_backoff: Duration::from_secs(1),
-            backoff_multiplier: 2.0,
-        };
+        let config = RetryPolicyBuilder::new()
+            .max_retries(3)
+            .initial_interval(Duration::from_millis(10))
+            .max_interval(Duration::from_secs(1))
+            .multiplier(2.0)
+            .build();
 
         let result = retry_with_backoff(&config, || async {
             attempts_clone.fetch_add(1, Ordering::SeqCst);
@@ -103,12 +103,12 @@ mod tests {
         let attempts = Arc::new(AtomicU32::new(0));
         let attempts_clone = attempts.clone();
 
-        let config = RetryConfig {
-            max_retries: 3,
-            initial_backoff: Duration::from_millis(10),
-            max_backoff: Duration::from_secs(1),
-            backoff_multiplier: 2.0,
-        };
+        let config = RetryPolicyBuilder::new()
+            .max_retries(3)
+            .initial_interval(Duration::from_millis(10))
+            .max_interval(Duration::from_secs(1))
+            .multiplier(2.0)
+            .build();
 
         let result = retry_with_backoff(&config, || async {
             let attempt = attempts_clone.fetch_add(1, Ordering::SeqCst);
@@ -130,12 +130,12 @@ mod tests {
         let attempts = Arc::new(AtomicU32::new(0));
         let attempts_clone = attempts.clone();
 
-        let config = RetryConfig {
-            max_retries: 2,
-            initial_backoff: Duration::from_millis(10),
-            max_backoff: Duration::from_secs(1),
-            backoff_multiplier: 2.0,
-        };
+        let config = RetryPolicyBuilder::new()
+            .max_retries(2)
+            .initial_interval(Duration::from_millis(10))
+            .max_interval(Duration::from_secs(1))
+            .multiplier(2.0)
+            .build();
 
         let result = retry_with_backoff(&config, || async {
             attempts_clone.fetch_add(1, Ordering::SeqCst);
@@ -152,12 +152,12 @@ mod tests {
         let attempts = Arc::new(AtomicU32::new(0));
         let attempts_clone = attempts.clone();
 
-        let config = RetryConfig {
-            max_retries: 3,
-            initial_backoff: Duration::from_millis(10),
-            max_backoff: Duration::from_secs(1),
-            backoff_multiplier: 2.0,
-        };
+        let config = RetryPolicyBuilder::new()
+            .max_retries(3)
+            .initial_interval(Duration::from_millis(10))
+            .max_interval(Duration::from_secs(1))
+            .multiplier(2.0)
+            .build();
 
         let result = retry_with_backoff(&config, || async {
             attempts_clone.fetch_add(1, Ordering::SeqCst);
@@ -174,12 +174,12 @@ mod tests {
         let attempts = Arc::new(AtomicU32::new(0));
         let attempts_clone = attempts.clone();
 
-        let config = RetryConfig {
-            max_retries: 3,
-            initial_backoff: Duration::from_millis(10),
-            max_backoff: Duration::from_secs(1),
-            backoff_multiplier: 2.0,
-        };
+        let config = RetryPolicyBuilder::new()
+            .max_retries(3)
+            .initial_interval(Duration::from_millis(10))
+            .max_interval(Duration::from_secs(1))
+            .multiplier(2.0)
+            .build();
 
         let result = retry_with_backoff(&config, || async {
             let attempt = attempts_clone.fetch_add(1, Ordering::SeqCst);
@@ -222,12 +222,12 @@ mod tests {
 
     #[test]
     fn test_calculate_backoff() {
-        let config = RetryConfig {
-            max_retries: 3,
-            initial_backoff: Duration::from_millis(100),
-            max_backoff: Duration::from_secs(1),
-            backoff_multiplier: 2.0,
-        };
+        let config = RetryPolicyBuilder::new()
+            .max_retries(3)
+            .initial_interval(Duration::from_millis(100))
+            .max_interval(Duration::from_secs(1))
+            .multiplier(2.0)
+            .build();
 
         // Test exponential backoff calculation
         let backoff = config.calculate_backoff(0);